@@ -0,0 +1,224 @@
+//! Cap table command line tool
+//! Builds a business's cap table from RPC account dumps, exports it as CSV or
+//! JSON, prints holder proofs and checks published CapTableSnapshot commitments
+//!
+//! Account dumps are the JSON returned by the RPC with base64 encoding:
+//! - a single account: `solana account <ADDRESS> --output json`
+//! - token accounts: the `getProgramAccounts` response for the token program,
+//!   filtered on the share mint (`memcmp` at offset 0), with `withContext: true`
+//!   so the dump carries the slot it was read at
+//!
+//! Build with `cargo run -p my_program --features cli --bin captable -- <command>`
+
+use std::collections::HashMap;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token::TokenAccount;
+use base64::Engine;
+use my_program::captable::{self, Snapshot};
+use my_program::{Business, CapTableSnapshot};
+use serde_json::Value;
+
+const USAGE: &str = "\
+Usage:
+  captable snapshot --business <dump> --token-accounts <dump> [--offering <address>]... [--slot <slot>] [--format csv|json]
+  captable proof    --business <dump> --token-accounts <dump> [--offering <address>]... [--slot <slot>] --holder <address>
+  captable verify   --business <dump> --token-accounts <dump> [--offering <address>]... [--slot <slot>] --cap-table <dump>";
+
+/// An account read from a dump: (address, data)
+type DumpedAccount = (Option<Pubkey>, Vec<u8>);
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Runs a command; Ok(false) means a verification mismatch
+fn run(args: &[String]) -> Result<bool, String> {
+    let (command, options) = args.split_first().ok_or("missing command")?;
+    let options = Options::parse(options)?;
+    let snapshot = load_snapshot(&options)?;
+
+    match command.as_str() {
+        "snapshot" => {
+            match options.single("format")?.unwrap_or("csv") {
+                "csv" => print!("{}", snapshot.to_csv()),
+                "json" => println!("{}", snapshot.to_json()),
+                other => return Err(format!("unknown format '{}'", other)),
+            }
+            Ok(true)
+        }
+        "proof" => {
+            let holder = parse_pubkey(options.required("holder")?)?;
+            let proof = snapshot
+                .proof(&holder)
+                .ok_or_else(|| format!("{} holds no shares at slot {}", holder, snapshot.slot))?;
+            let shares = snapshot
+                .holders
+                .iter()
+                .find(|line| line.owner == holder)
+                .map_or(0, |line| line.shares);
+            println!("root: {}", captable::hex(&snapshot.merkle_root()));
+            println!("holder: {} ({} shares)", holder, shares);
+            for sibling in proof {
+                println!("proof: {}", captable::hex(&sibling));
+            }
+            Ok(true)
+        }
+        "verify" => {
+            let (_, data) = read_account(options.required("cap-table")?)?;
+            let published = CapTableSnapshot::try_deserialize(&mut &data[..])
+                .map_err(|error| format!("invalid CapTableSnapshot account: {}", error))?;
+
+            println!("             published / computed");
+            println!("slot:        {} / {}", published.slot, snapshot.slot);
+            println!(
+                "merkle_root: {} / {}",
+                captable::hex(&published.merkle_root),
+                captable::hex(&snapshot.merkle_root())
+            );
+            println!("holders:     {} / {}", published.holder_count, snapshot.holder_count());
+            println!(
+                "circulating: {} / {}",
+                published.circulating_shares,
+                snapshot.circulating_shares()
+            );
+
+            let matches = snapshot.matches(&published);
+            println!("{}", if matches { "✅ commitment matches" } else { "❌ commitment does not match" });
+            Ok(matches)
+        }
+        other => Err(format!("unknown command '{}'", other)),
+    }
+}
+
+/// Command line options (`--name value`, repeatable)
+struct Options(HashMap<String, Vec<String>>);
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options: HashMap<String, Vec<String>> = HashMap::new();
+        let mut args = args.iter();
+        while let Some(flag) = args.next() {
+            let name = flag
+                .strip_prefix("--")
+                .ok_or_else(|| format!("unexpected argument '{}'", flag))?;
+            let value = args.next().ok_or_else(|| format!("missing value for --{}", name))?;
+            options.entry(name.to_string()).or_default().push(value.clone());
+        }
+        Ok(Self(options))
+    }
+
+    fn all(&self, name: &str) -> &[String] {
+        self.0.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    fn single(&self, name: &str) -> Result<Option<&str>, String> {
+        match self.all(name) {
+            [] => Ok(None),
+            [value] => Ok(Some(value)),
+            _ => Err(format!("--{} given more than once", name)),
+        }
+    }
+
+    fn required(&self, name: &str) -> Result<&str, String> {
+        self.single(name)?.ok_or_else(|| format!("missing --{}", name))
+    }
+}
+
+/// Builds the snapshot described by the dump options
+fn load_snapshot(options: &Options) -> Result<Snapshot, String> {
+    let (business_key, data) = read_account(options.required("business")?)?;
+    let business_key = business_key.ok_or("business dump has no address")?;
+    let business = Business::try_deserialize(&mut &data[..])
+        .map_err(|error| format!("invalid Business account: {}", error))?;
+
+    let offerings = options
+        .all("offering")
+        .iter()
+        .map(|address| parse_pubkey(address))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (context_slot, dumped) = read_accounts(options.required("token-accounts")?)?;
+    let slot = match options.single("slot")? {
+        Some(slot) => slot.parse().map_err(|_| format!("invalid slot '{}'", slot))?,
+        None => context_slot.ok_or("token account dump has no context slot, pass --slot")?,
+    };
+
+    let mut token_accounts = Vec::with_capacity(dumped.len());
+    for (address, data) in dumped {
+        let address = address.ok_or("token account dump entry has no address")?;
+        let account = TokenAccount::try_deserialize(&mut &data[..])
+            .map_err(|error| format!("invalid token account {}: {}", address, error))?;
+        token_accounts.push((address, account));
+    }
+
+    Ok(Snapshot::build(business_key, &business, &offerings, slot, &token_accounts))
+}
+
+fn parse_pubkey(address: &str) -> Result<Pubkey, String> {
+    Pubkey::from_str(address).map_err(|_| format!("invalid address '{}'", address))
+}
+
+fn read_json(path: &str) -> Result<Value, String> {
+    let contents = std::fs::read_to_string(path).map_err(|error| format!("{}: {}", path, error))?;
+    serde_json::from_str(&contents).map_err(|error| format!("{}: {}", path, error))
+}
+
+/// Reads a single account dump (CLI output, RPC response or bare account)
+fn read_account(path: &str) -> Result<DumpedAccount, String> {
+    let json = read_json(path)?;
+    let json = json.get("result").unwrap_or(&json);
+    let json = json.get("value").unwrap_or(json);
+    decode_account(json).map_err(|error| format!("{}: {}", path, error))
+}
+
+/// Reads a list of accounts (getProgramAccounts response or bare array)
+/// Returns the context slot when the dump carries one
+fn read_accounts(path: &str) -> Result<(Option<u64>, Vec<DumpedAccount>), String> {
+    let json = read_json(path)?;
+    let json = json.get("result").unwrap_or(&json);
+    let slot = json.pointer("/context/slot").and_then(Value::as_u64);
+    let list = json
+        .get("value")
+        .unwrap_or(json)
+        .as_array()
+        .ok_or_else(|| format!("{}: expected a list of accounts", path))?;
+
+    let accounts = list
+        .iter()
+        .map(decode_account)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("{}: {}", path, error))?;
+    Ok((slot, accounts))
+}
+
+/// Decodes `{ pubkey?, account: { data: [base64, "base64"] } }` or a bare `{ data }`
+fn decode_account(json: &Value) -> Result<DumpedAccount, String> {
+    let address = json
+        .get("pubkey")
+        .and_then(Value::as_str)
+        .map(parse_pubkey)
+        .transpose()?;
+    let account = json.get("account").unwrap_or(json);
+    let data = match account.get("data") {
+        Some(Value::Array(parts)) if parts.get(1).and_then(Value::as_str) == Some("base64") => {
+            parts[0].as_str().unwrap_or_default()
+        }
+        _ => return Err("account data must be base64 encoded".to_string()),
+    };
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|error| format!("invalid base64 data: {}", error))?;
+    Ok((address, data))
+}
@@ -0,0 +1,375 @@
+//! Cap table snapshots (off-chain)
+//! Builds the ownership table of a business from its share token accounts
+//!
+//! The caller fetches every token account of `business.share_mint` at the
+//! desired slot (e.g. `getProgramAccounts` with a mint memcmp filter and
+//! `minContextSlot`) and passes them in decoded. The resulting snapshot can be
+//! exported as CSV or JSON, and its Merkle root published on-chain through
+//! `publish_cap_table`. The `captable` binary (feature `cli`) wraps this module.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::{merkle, Business, CapTableSnapshot};
+
+/// Fixed-point scale for holder fractions (percent with four decimals)
+const PERCENT_SCALE: u128 = 1_000_000;
+
+/// A single shareholder line of the cap table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Holder {
+    /// Wallet that owns the share tokens
+    pub owner: Pubkey,
+    /// Shares held across all of the owner's token accounts
+    pub shares: u64,
+}
+
+/// Ownership table of a business at a given slot
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    /// Business the snapshot belongs to
+    pub business: Pubkey,
+    /// Share mint of the business
+    pub share_mint: Pubkey,
+    /// Slot at which the token accounts were read
+    pub slot: u64,
+    /// Total shares issued (Business.total_shares)
+    pub total_shares: u64,
+    /// Shares still held by the program in `shares_vault`
+    pub shares_vault_balance: u64,
+    /// Shares still held in offering vaults
+    pub offering_vault_balance: u64,
+    /// Investors, sorted by owner key
+    pub holders: Vec<Holder>,
+}
+
+impl Snapshot {
+    /// Builds a snapshot from the token accounts of `business.share_mint`
+    /// `offerings` lists the business's Offering accounts, whose vaults are reported separately
+    /// Accounts of other mints and empty accounts are ignored
+    pub fn build(
+        business_key: Pubkey,
+        business: &Business,
+        offerings: &[Pubkey],
+        slot: u64,
+        token_accounts: &[(Pubkey, TokenAccount)],
+    ) -> Self {
+        let (shares_vault, _) = Pubkey::find_program_address(
+            &[b"shares_vault", business_key.as_ref()],
+            &crate::ID,
+        );
+        let offering_vaults: Vec<Pubkey> = offerings
+            .iter()
+            .map(|offering| {
+                Pubkey::find_program_address(&[b"offering_vault", offering.as_ref()], &crate::ID).0
+            })
+            .collect();
+
+        let mut shares_vault_balance = 0u64;
+        let mut offering_vault_balance = 0u64;
+        let mut balances: BTreeMap<Pubkey, u64> = BTreeMap::new();
+
+        for (address, account) in token_accounts {
+            if account.mint != business.share_mint || account.amount == 0 {
+                continue;
+            }
+
+            if *address == shares_vault {
+                shares_vault_balance = shares_vault_balance.saturating_add(account.amount);
+            } else if offering_vaults.contains(address) {
+                offering_vault_balance = offering_vault_balance.saturating_add(account.amount);
+            } else {
+                let balance = balances.entry(account.owner).or_default();
+                *balance = balance.saturating_add(account.amount);
+            }
+        }
+
+        Self {
+            business: business_key,
+            share_mint: business.share_mint,
+            slot,
+            total_shares: business.total_shares,
+            shares_vault_balance,
+            offering_vault_balance,
+            holders: balances
+                .into_iter()
+                .map(|(owner, shares)| Holder { owner, shares })
+                .collect(),
+        }
+    }
+
+    /// Shares held by investors (outside program vaults)
+    pub fn circulating_shares(&self) -> u64 {
+        self.holders
+            .iter()
+            .fold(0u64, |total, holder| total.saturating_add(holder.shares))
+    }
+
+    /// Number of holder lines (committed to as CapTableSnapshot.holder_count)
+    pub fn holder_count(&self) -> u32 {
+        self.holders.len() as u32
+    }
+
+    /// Merkle leaf committing to a single holder line
+    pub fn leaf(holder: &Holder) -> [u8; 32] {
+        merkle::leaf_hash(&[holder.owner.as_ref(), &holder.shares.to_le_bytes()])
+    }
+
+    /// Merkle root over all holder lines, in snapshot order
+    pub fn merkle_root(&self) -> [u8; 32] {
+        let leaves: Vec<[u8; 32]> = self.holders.iter().map(Self::leaf).collect();
+        merkle::root(&leaves)
+    }
+
+    /// Inclusion proof of `owner`'s holder line
+    /// Returns None if the owner holds no shares in the snapshot
+    pub fn proof(&self, owner: &Pubkey) -> Option<Vec<[u8; 32]>> {
+        let index = self.holders.iter().position(|holder| holder.owner == *owner)?;
+        let leaves: Vec<[u8; 32]> = self.holders.iter().map(Self::leaf).collect();
+        merkle::proof(&leaves, index)
+    }
+
+    /// Whether a published commitment matches this snapshot
+    /// (same business and slot, root, holder count and circulating shares)
+    pub fn matches(&self, published: &CapTableSnapshot) -> bool {
+        published.business == self.business
+            && published.slot == self.slot
+            && published.merkle_root == self.merkle_root()
+            && published.holder_count == self.holder_count()
+            && published.circulating_shares == self.circulating_shares()
+    }
+
+    /// Exports the snapshot as CSV (one row per holder, vaults last)
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("holder,shares,percent\n");
+        for holder in &self.holders {
+            let _ = writeln!(out, "{},{},{}", holder.owner, holder.shares, self.percent(holder.shares));
+        }
+        let _ = writeln!(
+            out,
+            "shares_vault,{},{}",
+            self.shares_vault_balance,
+            self.percent(self.shares_vault_balance)
+        );
+        let _ = writeln!(
+            out,
+            "offering_vaults,{},{}",
+            self.offering_vault_balance,
+            self.percent(self.offering_vault_balance)
+        );
+        out
+    }
+
+    /// Exports the snapshot as a JSON document
+    pub fn to_json(&self) -> String {
+        let holders = self
+            .holders
+            .iter()
+            .map(|holder| {
+                format!(
+                    "{{\"holder\":\"{}\",\"shares\":{},\"percent\":\"{}\"}}",
+                    holder.owner,
+                    holder.shares,
+                    self.percent(holder.shares)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!(
+            "{{\"business\":\"{}\",\"share_mint\":\"{}\",\"slot\":{},\"total_shares\":{},\
+             \"circulating_shares\":{},\"shares_vault\":{},\"offering_vaults\":{},\
+             \"merkle_root\":\"{}\",\"holders\":[{}]}}",
+            self.business,
+            self.share_mint,
+            self.slot,
+            self.total_shares,
+            self.circulating_shares(),
+            self.shares_vault_balance,
+            self.offering_vault_balance,
+            hex(&self.merkle_root()),
+            holders
+        )
+    }
+
+    /// Formats `shares` as a percentage of total_shares with four decimals
+    fn percent(&self, shares: u64) -> String {
+        if self.total_shares == 0 {
+            return "0.0000".to_string();
+        }
+        let scaled = shares as u128 * PERCENT_SCALE / self.total_shares as u128;
+        format!("{}.{:04}", scaled / 10_000, scaled % 10_000)
+    }
+}
+
+/// Lowercase hex encoding of `bytes`
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::with_capacity(bytes.len() * 2), |mut out, byte| {
+        let _ = write!(out, "{:02x}", byte);
+        out
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_spl::token::spl_token::state::{Account, AccountState};
+    use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+
+    fn business(share_mint: Pubkey, total_shares: u64) -> Business {
+        Business {
+            owner: Pubkey::new_unique(),
+            name: "Padaria".to_string(),
+            share_mint,
+            total_shares,
+            price_per_share_lamports: 1_000,
+            treasury: Pubkey::new_unique(),
+            is_listed: true,
+            bump: 255,
+            version: crate::BUSINESS_VERSION,
+            in_registry: false,
+            registry_page: 0,
+            registry_slot: 0,
+            category: crate::BusinessCategory::Bakery,
+            region: *b"6gkzw",
+            in_search_index: false,
+            category_index_page: 0,
+            category_index_slot: 0,
+            region_index_page: 0,
+            region_index_slot: 0,
+            listed_at: 0,
+            reserved: [0; 30],
+        }
+    }
+
+    fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> TokenAccount {
+        let mut data = [0u8; Account::LEN];
+        Account {
+            mint,
+            owner,
+            amount,
+            state: AccountState::Initialized,
+            ..Account::default()
+        }
+        .pack_into_slice(&mut data);
+        TokenAccount::try_deserialize(&mut &data[..]).unwrap()
+    }
+
+    /// Snapshot of 10_000 shares: two investors (one with two accounts),
+    /// shares_vault, one offering vault, plus noise that must be ignored
+    fn snapshot() -> (Snapshot, Pubkey, Pubkey) {
+        let business_key = Pubkey::new_unique();
+        let share_mint = Pubkey::new_unique();
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+        let offering = Pubkey::new_unique();
+        let shares_vault =
+            Pubkey::find_program_address(&[b"shares_vault", business_key.as_ref()], &crate::ID).0;
+        let offering_vault =
+            Pubkey::find_program_address(&[b"offering_vault", offering.as_ref()], &crate::ID).0;
+
+        let accounts = vec![
+            (Pubkey::new_unique(), token_account(share_mint, alice, 1_500)),
+            (Pubkey::new_unique(), token_account(share_mint, bob, 250)),
+            (Pubkey::new_unique(), token_account(share_mint, alice, 500)),
+            (shares_vault, token_account(share_mint, Pubkey::new_unique(), 6_000)),
+            (offering_vault, token_account(share_mint, offering, 1_750)),
+            (Pubkey::new_unique(), token_account(share_mint, Pubkey::new_unique(), 0)),
+            (Pubkey::new_unique(), token_account(Pubkey::new_unique(), bob, 99)),
+        ];
+        let snapshot = Snapshot::build(
+            business_key,
+            &business(share_mint, 10_000),
+            &[offering],
+            42,
+            &accounts,
+        );
+        (snapshot, alice, bob)
+    }
+
+    #[test]
+    fn build_merges_accounts_and_separates_vaults() {
+        let (snapshot, alice, bob) = snapshot();
+
+        assert_eq!(snapshot.holder_count(), 2);
+        assert_eq!(snapshot.circulating_shares(), 2_250);
+        assert_eq!(snapshot.shares_vault_balance, 6_000);
+        assert_eq!(snapshot.offering_vault_balance, 1_750);
+        assert!(snapshot.holders.contains(&Holder { owner: alice, shares: 2_000 }));
+        assert!(snapshot.holders.contains(&Holder { owner: bob, shares: 250 }));
+        assert!(snapshot.holders.windows(2).all(|pair| pair[0].owner < pair[1].owner));
+    }
+
+    #[test]
+    fn proofs_verify_against_root() {
+        let (snapshot, alice, bob) = snapshot();
+        let root = snapshot.merkle_root();
+        assert_ne!(root, [0u8; 32]);
+
+        for owner in [alice, bob] {
+            let holder = snapshot.holders.iter().find(|holder| holder.owner == owner).unwrap();
+            let proof = snapshot.proof(&owner).unwrap();
+            assert!(merkle::verify(&proof, &root, Snapshot::leaf(holder)));
+
+            // A different share count is not committed to
+            let forged = Holder { owner, shares: holder.shares + 1 };
+            assert!(!merkle::verify(&proof, &root, Snapshot::leaf(&forged)));
+        }
+        assert!(snapshot.proof(&Pubkey::new_unique()).is_none());
+    }
+
+    #[test]
+    fn matches_published_commitment() {
+        let (snapshot, _, _) = snapshot();
+        let mut published = CapTableSnapshot {
+            business: snapshot.business,
+            slot: snapshot.slot,
+            merkle_root: snapshot.merkle_root(),
+            holder_count: snapshot.holder_count(),
+            circulating_shares: snapshot.circulating_shares(),
+            published_at: 0,
+            bump: 255,
+        };
+        assert!(snapshot.matches(&published));
+
+        published.holder_count += 1;
+        assert!(!snapshot.matches(&published));
+    }
+
+    #[test]
+    fn csv_lists_holders_then_vaults() {
+        let (snapshot, _, _) = snapshot();
+        let csv = snapshot.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "holder,shares,percent");
+        assert!(lines[1..3].iter().any(|line| line.ends_with(",2000,20.0000")));
+        assert!(lines[1..3].iter().any(|line| line.ends_with(",250,2.5000")));
+        assert_eq!(lines[3], "shares_vault,6000,60.0000");
+        assert_eq!(lines[4], "offering_vaults,1750,17.5000");
+    }
+
+    #[test]
+    fn json_reports_totals_and_root() {
+        let (snapshot, alice, _) = snapshot();
+        let json = snapshot.to_json();
+
+        assert!(json.starts_with(&format!("{{\"business\":\"{}\"", snapshot.business)));
+        assert!(json.contains("\"slot\":42,\"total_shares\":10000,\"circulating_shares\":2250"));
+        assert!(json.contains(&format!("\"merkle_root\":\"{}\"", hex(&snapshot.merkle_root()))));
+        assert!(json.contains(&format!("{{\"holder\":\"{}\",\"shares\":2000,\"percent\":\"20.0000\"}}", alice)));
+    }
+
+    #[test]
+    fn percent_handles_rounding_and_zero_supply() {
+        let (mut snapshot, _, _) = snapshot();
+        snapshot.total_shares = 3;
+        assert_eq!(snapshot.percent(1), "33.3333");
+        snapshot.total_shares = 0;
+        assert_eq!(snapshot.percent(1), "0.0000");
+    }
+}
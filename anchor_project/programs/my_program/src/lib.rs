@@ -1,9 +1,21 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 use anchor_spl::associated_token::AssociatedToken;
+use validation::{TextError, TextLimit};
+use std::cell::RefMut;
+
+pub mod merkle;
+pub mod migration;
+pub mod slug;
+pub mod validation;
+#[cfg(not(target_os = "solana"))]
+pub mod captable;
 
 declare_id!("91CC3aZEnHLe7VvnE9wXwY4TPUTLR4EKfRAZYNjRPM2a");
 
+/// Denominator for values expressed in basis points (100% = 10000)
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
 /// Localshare Lite Program
 /// Local business investment sharing system
 #[program]
@@ -27,6 +39,7 @@ pub mod my_program {
 
         // Save the PDA bump for future validations
         config.bump = ctx.bumps.config;
+        config.version = CONFIG_VERSION;
 
         msg!("✅ Config initialized successfully!");
         msg!("Admin: {}", config.admin);
@@ -40,14 +53,47 @@ pub mod my_program {
     ///
     /// # Security
     /// - Each owner can only have one business (unique PDA per owner)
-    /// - Name is validated to not exceed size limit
+    /// - Name is trimmed and validated (characters, bytes, no control/invisible characters)
+    /// - Letters and digits must fold to ASCII (see `slug`), so look-alike names share a slug
     /// - Supports upsert: initializes if new, updates name if exists
+    /// - Names are unique by slug: a NameRecord is reserved for the new name
+    ///   and the previous one is released on rename
+    /// - New businesses are appended to the BusinessRegistry page passed in
+    /// - Region must be a lowercase geohash; the business is indexed in the SearchIndex
+    ///   pages of its category and region, and moved when either changes
+    /// - Businesses created before versioning must call migrate_business first:
+    ///   the account is deserialized with the current layout before the handler runs
     pub fn register_business(
         ctx: Context<RegisterBusiness>,
         name: String,
+        category: BusinessCategory,
+        region: [u8; REGION_CODE_LENGTH],
     ) -> Result<()> {
-        require!(!name.is_empty(), LocalshareError::EmptyBusinessName);
-        require!(name.len() <= 50, LocalshareError::BusinessNameTooLong);
+        let name = validation::sanitize(&name, &BUSINESS_NAME_LIMIT).map_err(|err| match err {
+            TextError::Empty => LocalshareError::EmptyBusinessName,
+            TextError::TooLong => LocalshareError::BusinessNameTooLong,
+            TextError::InvalidCharacter => LocalshareError::InvalidBusinessName,
+        })?;
+
+        require!(slug::is_foldable(&name), LocalshareError::UnsupportedBusinessName);
+        let name_slug = slug::slugify(&name);
+        require!(!name_slug.is_empty(), LocalshareError::EmptyBusinessName);
+
+        require!(validation::is_geohash(&region), LocalshareError::InvalidRegion);
+
+        // Reserve the slug (a record held by another business means the name is taken)
+        let business_key = ctx.accounts.business.key();
+        let name_record = &mut ctx.accounts.name_record;
+        if name_record.business == Pubkey::default() {
+            name_record.business = business_key;
+            name_record.slug = name_slug.clone();
+            name_record.bump = ctx.bumps.name_record;
+        }
+        require_keys_eq!(
+            name_record.business,
+            business_key,
+            LocalshareError::BusinessNameTaken
+        );
 
         let business = &mut ctx.accounts.business;
         
@@ -67,10 +113,16 @@ pub mod my_program {
             business.treasury = ctx.accounts.owner.key(); // Default to owner as treasury
             business.is_listed = false;
             business.bump = ctx.bumps.business;
+            business.version = BUSINESS_VERSION;
+            business.category = category;
+            business.region = region;
 
-            // Note: The old mint (ctx.accounts.mint) is kept for backward compatibility
-            // with the old offering flow, but the new flow uses share_mint created
-            // in init_share_mint. We don't mint shares here anymore.
+            let registry_page = ctx
+                .accounts
+                .registry_page
+                .as_ref()
+                .ok_or(LocalshareError::InvalidRegistryPage)?;
+            BusinessRegistry::append(registry_page, business_key, business)?;
             
             msg!("✅ New business registered: {}", name);
         } else {
@@ -80,10 +132,63 @@ pub mod my_program {
                 LocalshareError::InvalidBusinessOwner
             );
             
+            // Release the previous slug, refunding its rent to the owner
+            // (a business registered before name records may share a slug it never held)
+            let previous_info = ctx.accounts.previous_name_record.to_account_info();
+            if previous_info.key() != ctx.accounts.name_record.key()
+                && NameRecord::load(&previous_info)?
+                    .is_some_and(|previous| previous.business == business_key)
+            {
+                let owner_info = ctx.accounts.owner.to_account_info();
+                **owner_info.try_borrow_mut_lamports()? += previous_info.lamports();
+                **previous_info.try_borrow_mut_lamports()? = 0;
+                previous_info.assign(&anchor_lang::system_program::ID);
+                previous_info.resize(0)?;
+            }
+
             business.name = name.clone();
             msg!("✅ Business name updated: {}", name);
         }
 
+        // Index by category and region (unchanged profiles need no index pages)
+        let indexed = business.in_search_index;
+        if !indexed || business.category != category || business.region != region {
+            let category_from = indexed.then(|| {
+                (business.category.index_key(), business.category_index_page, business.category_index_slot)
+            });
+            let region_from = indexed.then(|| {
+                (SearchIndex::region_key(&business.region), business.region_index_page, business.region_index_slot)
+            });
+            business.category = category;
+            business.region = region;
+            let entry = business.index_entry(business_key);
+
+            (business.category_index_page, business.category_index_slot) = SearchIndex::reindex(
+                ctx.accounts.category_index.as_ref(),
+                ctx.accounts.previous_category_index.as_ref(),
+                IndexKind::Category,
+                category_from,
+                category.index_key(),
+                entry,
+            )?;
+            (business.region_index_page, business.region_index_slot) = SearchIndex::reindex(
+                ctx.accounts.region_index.as_ref(),
+                ctx.accounts.previous_region_index.as_ref(),
+                IndexKind::Region,
+                region_from,
+                SearchIndex::region_key(&region),
+                entry,
+            )?;
+            business.in_search_index = true;
+
+            if !is_new {
+                BusinessRegistry::sync(ctx.accounts.registry_page.as_ref(), business_key, business)?;
+            }
+        }
+
+        msg!("Slug: {}", name_slug);
+        msg!("Category: {:?}, region: {}", category, String::from_utf8_lossy(&region));
+
         Ok(())
     }
 
@@ -94,6 +199,8 @@ pub mod my_program {
     /// - Only the business owner can configure offerings
     /// - Validates that total_shares and price_per_share are greater than zero
     /// - Does NOT list the business (is_listed remains false)
+    /// - Rejects changes while the business is frozen
+    /// - Mirrors the unlisting into the business's registry and search index entries
     pub fn configure_offering(
         ctx: Context<ConfigureOffering>,
         total_shares: u64,
@@ -106,6 +213,21 @@ pub mod my_program {
         // Validation: price_per_share_lamports must be greater than zero
         require!(price_per_share_lamports > 0, LocalshareError::InvalidPrice);
 
+        // Validation: Business must not be frozen
+        require!(
+            !BusinessFreeze::is_frozen(&ctx.accounts.business_freeze)?,
+            LocalshareError::BusinessFrozen
+        );
+
+        // Validation: a program-controlled treasury cannot be swapped back out
+        let current = ctx.accounts.business.treasury;
+        let business_treasury = BusinessTreasury::address(&ctx.accounts.business.key());
+        let milestone_escrow = MilestoneEscrow::address(&ctx.accounts.business.key());
+        require!(
+            (current != business_treasury && current != milestone_escrow) || treasury == current,
+            LocalshareError::TreasuryLocked
+        );
+
         // Update business account with new offering configuration
         let business = &mut ctx.accounts.business;
         business.total_shares = total_shares;
@@ -113,6 +235,12 @@ pub mod my_program {
         business.treasury = treasury;
         // Explicitly keep is_listed as false (do not list the business yet)
         business.is_listed = false;
+        business.sync_listing(
+            business.key(),
+            ctx.accounts.registry_page.as_ref(),
+            ctx.accounts.category_index.as_ref(),
+            ctx.accounts.region_index.as_ref(),
+        )?;
 
         msg!("✅ Offering configured successfully!");
         msg!("Total shares: {}", total_shares);
@@ -177,65 +305,161 @@ pub mod my_program {
         Ok(())
     }
 
-    /// Creates a new share offering for a business
-    /// Defines quantity, price, and other share characteristics
+    /// Opens a funding round (Seed, Series A, community round, ...) for a business
+    /// Moves `shares` from the shares_vault into a dedicated round vault sold during
+    /// [start_ts, end_ts); `end_ts = 0` keeps the round open until sold out or closed
+    /// Rounds are identified by `index` and may run concurrently or one after another
+    /// `pricing` selects how the round is priced: `Fixed` sells at price_per_share_lamports,
+    /// `DutchAuction` decays linearly from start_price to floor_price between start_ts and
+    /// end_ts, and `Tiered` sells early-bird tranches at their own price before the fixed price
     /// 
     /// # Security
     /// - Only the business owner can create offerings
-    /// - Price and quantity validations
-    /// - Payment mint comes from global config
+    /// - Requires that the share mint is initialized (init_share_mint)
+    /// - One offering per business per index (unique PDA)
+    /// - Price, quantity and window validations
+    /// - Rejected while the business is frozen
+    /// - Pricing is validated and fixed for the life of the round; non-fixed modes require the pricing account
+    #[allow(clippy::too_many_arguments)]
     pub fn create_offering(
         ctx: Context<CreateOffering>,
-        price_per_share: u64,
-        initial_shares: u64,
+        index: u32,
+        round_name: String,
+        price_per_share_lamports: u64,
+        shares: u64,
+        start_ts: i64,
+        end_ts: i64,
+        pricing: PricingMode,
     ) -> Result<()> {
-        require!(price_per_share > 0, LocalshareError::InvalidPrice);
-        require!(initial_shares > 0, LocalshareError::InvalidShareAmount);
-        price_per_share.checked_mul(initial_shares).ok_or(LocalshareError::MathOverflow)?;
+        let round_name = validation::sanitize(&round_name, &ROUND_NAME_LIMIT)
+            .map_err(|_| LocalshareError::InvalidRound)?;
+        require!(end_ts == 0 || end_ts > start_ts, LocalshareError::InvalidRound);
+        require!(price_per_share_lamports > 0, LocalshareError::InvalidPrice);
+        require!(shares > 0, LocalshareError::InvalidShareAmount);
+        price_per_share_lamports.checked_mul(shares).ok_or(LocalshareError::MathOverflow)?;
+        pricing.validate()?;
+
+        // Validation: Business must not be frozen
+        require!(
+            !BusinessFreeze::is_frozen(&ctx.accounts.business_freeze)?,
+            LocalshareError::BusinessFrozen
+        );
 
         require!(
-            ctx.accounts.owner_token_account.amount >= initial_shares,
+            ctx.accounts.shares_vault.amount >= shares,
             LocalshareError::InsufficientShares
         );
 
+        // Move the offered shares out of the business vault
+        let business_key = ctx.accounts.business.key();
+        let seeds = &[
+            b"share_mint_authority",
+            business_key.as_ref(),
+            &[ctx.bumps.share_mint_authority],
+        ];
+        let signer = &[&seeds[..]];
+
         token::transfer(
-            CpiContext::new(
+            CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 token::Transfer {
-                    from: ctx.accounts.owner_token_account.to_account_info(),
+                    from: ctx.accounts.shares_vault.to_account_info(),
                     to: ctx.accounts.offering_vault.to_account_info(),
-                    authority: ctx.accounts.owner.to_account_info(),
+                    authority: ctx.accounts.share_mint_authority.to_account_info(),
                 },
+                signer,
             ),
-            initial_shares,
+            shares,
         )?;
 
         let offering = &mut ctx.accounts.offering;
-        let business = &ctx.accounts.business;
-        let config = &ctx.accounts.config;
-        offering.business = business.key();
-        offering.share_mint = business.share_mint;
-        offering.payment_mint = config.payment_mint;
-        offering.price_per_share = price_per_share;
-        offering.remaining_shares = initial_shares;
+        offering.version = OFFERING_VERSION;
+        offering.business = business_key;
+        offering.share_mint = ctx.accounts.share_mint.key();
+        offering.index = index;
+        offering.round_name = round_name;
+        offering.price_per_share_lamports = price_per_share_lamports;
+        offering.total_shares = shares;
+        offering.remaining_shares = shares;
+        offering.start_ts = start_ts;
+        offering.end_ts = end_ts;
+        offering.raised_lamports = 0;
         offering.is_active = true;
         offering.bump = ctx.bumps.offering;
 
+        // Fixed-price rounds don't need a pricing account
+        let offering_key = offering.key();
+        match (ctx.accounts.pricing.as_mut(), ctx.bumps.pricing) {
+            (Some(account), Some(bump)) => account.set_inner(OfferingPricing {
+                offering: offering_key,
+                mode: pricing,
+                bump,
+            }),
+            _ => require!(pricing == PricingMode::Fixed, LocalshareError::InvalidPricingMode),
+        }
+
+        let stats = &mut ctx.accounts.business_stats;
+        stats.business = business_key;
+        stats.rounds_opened = stats
+            .rounds_opened
+            .checked_add(1)
+            .ok_or(LocalshareError::MathOverflow)?;
+        stats.bump = ctx.bumps.business_stats;
+
+        msg!("✅ Round #{} ({}) created!", index, ctx.accounts.offering.round_name);
+        msg!("Shares offered: {}", shares);
+        msg!("Price per share: {} lamports", price_per_share_lamports);
+        match pricing {
+            PricingMode::Fixed => msg!("Fixed price"),
+            PricingMode::DutchAuction { start_price, floor_price, start_ts, end_ts } => {
+                msg!("Dutch auction: {} -> {} lamports", start_price, floor_price);
+                msg!("Window: {} -> {}", start_ts, end_ts);
+            }
+            PricingMode::Tiered { tier_count, .. } => msg!("Tiered pricing: {} tiers", tier_count),
+        }
+
         Ok(())
     }
 
-    /// Allows investors to buy shares from an offering
-    /// Performs fund transfer and mints shares to the buyer
+    /// Allows investors to buy shares from an offering (the only primary sale path)
+    /// Transfers SOL from buyer to treasury and share tokens from the offering vault to buyer
+    /// Price comes from the offering's pricing mode (fixed price, Dutch auction or tiers)
     ///
     /// # Security
     /// - Validates that the offering is active
+    /// - Rejects purchases while the business is suspended
+    /// - Rejects purchases while the business is frozen
     /// - Validates share availability
-    /// - Atomic SOL transfer and token minting via CPI
+    /// - `max_price_per_share_lamports` caps the price of every share bought: with tiers,
+    ///   the purchase is rejected if any share falls in a tier priced above it
+    /// - Atomic SOL transfer and token transfer via CPI
     /// - Automatically deactivates offering when exhausted
-    pub fn buy_shares_from_offering(ctx: Context<BuyShares>, amount: u64) -> Result<()> {
+    /// - Writes an immutable PurchaseReceipt when the buyer passes one
+    pub fn buy_shares_from_offering(
+        ctx: Context<BuyShares>,
+        amount: u64,
+        max_price_per_share_lamports: u64,
+    ) -> Result<()> {
         
         // Validation: Offering must be active
         require!(ctx.accounts.offering.is_active, LocalshareError::OfferingNotActive);
+
+        // Validation: Business must not be suspended
+        require!(
+            BusinessVerification::status_of(&ctx.accounts.verification)?
+                != VerificationStatus::Suspended,
+            LocalshareError::BusinessSuspended
+        );
+
+        // Validation: Business must not be frozen
+        require!(
+            !BusinessFreeze::is_frozen(&ctx.accounts.business_freeze)?,
+            LocalshareError::BusinessFrozen
+        );
+
+        // Validation: Round window must be open
+        let now = Clock::get()?.unix_timestamp;
+        require!(ctx.accounts.offering.is_open(now), LocalshareError::RoundNotOpen);
         
         // Validation: Amount must be greater than zero
         require!(amount > 0, LocalshareError::InvalidShareAmount);
@@ -249,23 +473,37 @@ pub mod my_program {
             ctx.accounts.offering_vault.amount >= amount,
             LocalshareError::InsufficientShares
         );
-        
-        // Calculate total cost with overflow protection
-        let total_cost = ctx.accounts.offering
-            .price_per_share
-            .checked_mul(amount)
-            .ok_or(LocalshareError::MathOverflow)?;
+
+        // Calculate total cost (fixed unless the round has a pricing mode)
+        let offering = &ctx.accounts.offering;
+        let mode = OfferingPricing::load(&ctx.accounts.pricing)?
+            .map_or(PricingMode::Fixed, |pricing| pricing.mode);
+        let quote = mode.quote(
+            now,
+            offering.price_per_share_lamports,
+            offering.total_shares.saturating_sub(offering.remaining_shares),
+            amount,
+        )?;
+        let total_cost = quote.total_lamports;
+        let price_per_share = total_cost / amount;
+
+        // Validation: no share may cost more than what the buyer agreed to pay
+        require!(
+            quote.max_price_per_share <= max_price_per_share_lamports,
+            LocalshareError::PriceAboveMax
+        );
         
         msg!("💰 Processing purchase of {} shares", amount);
+        msg!("Price per share: {} lamports", price_per_share);
         msg!("Total cost: {} lamports", total_cost);
         
-        // Transfer SOL from buyer to business owner
+        // Transfer SOL from buyer to treasury
         anchor_lang::system_program::transfer(
             CpiContext::new(
                 ctx.accounts.system_program.to_account_info(),
                 anchor_lang::system_program::Transfer {
                     from: ctx.accounts.buyer.to_account_info(),
-                    to: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
                 },
             ),
             total_cost,
@@ -273,35 +511,53 @@ pub mod my_program {
 
         let offering_bump = ctx.accounts.offering.bump;
         let business_key = ctx.accounts.business.key();
-        let mint_key = ctx.accounts.mint.key();
+        let offering_index = ctx.accounts.offering.index.to_le_bytes();
         token::transfer(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 token::Transfer {
                     from: ctx.accounts.offering_vault.to_account_info(),
-                    to: ctx.accounts.buyer_token_account.to_account_info(),
+                    to: ctx.accounts.buyer_shares_ata.to_account_info(),
                     authority: ctx.accounts.offering.to_account_info(),
                 },
                 &[&[
                     b"offering",
                     business_key.as_ref(),
-                    mint_key.as_ref(),
+                    offering_index.as_ref(),
                     &[offering_bump],
                 ]],
             ),
             amount,
         )?;
 
-        // Update remaining shares count
+        // Update remaining shares count and round/business totals
         let offering = &mut ctx.accounts.offering;
         offering.remaining_shares = offering
             .remaining_shares
             .checked_sub(amount)
             .ok_or(LocalshareError::MathOverflow)?;
+        offering.raised_lamports = offering
+            .raised_lamports
+            .checked_add(total_cost)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        let stats = &mut ctx.accounts.business_stats;
+        stats.total_raised_lamports = stats
+            .total_raised_lamports
+            .checked_add(total_cost)
+            .ok_or(LocalshareError::MathOverflow)?;
+        stats.total_shares_sold = stats
+            .total_shares_sold
+            .checked_add(amount)
+            .ok_or(LocalshareError::MathOverflow)?;
 
         // If no more shares, deactivate offering automatically
         if offering.remaining_shares == 0 {
             offering.is_active = false;
+            stats.rounds_completed = stats
+                .rounds_completed
+                .checked_add(1)
+                .ok_or(LocalshareError::MathOverflow)?;
             msg!("🔒 Offering exhausted and deactivated automatically");
         }
 
@@ -309,10 +565,301 @@ pub mod my_program {
         msg!("Buyer: {}", ctx.accounts.buyer.key());
         msg!("Shares transferred: {}", amount);
         msg!("Remaining shares: {}", offering.remaining_shares);
+
+        let offering_key = offering.key();
+        let sequence = ctx.accounts.position.purchase_count;
+        ctx.accounts.position.record(
+            business_key,
+            ctx.accounts.buyer.key(),
+            amount,
+            total_cost,
+            now,
+            ctx.bumps.position,
+        )?;
+
+        if let (Some(receipt), Some(bump)) = (ctx.accounts.receipt.as_mut(), ctx.bumps.receipt) {
+            receipt.set_inner(PurchaseReceipt {
+                buyer: ctx.accounts.buyer.key(),
+                business: business_key,
+                offering: offering_key,
+                shares: amount,
+                price_per_share_lamports: price_per_share,
+                total_lamports: total_cost,
+                slot: Clock::get()?.slot,
+                timestamp: now,
+                sequence,
+                bump,
+            });
+            msg!("🧾 Receipt #{} recorded", sequence);
+        }
+
+        // Remember escrowed contributions so failed milestones can be refunded
+        if ctx.accounts.treasury.key() == MilestoneEscrow::address(&business_key) {
+            ctx.accounts.position.add_escrowed(total_cost)?;
+            MilestoneEscrow::record_contribution(&ctx.accounts.treasury, total_cost)?;
+        }
+
+        emit!(SharesPurchased {
+            business: business_key,
+            buyer: ctx.accounts.buyer.key(),
+            amount_shares: amount,
+            price_per_share_lamports: price_per_share,
+            total_cost_lamports: total_cost,
+            timestamp: now,
+        });
         
         Ok(())
     }
 
+    /// Closes an offering and returns its unsold shares to the shares_vault
+    /// The owner can end a round early; once a round's end_ts has passed anyone can close it,
+    /// so rounds that run out of time are settled too
+    /// A round that was still active counts as completed
+    ///
+    /// # Security
+    /// - Only the business owner can close offerings before their end_ts
+    /// - Shares go back to the PDA-controlled shares_vault, never to the signer
+    pub fn close_offering(ctx: Context<CloseOffering>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let offering = &ctx.accounts.offering;
+        let ended = offering.end_ts != 0 && now >= offering.end_ts;
+        require!(
+            ended || ctx.accounts.authority.key() == ctx.accounts.business.owner,
+            LocalshareError::InvalidBusinessOwner
+        );
+
+        let unsold = ctx.accounts.offering_vault.amount;
+
+        if unsold > 0 {
+            let offering_bump = ctx.accounts.offering.bump;
+            let business_key = ctx.accounts.business.key();
+            let offering_index = ctx.accounts.offering.index.to_le_bytes();
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.offering_vault.to_account_info(),
+                        to: ctx.accounts.shares_vault.to_account_info(),
+                        authority: ctx.accounts.offering.to_account_info(),
+                    },
+                    &[&[
+                        b"offering",
+                        business_key.as_ref(),
+                        offering_index.as_ref(),
+                        &[offering_bump],
+                    ]],
+                ),
+                unsold,
+            )?;
+        }
+
+        let offering = &mut ctx.accounts.offering;
+        if offering.is_active {
+            let stats = &mut ctx.accounts.business_stats;
+            stats.rounds_completed = stats
+                .rounds_completed
+                .checked_add(1)
+                .ok_or(LocalshareError::MathOverflow)?;
+        }
+        offering.remaining_shares = 0;
+        offering.is_active = false;
+
+        msg!("✅ Offering #{} closed", offering.index);
+        msg!("Unsold shares returned to vault: {}", unsold);
+
+        Ok(())
+    }
+
+    /// Migrates a legacy offering (created before offerings were indexed) to the current model
+    /// Creates offering `index` with the legacy price and remaining amount funded from the
+    /// shares_vault, burns the unsold legacy-mint tokens and closes the legacy accounts
+    /// Legacy-mint tokens held by investors are backed one for one by shares moved into the
+    /// legacy swap vault, which holders redeem with swap_legacy_shares
+    ///
+    /// # Security
+    /// - Only the business owner can migrate
+    /// - Rejected while the business is frozen
+    /// - Legacy account is identified by its PDA, discriminator and size
+    /// - Unsold legacy tokens are burned, never handed to the owner
+    pub fn migrate_legacy_offering(ctx: Context<MigrateLegacyOffering>, index: u32) -> Result<()> {
+        let legacy = LegacyOffering::load(&ctx.accounts.legacy_offering)?;
+        require_keys_eq!(
+            legacy.business,
+            ctx.accounts.business.key(),
+            LocalshareError::InvalidLegacyOffering
+        );
+
+        // Validation: Business must not be frozen
+        require!(
+            !BusinessFreeze::is_frozen(&ctx.accounts.business_freeze)?,
+            LocalshareError::BusinessFrozen
+        );
+
+        let unsold_legacy = ctx.accounts.legacy_vault.amount;
+        let (shares, circulating_legacy) =
+            legacy.migration_shares(ctx.accounts.legacy_mint.supply, unsold_legacy)?;
+        require!(
+            ctx.accounts.shares_vault.amount
+                >= shares
+                    .checked_add(circulating_legacy)
+                    .ok_or(LocalshareError::MathOverflow)?,
+            LocalshareError::InsufficientShares
+        );
+
+        let business_key = ctx.accounts.business.key();
+        let seeds = &[
+            b"share_mint_authority",
+            business_key.as_ref(),
+            &[ctx.bumps.share_mint_authority],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Fund the new offering and the legacy swap vault from the business vault
+        for (destination, amount) in [
+            (ctx.accounts.offering_vault.to_account_info(), shares),
+            (ctx.accounts.legacy_swap_vault.to_account_info(), circulating_legacy),
+        ] {
+            if amount > 0 {
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        token::Transfer {
+                            from: ctx.accounts.shares_vault.to_account_info(),
+                            to: destination,
+                            authority: ctx.accounts.share_mint_authority.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    amount,
+                )?;
+            }
+        }
+
+        // Burn the unsold legacy-mint tokens and close the legacy vault
+        let legacy_mint_key = ctx.accounts.legacy_mint.key();
+        let legacy_seeds = &[
+            b"offering",
+            business_key.as_ref(),
+            legacy_mint_key.as_ref(),
+            &[ctx.bumps.legacy_offering],
+        ];
+        let legacy_signer = &[&legacy_seeds[..]];
+
+        if unsold_legacy > 0 {
+            token::burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Burn {
+                        mint: ctx.accounts.legacy_mint.to_account_info(),
+                        from: ctx.accounts.legacy_vault.to_account_info(),
+                        authority: ctx.accounts.legacy_offering.to_account_info(),
+                    },
+                    legacy_signer,
+                ),
+                unsold_legacy,
+            )?;
+        }
+
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::CloseAccount {
+                account: ctx.accounts.legacy_vault.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: ctx.accounts.legacy_offering.to_account_info(),
+            },
+            legacy_signer,
+        ))?;
+
+        // Close the legacy offering account, refunding its rent to the owner
+        let legacy_info = ctx.accounts.legacy_offering.to_account_info();
+        let owner_info = ctx.accounts.owner.to_account_info();
+        **owner_info.try_borrow_mut_lamports()? += legacy_info.lamports();
+        **legacy_info.try_borrow_mut_lamports()? = 0;
+        legacy_info.assign(&anchor_lang::system_program::ID);
+        legacy_info.resize(0)?;
+
+        let offering = &mut ctx.accounts.offering;
+        offering.version = OFFERING_VERSION;
+        offering.business = business_key;
+        offering.share_mint = ctx.accounts.share_mint.key();
+        offering.index = index;
+        offering.round_name = LEGACY_ROUND_NAME.to_string();
+        offering.price_per_share_lamports = legacy.price_per_share;
+        offering.total_shares = shares;
+        offering.remaining_shares = shares;
+        offering.start_ts = 0;
+        offering.end_ts = 0;
+        offering.raised_lamports = 0;
+        offering.is_active = shares > 0;
+        offering.bump = ctx.bumps.offering;
+
+        let stats = &mut ctx.accounts.business_stats;
+        stats.business = business_key;
+        stats.rounds_opened = stats
+            .rounds_opened
+            .checked_add(1)
+            .ok_or(LocalshareError::MathOverflow)?;
+        stats.bump = ctx.bumps.business_stats;
+
+        msg!("✅ Legacy offering migrated to offering #{}", index);
+        msg!("Shares offered: {}", shares);
+        msg!("Price per share: {} lamports", offering.price_per_share_lamports);
+        msg!("Legacy tokens burned: {}", unsold_legacy);
+        msg!("Shares reserved for legacy holders: {}", circulating_legacy);
+
+        Ok(())
+    }
+
+    /// Swaps legacy-mint tokens for shares of the business, one for one
+    /// Burns the holder's legacy tokens and pays the shares out of the legacy swap
+    /// vault funded by migrate_legacy_offering
+    ///
+    /// # Security
+    /// - Only the holder can swap their tokens (they sign the burn)
+    /// - The swap vault only holds the legacy supply that was in circulation at migration
+    pub fn swap_legacy_shares(ctx: Context<SwapLegacyShares>, amount: u64) -> Result<()> {
+        LegacyOffering::check_swap(ctx.accounts.legacy_swap_vault.amount, amount)?;
+
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Burn {
+                    mint: ctx.accounts.legacy_mint.to_account_info(),
+                    from: ctx.accounts.holder_legacy_ata.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let business_key = ctx.accounts.business.key();
+        let seeds = &[
+            b"share_mint_authority",
+            business_key.as_ref(),
+            &[ctx.bumps.share_mint_authority],
+        ];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.legacy_swap_vault.to_account_info(),
+                    to: ctx.accounts.holder_shares_ata.to_account_info(),
+                    authority: ctx.accounts.share_mint_authority.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        msg!("✅ Legacy shares swapped!");
+        msg!("Holder: {}", ctx.accounts.holder.key());
+        msg!("Shares: {}", amount);
+
+        Ok(())
+    }
+
     /// Lists a business on the marketplace
     /// Sets is_listed to true, making the business available for investment
     /// 
@@ -322,6 +869,10 @@ pub mod my_program {
     /// - Requires that price_per_share_lamports > 0
     /// - Requires that share_mint is initialized (not default)
     /// - Prevents double-listing (returns error if already listed)
+    /// - Rejects suspended businesses, and unverified ones when the policy requires it
+    /// - Rejects frozen businesses
+    /// - Mirrors the listing into the business's registry and search index entries
+    /// - Records the first listing time, from which reporting becomes due
     pub fn list_business(ctx: Context<ListBusiness>) -> Result<()> {
         let business = &mut ctx.accounts.business;
         
@@ -348,9 +899,37 @@ pub mod my_program {
             !business.is_listed,
             LocalshareError::BusinessAlreadyListed
         );
+
+        // Validation: Business must not be frozen
+        require!(
+            !BusinessFreeze::is_frozen(&ctx.accounts.business_freeze)?,
+            LocalshareError::BusinessFrozen
+        );
+
+        // Validation: suspended businesses can't list; verification may be required
+        let status = BusinessVerification::status_of(&ctx.accounts.verification)?;
+        require!(
+            status != VerificationStatus::Suspended,
+            LocalshareError::BusinessSuspended
+        );
+        if VerificationPolicy::requires_verified_listing(&ctx.accounts.verification_policy)? {
+            require!(
+                status == VerificationStatus::Verified,
+                LocalshareError::BusinessNotVerified
+            );
+        }
         
-        // Set business as listed
+        // Set business as listed (relisting keeps the first listing time)
         business.is_listed = true;
+        if business.listed_at == 0 {
+            business.listed_at = Clock::get()?.unix_timestamp;
+        }
+        business.sync_listing(
+            business.key(),
+            ctx.accounts.registry_page.as_ref(),
+            ctx.accounts.category_index.as_ref(),
+            ctx.accounts.region_index.as_ref(),
+        )?;
         
         msg!("✅ Business listed successfully!");
         msg!("Business: {}", business.name);
@@ -360,558 +939,7832 @@ pub mod my_program {
         Ok(())
     }
 
-    /// Allows investors to buy shares directly from a listed business
-    /// Transfers SOL from buyer to treasury and transfers share tokens from vault to buyer
+    /// Publishes a cap table snapshot commitment for a business
+    /// Stores the Merkle root of the off-chain cap table (see `captable` module)
+    /// so holders can prove their line against what the owner published
     ///
     /// # Security
-    /// - Requires that the business is listed (is_listed == true)
-    /// - Validates share availability in vault
-    /// - Atomic SOL transfer and token transfer via CPI
-    /// - Overflow protection for price calculations
-    pub fn buy_shares(ctx: Context<BuySharesFromBusiness>, amount_shares: u64) -> Result<()> {
-        let business = &ctx.accounts.business;
-        
-        // Validation: Business must be listed
+    /// - Only the business owner can publish snapshots
+    /// - One snapshot per business per slot (unique PDA)
+    /// - Slot cannot be in the future and circulating shares cannot exceed total_shares
+    pub fn publish_cap_table(
+        ctx: Context<PublishCapTable>,
+        slot: u64,
+        merkle_root: [u8; 32],
+        holder_count: u32,
+        circulating_shares: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        // Validation: snapshot must describe a slot that already happened
+        require!(slot <= clock.slot, LocalshareError::InvalidSnapshot);
+
+        // Validation: an empty root commits to nothing
+        require!(merkle_root != [0u8; 32], LocalshareError::InvalidSnapshot);
+
+        // Validation: investors cannot hold more than what was issued
         require!(
-            business.is_listed,
-            LocalshareError::OfferingNotActive // Reusing error, or could create new one
+            circulating_shares <= ctx.accounts.business.total_shares,
+            LocalshareError::InvalidSnapshot
         );
-        
-        // Validation: Amount must be greater than zero
+
+        // Validation: every holder line holds at least one share
         require!(
-            amount_shares > 0,
-            LocalshareError::InvalidShareAmount
+            holder_count > 0 && holder_count as u64 <= circulating_shares,
+            LocalshareError::InvalidSnapshot
         );
-        
-        // Validation: There must be enough shares in the vault
+
+        let snapshot = &mut ctx.accounts.cap_table_snapshot;
+        snapshot.business = ctx.accounts.business.key();
+        snapshot.slot = slot;
+        snapshot.merkle_root = merkle_root;
+        snapshot.holder_count = holder_count;
+        snapshot.circulating_shares = circulating_shares;
+        snapshot.published_at = clock.unix_timestamp;
+        snapshot.bump = ctx.bumps.cap_table_snapshot;
+
+        msg!("✅ Cap table snapshot published!");
+        msg!("Business: {}", snapshot.business);
+        msg!("Slot: {}", slot);
+        msg!("Holders: {}", holder_count);
+        msg!("Circulating shares: {}", circulating_shares);
+
+        Ok(())
+    }
+
+    /// Creates a Merkle-root based share airdrop for a business
+    /// Moves `total_amount` shares from the shares_vault into a dedicated airdrop vault
+    /// Claimants are committed to by `merkle_root` over (claimant, amount) leaves
+    /// Claims are accepted until `expiry_ts`; close_airdrop then returns the unclaimed shares
+    ///
+    /// # Security
+    /// - Only the business owner can create airdrops
+    /// - One airdrop per business per Merkle root (unique PDA)
+    /// - Shares leave the vault via the share_mint_authority PDA signature
+    pub fn create_share_airdrop(
+        ctx: Context<CreateShareAirdrop>,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        expiry_ts: i64,
+    ) -> Result<()> {
+        // Validation: an empty root commits to nothing
+        require!(merkle_root != [0u8; 32], LocalshareError::InvalidMerkleProof);
+
+        // Validation: amount must be greater than zero
+        require!(total_amount > 0, LocalshareError::InvalidShareAmount);
+
+        // Validation: claimants need time to claim
+        let now = Clock::get()?.unix_timestamp;
+        require!(expiry_ts > now, LocalshareError::AirdropExpired);
+
+        // Validation: there must be enough shares in the vault
         require!(
-            ctx.accounts.shares_vault.amount >= amount_shares,
+            ctx.accounts.shares_vault.amount >= total_amount,
             LocalshareError::InsufficientShares
         );
-        
-        // Calculate total cost with overflow protection
-        let amount_lamports = business
-            .price_per_share_lamports
-            .checked_mul(amount_shares)
-            .ok_or(LocalshareError::MathOverflow)?;
-        
-        msg!("💰 Processing purchase of {} shares", amount_shares);
-        msg!("Price per share: {} lamports", business.price_per_share_lamports);
-        msg!("Total cost: {} lamports", amount_lamports);
-        
-        // Transfer SOL from buyer to treasury
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.buyer.to_account_info(),
-                    to: ctx.accounts.treasury.to_account_info(),
-                },
-            ),
-            amount_lamports,
-        )?;
-        
-        msg!("✅ SOL transferred to treasury: {} lamports", amount_lamports);
-        
-        // Transfer share tokens from shares_vault to buyer_shares_ata
-        // The shares_vault authority is share_mint_authority, so we sign with that PDA
-        let business_key = business.key();
+
+        // Move the airdropped shares out of the sale vault
+        let business_key = ctx.accounts.business.key();
         let seeds = &[
             b"share_mint_authority",
             business_key.as_ref(),
             &[ctx.bumps.share_mint_authority],
         ];
         let signer = &[&seeds[..]];
-        
+
         token::transfer(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 token::Transfer {
                     from: ctx.accounts.shares_vault.to_account_info(),
-                    to: ctx.accounts.buyer_shares_ata.to_account_info(),
+                    to: ctx.accounts.airdrop_vault.to_account_info(),
                     authority: ctx.accounts.share_mint_authority.to_account_info(),
                 },
                 signer,
             ),
-            amount_shares,
+            total_amount,
         )?;
-        
-        msg!("✅ Shares transferred to buyer: {}", amount_shares);
-        msg!("✅ Purchase completed successfully!");
-        msg!("Buyer: {}", ctx.accounts.buyer.key());
-        msg!("Treasury: {}", ctx.accounts.treasury.key());
-        msg!("Remaining shares in vault: {}", ctx.accounts.shares_vault.amount - amount_shares);
-        
+
+        let airdrop = &mut ctx.accounts.airdrop;
+        airdrop.business = business_key;
+        airdrop.share_mint = ctx.accounts.share_mint.key();
+        airdrop.merkle_root = merkle_root;
+        airdrop.total_amount = total_amount;
+        airdrop.claimed_amount = 0;
+        airdrop.num_claims = 0;
+        airdrop.created_at = now;
+        airdrop.expiry_ts = expiry_ts;
+        airdrop.bump = ctx.bumps.airdrop;
+
+        msg!("✅ Share airdrop created!");
+        msg!("Airdrop: {}", airdrop.key());
+        msg!("Total shares: {}", total_amount);
+        msg!("Claimable until: {}", expiry_ts);
+
         Ok(())
     }
-}
 
-// ============================================================================
-// Account State Structs
-// ============================================================================
+    /// Claims shares from an airdrop
+    /// Verifies the claimant's (claimant, amount) leaf against the airdrop Merkle root
+    ///
+    /// # Security
+    /// - Proof must match the committed Merkle root
+    /// - Each claimant can claim only once (ClaimReceipt PDA per claimant)
+    /// - Total claims can never exceed the airdropped amount
+    /// - No claims after expiry_ts
+    pub fn claim_airdrop(
+        ctx: Context<ClaimAirdrop>,
+        proof: Vec<[u8; 32]>,
+        amount: u64,
+    ) -> Result<()> {
+        // Validation: amount must be greater than zero
+        require!(amount > 0, LocalshareError::InvalidShareAmount);
 
-/// Global protocol configuration
-/// PDA: ["config"]
-#[account]
-pub struct Config {
-    /// Protocol administrator (can update configurations)
-    pub admin: Pubkey,
-    
-    /// Token mint used for payments (e.g., USDC)
-    pub payment_mint: Pubkey,
-    
-    /// PDA bump seed
-    pub bump: u8,
-}
+        // Validation: airdrop must not have expired
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < ctx.accounts.airdrop.expiry_ts, LocalshareError::AirdropExpired);
 
-/// Represents a registered business in the protocol
-/// PDA: ["business", owner.key()]
+        // Validation: (claimant, amount) must be part of the airdrop
+        let claimant_key = ctx.accounts.claimant.key();
+        let leaf = merkle::leaf_hash(&[claimant_key.as_ref(), &amount.to_le_bytes()]);
+        require!(
+            merkle::verify(&proof, &ctx.accounts.airdrop.merkle_root, leaf),
+            LocalshareError::InvalidMerkleProof
+        );
+
+        // Validation: airdrop must still hold enough shares
+        let claimed_amount = ctx.accounts.airdrop
+            .claimed_amount
+            .checked_add(amount)
+            .ok_or(LocalshareError::MathOverflow)?;
+        require!(
+            claimed_amount <= ctx.accounts.airdrop.total_amount,
+            LocalshareError::AirdropExhausted
+        );
+
+        // Transfer shares from the airdrop vault, signed by the airdrop PDA
+        let business_key = ctx.accounts.airdrop.business;
+        let merkle_root = ctx.accounts.airdrop.merkle_root;
+        let airdrop_bump = ctx.accounts.airdrop.bump;
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.airdrop_vault.to_account_info(),
+                    to: ctx.accounts.claimant_shares_ata.to_account_info(),
+                    authority: ctx.accounts.airdrop.to_account_info(),
+                },
+                &[&[
+                    b"airdrop",
+                    business_key.as_ref(),
+                    merkle_root.as_ref(),
+                    &[airdrop_bump],
+                ]],
+            ),
+            amount,
+        )?;
+
+        let airdrop = &mut ctx.accounts.airdrop;
+        airdrop.claimed_amount = claimed_amount;
+        airdrop.num_claims = airdrop
+            .num_claims
+            .checked_add(1)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        // Record the claim so it cannot be replayed
+        let receipt = &mut ctx.accounts.claim_receipt;
+        receipt.airdrop = airdrop.key();
+        receipt.claimant = claimant_key;
+        receipt.amount = amount;
+        receipt.claimed_at = now;
+        receipt.bump = ctx.bumps.claim_receipt;
+
+        msg!("✅ Airdrop claimed!");
+        msg!("Claimant: {}", claimant_key);
+        msg!("Shares: {}", amount);
+
+        Ok(())
+    }
+
+    /// Closes an airdrop after expiry (or once fully claimed)
+    /// Returns the unclaimed shares to the shares_vault and the account rent to the owner
+    /// Claim receipts are kept, so a new airdrop with the same root can't be claimed twice
+    ///
+    /// # Security
+    /// - Only the business owner can close the airdrop
+    /// - Cannot close while claimants can still claim
+    /// - Shares go back to the PDA-controlled shares_vault, never to the owner
+    pub fn close_airdrop(ctx: Context<CloseAirdrop>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let airdrop = &ctx.accounts.airdrop;
+
+        require!(
+            now >= airdrop.expiry_ts || airdrop.claimed_amount == airdrop.total_amount,
+            LocalshareError::AirdropActive
+        );
+
+        let unclaimed = ctx.accounts.airdrop_vault.amount;
+        let business_key = airdrop.business;
+        let merkle_root = airdrop.merkle_root;
+        let airdrop_bump = airdrop.bump;
+        let signer: &[&[&[u8]]] = &[&[
+            b"airdrop",
+            business_key.as_ref(),
+            merkle_root.as_ref(),
+            &[airdrop_bump],
+        ]];
+
+        if unclaimed > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.airdrop_vault.to_account_info(),
+                        to: ctx.accounts.shares_vault.to_account_info(),
+                        authority: ctx.accounts.airdrop.to_account_info(),
+                    },
+                    signer,
+                ),
+                unclaimed,
+            )?;
+        }
+
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::CloseAccount {
+                account: ctx.accounts.airdrop_vault.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: ctx.accounts.airdrop.to_account_info(),
+            },
+            signer,
+        ))?;
+
+        msg!("✅ Airdrop closed");
+        msg!("Claims: {} ({} shares)", ctx.accounts.airdrop.num_claims, ctx.accounts.airdrop.claimed_amount);
+        msg!("Unclaimed shares returned to vault: {}", unclaimed);
+
+        Ok(())
+    }
+
+    /// Creates a vesting schedule for a founder or employee
+    /// Moves `amount` shares from the shares_vault into a vesting vault that unlocks
+    /// linearly from `start_ts` over `duration_seconds`, with nothing unlocked before the cliff
+    ///
+    /// # Security
+    /// - Only the business owner can create vesting schedules
+    /// - One schedule per beneficiary per business (unique PDA)
+    /// - Cliff cannot be longer than the vesting duration
+    pub fn create_vesting(
+        ctx: Context<CreateVesting>,
+        beneficiary: Pubkey,
+        amount: u64,
+        start_ts: i64,
+        cliff_seconds: i64,
+        duration_seconds: i64,
+        revocable: bool,
+    ) -> Result<()> {
+        // Validation: amount must be greater than zero
+        require!(amount > 0, LocalshareError::InvalidShareAmount);
+
+        // Validation: schedule must have a positive duration and a cliff within it
+        require!(duration_seconds > 0, LocalshareError::InvalidVestingSchedule);
+        require!(
+            cliff_seconds >= 0 && cliff_seconds <= duration_seconds,
+            LocalshareError::InvalidVestingSchedule
+        );
+        let cliff_ts = start_ts
+            .checked_add(cliff_seconds)
+            .ok_or(LocalshareError::MathOverflow)?;
+        let end_ts = start_ts
+            .checked_add(duration_seconds)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        // Validation: there must be enough shares in the vault
+        require!(
+            ctx.accounts.shares_vault.amount >= amount,
+            LocalshareError::InsufficientShares
+        );
+
+        // Move the granted shares into the vesting vault
+        let business_key = ctx.accounts.business.key();
+        let seeds = &[
+            b"share_mint_authority",
+            business_key.as_ref(),
+            &[ctx.bumps.share_mint_authority],
+        ];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.shares_vault.to_account_info(),
+                    to: ctx.accounts.vesting_vault.to_account_info(),
+                    authority: ctx.accounts.share_mint_authority.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        let vesting = &mut ctx.accounts.vesting;
+        vesting.business = business_key;
+        vesting.beneficiary = beneficiary;
+        vesting.share_mint = ctx.accounts.share_mint.key();
+        vesting.total_amount = amount;
+        vesting.released_amount = 0;
+        vesting.start_ts = start_ts;
+        vesting.cliff_ts = cliff_ts;
+        vesting.end_ts = end_ts;
+        vesting.revocable = revocable;
+        vesting.revoked = false;
+        vesting.bump = ctx.bumps.vesting;
+
+        msg!("✅ Vesting schedule created!");
+        msg!("Beneficiary: {}", beneficiary);
+        msg!("Shares: {}", amount);
+        msg!("Cliff: {} | End: {}", cliff_ts, end_ts);
+
+        Ok(())
+    }
+
+    /// Releases the vested and not yet released shares to the beneficiary
+    ///
+    /// # Security
+    /// - Only the beneficiary can release their shares
+    /// - Unlocked amount is computed from the on-chain Clock
+    pub fn release_vested(ctx: Context<ReleaseVested>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let vesting = &ctx.accounts.vesting;
+
+        let releasable = vesting
+            .vested_amount(now)?
+            .checked_sub(vesting.released_amount)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        // Validation: something must have unlocked since the last release
+        require!(releasable > 0, LocalshareError::NothingToRelease);
+
+        let business_key = vesting.business;
+        let beneficiary_key = vesting.beneficiary;
+        let vesting_bump = vesting.bump;
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.vesting_vault.to_account_info(),
+                    to: ctx.accounts.beneficiary_shares_ata.to_account_info(),
+                    authority: ctx.accounts.vesting.to_account_info(),
+                },
+                &[&[
+                    b"vesting",
+                    business_key.as_ref(),
+                    beneficiary_key.as_ref(),
+                    &[vesting_bump],
+                ]],
+            ),
+            releasable,
+        )?;
+
+        let vesting = &mut ctx.accounts.vesting;
+        vesting.released_amount = vesting
+            .released_amount
+            .checked_add(releasable)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        msg!("✅ Vested shares released: {}", releasable);
+        msg!("Released so far: {} / {}", vesting.released_amount, vesting.total_amount);
+
+        Ok(())
+    }
+
+    /// Revokes the unvested portion of a vesting schedule
+    /// Unvested shares return to the shares_vault; already vested shares stay releasable
+    ///
+    /// # Security
+    /// - Only the business owner can revoke
+    /// - Schedule must have been created as revocable and not already revoked
+    pub fn revoke_vesting(ctx: Context<RevokeVesting>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let vesting = &ctx.accounts.vesting;
+
+        // Validation: schedule must allow revocation
+        require!(vesting.revocable, LocalshareError::VestingNotRevocable);
+        require!(!vesting.revoked, LocalshareError::VestingNotRevocable);
+
+        let vested = vesting.vested_amount(now)?;
+        let unvested = vesting
+            .total_amount
+            .checked_sub(vested)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        if unvested > 0 {
+            let business_key = vesting.business;
+            let beneficiary_key = vesting.beneficiary;
+            let vesting_bump = vesting.bump;
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.vesting_vault.to_account_info(),
+                        to: ctx.accounts.shares_vault.to_account_info(),
+                        authority: ctx.accounts.vesting.to_account_info(),
+                    },
+                    &[&[
+                        b"vesting",
+                        business_key.as_ref(),
+                        beneficiary_key.as_ref(),
+                        &[vesting_bump],
+                    ]],
+                ),
+                unvested,
+            )?;
+        }
+
+        // Freeze the schedule at what has vested so far
+        let vesting = &mut ctx.accounts.vesting;
+        vesting.total_amount = vested;
+        vesting.revoked = true;
+
+        msg!("✅ Vesting revoked!");
+        msg!("Beneficiary: {}", vesting.beneficiary);
+        msg!("Shares returned to vault: {}", unvested);
+
+        Ok(())
+    }
+
+    /// Configures shareholder governance rules for a business
+    /// Sets quorum, approval threshold, and minimum shares required to create proposals
+    ///
+    /// # Security
+    /// - Only the business owner can configure governance
+    /// - Quorum and approval thresholds are expressed in basis points (max 10000)
+    /// - The approval threshold must be a strict majority (above 5000); 10000 requires unanimity
+    /// - Rules are copied into each proposal at creation, so changes never affect open votes
+    pub fn configure_governance(
+        ctx: Context<ConfigureGovernance>,
+        quorum_bps: u16,
+        approval_threshold_bps: u16,
+        proposal_threshold: u64,
+    ) -> Result<()> {
+        // Validation: thresholds must be valid percentages
+        require!(
+            quorum_bps > 0 && quorum_bps <= BPS_DENOMINATOR,
+            LocalshareError::InvalidGovernanceConfig
+        );
+        require!(
+            (BPS_DENOMINATOR / 2 + 1..=BPS_DENOMINATOR).contains(&approval_threshold_bps),
+            LocalshareError::InvalidGovernanceConfig
+        );
+
+        let governance = &mut ctx.accounts.governance;
+        governance.business = ctx.accounts.business.key();
+        governance.quorum_bps = quorum_bps;
+        governance.approval_threshold_bps = approval_threshold_bps;
+        governance.proposal_threshold = proposal_threshold;
+        governance.bump = ctx.bumps.governance;
+
+        msg!("✅ Governance configured!");
+        msg!("Quorum: {} bps", quorum_bps);
+        msg!("Approval threshold: {} bps", approval_threshold_bps);
+        msg!("Proposal threshold: {} shares", proposal_threshold);
+
+        Ok(())
+    }
+
+    /// Creates a governance proposal for a business
+    /// Voting opens immediately and lasts `voting_period_seconds`
+    ///
+    /// # Security
+    /// - The business owner can always propose
+    /// - Other holders must hold at least `proposal_threshold` shares
+    /// - Title and description URI are validated against their size limits
+    /// - Quorum is taken from circulating supply: unsold shares in the business vault and in
+    ///   the offering vaults (passed as (offering, offering_vault) remaining account pairs) don't count
+    pub fn create_proposal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateProposal<'info>>,
+        title: String,
+        description_uri: String,
+        voting_period_seconds: i64,
+    ) -> Result<()> {
+        let title = validation::sanitize(&title, &PROPOSAL_TITLE_LIMIT)
+            .map_err(|_| LocalshareError::InvalidProposal)?;
+        let description_uri = validation::sanitize(&description_uri, &DESCRIPTION_URI_LIMIT)
+            .map_err(|_| LocalshareError::InvalidProposal)?;
+        require!(voting_period_seconds > 0, LocalshareError::InvalidProposal);
+
+        // Validation: non-owners must hold enough shares to propose
+        let business = &ctx.accounts.business;
+        let governance = &ctx.accounts.governance;
+        let proposer_key = ctx.accounts.proposer.key();
+        if proposer_key != business.owner {
+            let proposer_shares = ctx
+                .accounts
+                .proposer_shares_ata
+                .as_ref()
+                .map(|ata| ata.amount)
+                .unwrap_or(0);
+            require!(
+                proposer_shares >= governance.proposal_threshold,
+                LocalshareError::InsufficientVotingPower
+            );
+        }
+
+        // Copy quorum rules so later configuration changes don't affect this vote
+        let circulating = ctx.accounts.circulating_supply(ctx.remaining_accounts)?;
+        let quorum_votes = (circulating as u128)
+            .checked_mul(governance.quorum_bps as u128)
+            .ok_or(LocalshareError::MathOverflow)?
+            / BPS_DENOMINATOR as u128;
+
+        let now = Clock::get()?.unix_timestamp;
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.business = business.key();
+        proposal.proposer = proposer_key;
+        proposal.id = governance.proposal_count;
+        proposal.title = title;
+        proposal.description_uri = description_uri;
+        proposal.voting_start = now;
+        proposal.voting_end = now
+            .checked_add(voting_period_seconds)
+            .ok_or(LocalshareError::MathOverflow)?;
+        proposal.quorum_votes = quorum_votes as u64;
+        proposal.approval_threshold_bps = governance.approval_threshold_bps;
+        proposal.yes_votes = 0;
+        proposal.no_votes = 0;
+        proposal.status = ProposalStatus::Active;
+        proposal.bump = ctx.bumps.proposal;
+
+        let governance = &mut ctx.accounts.governance;
+        governance.proposal_count = governance
+            .proposal_count
+            .checked_add(1)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        msg!("✅ Proposal created!");
+        msg!("Proposal #{}: {}", proposal.id, proposal.title);
+        msg!("Voting ends at: {}", proposal.voting_end);
+
+        Ok(())
+    }
+
+    /// Casts a token-weighted vote on a proposal
+    /// The voting shares are locked in the proposal vote vault until voting ends,
+    /// so the same tokens cannot be moved to another wallet and vote again
+    ///
+    /// # Security
+    /// - Voting window is enforced with the on-chain Clock
+    /// - One vote per wallet per proposal (VoteRecord PDA)
+    /// - Vote weight equals the shares locked
+    pub fn cast_vote(ctx: Context<CastVote>, approve: bool, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let proposal = &ctx.accounts.proposal;
+
+        // Validation: voting window must be open
+        require!(
+            proposal.status == ProposalStatus::Active && now < proposal.voting_end,
+            LocalshareError::VotingClosed
+        );
+
+        // Validation: vote must carry weight
+        require!(amount > 0, LocalshareError::InsufficientVotingPower);
+        require!(
+            ctx.accounts.voter_shares_ata.amount >= amount,
+            LocalshareError::InsufficientVotingPower
+        );
+
+        // Lock the voting shares in the vote vault
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.voter_shares_ata.to_account_info(),
+                    to: ctx.accounts.vote_vault.to_account_info(),
+                    authority: ctx.accounts.voter.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        if approve {
+            proposal.yes_votes = proposal
+                .yes_votes
+                .checked_add(amount)
+                .ok_or(LocalshareError::MathOverflow)?;
+        } else {
+            proposal.no_votes = proposal
+                .no_votes
+                .checked_add(amount)
+                .ok_or(LocalshareError::MathOverflow)?;
+        }
+
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.proposal = proposal.key();
+        vote_record.voter = ctx.accounts.voter.key();
+        vote_record.approve = approve;
+        vote_record.weight = amount;
+        vote_record.bump = ctx.bumps.vote_record;
+
+        msg!("🗳️ Vote cast on proposal #{}", proposal.id);
+        msg!("Voter: {}", vote_record.voter);
+        msg!("Approve: {} | Weight: {}", approve, amount);
+
+        Ok(())
+    }
+
+    /// Tallies a proposal after its voting window has closed
+    /// Permissionless: anyone can finalize once voting has ended
+    ///
+    /// # Security
+    /// - Can only run after voting_end and only once
+    /// - Succeeds only if quorum is reached and yes votes meet the approval threshold
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let proposal = &mut ctx.accounts.proposal;
+
+        // Validation: voting must be over and the proposal not yet finalized
+        require!(proposal.status == ProposalStatus::Active, LocalshareError::ProposalFinalized);
+        require!(now >= proposal.voting_end, LocalshareError::VotingStillOpen);
+
+        proposal.status = proposal.tally()?;
+
+        msg!("✅ Proposal #{} finalized", proposal.id);
+        msg!("Yes: {} | No: {} | Quorum: {}", proposal.yes_votes, proposal.no_votes, proposal.quorum_votes);
+        msg!("Succeeded: {}", proposal.status == ProposalStatus::Succeeded);
+
+        Ok(())
+    }
+
+    /// Returns the shares locked by a vote once voting has ended
+    /// Closes the vote record and refunds its rent to the voter
+    ///
+    /// # Security
+    /// - Only the voter can withdraw their own locked shares
+    /// - Shares stay locked until voting_end
+    pub fn withdraw_vote_tokens(ctx: Context<WithdrawVoteTokens>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let proposal = &ctx.accounts.proposal;
+
+        // Validation: shares stay locked while voting is open
+        require!(now >= proposal.voting_end, LocalshareError::VotingStillOpen);
+
+        let business_key = proposal.business;
+        let proposal_id = proposal.id.to_le_bytes();
+        let proposal_bump = proposal.bump;
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.vote_vault.to_account_info(),
+                    to: ctx.accounts.voter_shares_ata.to_account_info(),
+                    authority: ctx.accounts.proposal.to_account_info(),
+                },
+                &[&[
+                    b"proposal",
+                    business_key.as_ref(),
+                    proposal_id.as_ref(),
+                    &[proposal_bump],
+                ]],
+            ),
+            ctx.accounts.vote_record.weight,
+        )?;
+
+        msg!("✅ Vote tokens withdrawn: {}", ctx.accounts.vote_record.weight);
+
+        Ok(())
+    }
+
+    /// Opens a fair-launch subscription for an oversubscribed offering
+    /// Moves `shares_offered` from the shares_vault into a subscription vault;
+    /// investors commit SOL during [start_ts, end_ts] and shares are allocated pro-rata after close
+    ///
+    /// # Security
+    /// - Only the business owner can open a subscription
+    /// - One open subscription per business (unique PDA)
+    /// - Price, amount and window are validated
+    /// - Rejected while the business is frozen
+    pub fn create_subscription(
+        ctx: Context<CreateSubscription>,
+        shares_offered: u64,
+        price_per_share_lamports: u64,
+        start_ts: i64,
+        end_ts: i64,
+    ) -> Result<()> {
+        require!(shares_offered > 0, LocalshareError::InvalidShareAmount);
+        require!(price_per_share_lamports > 0, LocalshareError::InvalidPrice);
+        require!(end_ts > start_ts, LocalshareError::InvalidSubscription);
+        shares_offered
+            .checked_mul(price_per_share_lamports)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        // Validation: Business must not be frozen
+        require!(
+            !BusinessFreeze::is_frozen(&ctx.accounts.business_freeze)?,
+            LocalshareError::BusinessFrozen
+        );
+
+        // Validation: there must be enough shares in the vault
+        require!(
+            ctx.accounts.shares_vault.amount >= shares_offered,
+            LocalshareError::InsufficientShares
+        );
+
+        // Move the offered shares out of the sale vault
+        let business_key = ctx.accounts.business.key();
+        let seeds = &[
+            b"share_mint_authority",
+            business_key.as_ref(),
+            &[ctx.bumps.share_mint_authority],
+        ];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.shares_vault.to_account_info(),
+                    to: ctx.accounts.subscription_vault.to_account_info(),
+                    authority: ctx.accounts.share_mint_authority.to_account_info(),
+                },
+                signer,
+            ),
+            shares_offered,
+        )?;
+
+        let subscription = &mut ctx.accounts.subscription;
+        subscription.business = business_key;
+        subscription.share_mint = ctx.accounts.share_mint.key();
+        subscription.price_per_share_lamports = price_per_share_lamports;
+        subscription.shares_offered = shares_offered;
+        subscription.start_ts = start_ts;
+        subscription.end_ts = end_ts;
+        subscription.total_committed_lamports = 0;
+        subscription.shares_demanded = 0;
+        subscription.commitment_count = 0;
+        subscription.claimed_count = 0;
+        subscription.settled = false;
+        subscription.bump = ctx.bumps.subscription;
+
+        msg!("✅ Subscription opened!");
+        msg!("Shares offered: {}", shares_offered);
+        msg!("Price per share: {} lamports", price_per_share_lamports);
+        msg!("Window: {} -> {}", start_ts, end_ts);
+
+        Ok(())
+    }
+
+    /// Commits SOL to an open subscription
+    /// Lamports are escrowed in the subscription PDA until the investor claims
+    ///
+    /// # Security
+    /// - Only accepted during the subscription window
+    /// - Rejected while the business is suspended or frozen
+    /// - Commitment must cover at least one share
+    /// - Repeated commits accumulate in the investor's commitment account
+    pub fn commit_subscription(ctx: Context<CommitSubscription>, amount_lamports: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let subscription = &ctx.accounts.subscription;
+
+        // Validation: subscription window must be open
+        require!(
+            now >= subscription.start_ts && now < subscription.end_ts,
+            LocalshareError::SubscriptionClosed
+        );
+
+        // Validation: Business must not be suspended
+        require!(
+            BusinessVerification::status_of(&ctx.accounts.verification)?
+                != VerificationStatus::Suspended,
+            LocalshareError::BusinessSuspended
+        );
+
+        // Validation: Business must not be frozen
+        require!(
+            !BusinessFreeze::is_frozen(&ctx.accounts.business_freeze)?,
+            LocalshareError::BusinessFrozen
+        );
+
+        // Validation: commitment must buy at least one share
+        require!(
+            amount_lamports >= subscription.price_per_share_lamports,
+            LocalshareError::InvalidShareAmount
+        );
+
+        // Escrow the lamports in the subscription PDA
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.investor.to_account_info(),
+                    to: ctx.accounts.subscription.to_account_info(),
+                },
+            ),
+            amount_lamports,
+        )?;
+
+        let commitment = &mut ctx.accounts.commitment;
+        let is_new = commitment.investor == Pubkey::default();
+        if is_new {
+            commitment.subscription = ctx.accounts.subscription.key();
+            commitment.investor = ctx.accounts.investor.key();
+            commitment.claimed = false;
+            commitment.bump = ctx.bumps.commitment;
+        }
+        commitment.committed_lamports = commitment
+            .committed_lamports
+            .checked_add(amount_lamports)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        let subscription = &mut ctx.accounts.subscription;
+        subscription.total_committed_lamports = subscription
+            .total_committed_lamports
+            .checked_add(amount_lamports)
+            .ok_or(LocalshareError::MathOverflow)?;
+        if is_new {
+            subscription.commitment_count = subscription
+                .commitment_count
+                .checked_add(1)
+                .ok_or(LocalshareError::MathOverflow)?;
+        }
+
+        msg!("✅ Committed {} lamports", amount_lamports);
+        msg!("Investor total: {} lamports", commitment.committed_lamports);
+        msg!("Subscription total: {} lamports", subscription.total_committed_lamports);
+
+        Ok(())
+    }
+
+    /// Settles a subscription after its window closes
+    /// Permissionless: fixes total demand so allocations can be claimed, and
+    /// returns unsubscribed shares to the shares_vault when demand is below supply
+    ///
+    /// # Security
+    /// - Can only run after end_ts and only once
+    pub fn settle_subscription(ctx: Context<SettleSubscription>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let subscription = &ctx.accounts.subscription;
+
+        // Validation: window must be closed and subscription not yet settled
+        require!(now >= subscription.end_ts, LocalshareError::SubscriptionOpen);
+        require!(!subscription.settled, LocalshareError::SubscriptionSettled);
+
+        let shares_demanded = subscription.total_committed_lamports / subscription.price_per_share_lamports;
+        let unsubscribed = subscription.shares_offered.saturating_sub(shares_demanded);
+
+        // Return shares nobody asked for to the sale vault
+        if unsubscribed > 0 {
+            let business_key = subscription.business;
+            let subscription_bump = subscription.bump;
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.subscription_vault.to_account_info(),
+                        to: ctx.accounts.shares_vault.to_account_info(),
+                        authority: ctx.accounts.subscription.to_account_info(),
+                    },
+                    &[&[b"subscription", business_key.as_ref(), &[subscription_bump]]],
+                ),
+                unsubscribed,
+            )?;
+        }
+
+        let subscription = &mut ctx.accounts.subscription;
+        subscription.shares_demanded = shares_demanded;
+        subscription.settled = true;
+
+        msg!("✅ Subscription settled!");
+        msg!("Shares demanded: {} / offered: {}", shares_demanded, subscription.shares_offered);
+        msg!("Shares returned to vault: {}", unsubscribed);
+
+        Ok(())
+    }
+
+    /// Claims an investor's allocation from a settled subscription
+    /// Transfers the allocated shares, pays their cost to the treasury and refunds the rest
+    /// Allocations stay claimable after the claim deadline until refunded with
+    /// refund_unclaimed_commitment
+    ///
+    /// # Security
+    /// - Subscription must be settled
+    /// - Each commitment can be claimed once (the commitment is closed, rent to the investor)
+    /// - Rejected while the business is suspended or frozen
+    /// - Allocation is pro-rata to commitment when oversubscribed (rounded down)
+    /// - Shares and proceeds are added to the BusinessStats totals
+    pub fn claim_allocation(ctx: Context<ClaimAllocation>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let subscription = &ctx.accounts.subscription;
+        let commitment = &ctx.accounts.commitment;
+
+        // Validation: allocations are only known after settlement
+        require!(subscription.settled, LocalshareError::SubscriptionNotSettled);
+        require!(!commitment.claimed, LocalshareError::AllocationClaimed);
+
+        // Validation: Business must not be suspended
+        require!(
+            BusinessVerification::status_of(&ctx.accounts.verification)?
+                != VerificationStatus::Suspended,
+            LocalshareError::BusinessSuspended
+        );
+
+        // Validation: Business must not be frozen
+        require!(
+            !BusinessFreeze::is_frozen(&ctx.accounts.business_freeze)?,
+            LocalshareError::BusinessFrozen
+        );
+
+        let allocation = subscription.allocation_for(commitment.committed_lamports)?;
+        let cost = allocation
+            .checked_mul(subscription.price_per_share_lamports)
+            .ok_or(LocalshareError::MathOverflow)?;
+        let refund = commitment
+            .committed_lamports
+            .checked_sub(cost)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        if allocation > 0 {
+            let business_key = subscription.business;
+            let subscription_bump = subscription.bump;
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.subscription_vault.to_account_info(),
+                        to: ctx.accounts.investor_shares_ata.to_account_info(),
+                        authority: ctx.accounts.subscription.to_account_info(),
+                    },
+                    &[&[b"subscription", business_key.as_ref(), &[subscription_bump]]],
+                ),
+                allocation,
+            )?;
+        }
+
+        // Pay out escrowed lamports: cost to treasury, excess back to the investor
+        let subscription_info = ctx.accounts.subscription.to_account_info();
+        **subscription_info.try_borrow_mut_lamports()? -= cost + refund;
+        **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += cost;
+        **ctx.accounts.investor.to_account_info().try_borrow_mut_lamports()? += refund;
+
+        ctx.accounts.commitment.claimed = true;
+        let subscription = &mut ctx.accounts.subscription;
+        subscription.claimed_count = subscription
+            .claimed_count
+            .checked_add(1)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        // Subscription purchases are recorded in the investor's position like offering purchases
+        let business_key = ctx.accounts.business.key();
+        if allocation > 0 {
+            ctx.accounts.position.record(
+                business_key,
+                ctx.accounts.investor.key(),
+                allocation,
+                cost,
+                now,
+                ctx.bumps.position,
+            )?;
+        }
+
+        // Remember escrowed contributions so failed milestones can be refunded
+        if ctx.accounts.treasury.key() == MilestoneEscrow::address(&business_key) {
+            ctx.accounts.position.add_escrowed(cost)?;
+            MilestoneEscrow::record_contribution(&ctx.accounts.treasury, cost)?;
+        }
+
+        // Subscription sales count towards the business totals like offering sales
+        let stats = &mut ctx.accounts.business_stats;
+        stats.business = ctx.accounts.business.key();
+        stats.total_raised_lamports = stats
+            .total_raised_lamports
+            .checked_add(cost)
+            .ok_or(LocalshareError::MathOverflow)?;
+        stats.total_shares_sold = stats
+            .total_shares_sold
+            .checked_add(allocation)
+            .ok_or(LocalshareError::MathOverflow)?;
+        stats.bump = ctx.bumps.business_stats;
+
+        msg!("✅ Allocation claimed!");
+        msg!("Shares: {}", allocation);
+        msg!("Paid to treasury: {} lamports", cost);
+        msg!("Refunded: {} lamports", refund);
+
+        Ok(())
+    }
+
+    /// Refunds a commitment that was not claimed by the subscription's claim deadline
+    /// Permissionless: the whole commitment goes back to the investor and the commitment
+    /// is closed, so the owner can close the subscription; the unclaimed shares stay in
+    /// the subscription vault and return to the shares_vault on close
+    ///
+    /// # Security
+    /// - Only after Subscription::claim_deadline
+    /// - Lamports can only go to the investor who committed them
+    pub fn refund_unclaimed_commitment(ctx: Context<RefundUnclaimedCommitment>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let subscription = &ctx.accounts.subscription;
+
+        // Validation: investors keep the claim period to take their allocation
+        require!(subscription.settled, LocalshareError::SubscriptionNotSettled);
+        require!(
+            now >= subscription.claim_deadline()?,
+            LocalshareError::ClaimPeriodOpen
+        );
+        require!(!ctx.accounts.commitment.claimed, LocalshareError::AllocationClaimed);
+
+        let refund = ctx.accounts.commitment.committed_lamports;
+        **ctx.accounts.subscription.to_account_info().try_borrow_mut_lamports()? -= refund;
+        **ctx.accounts.investor.to_account_info().try_borrow_mut_lamports()? += refund;
+
+        let subscription = &mut ctx.accounts.subscription;
+        subscription.claimed_count = subscription
+            .claimed_count
+            .checked_add(1)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        msg!("✅ Unclaimed commitment refunded!");
+        msg!("Investor: {}", ctx.accounts.investor.key());
+        msg!("Refunded: {} lamports", refund);
+
+        Ok(())
+    }
+
+    /// Closes a subscription once every commitment was claimed or refunded
+    /// Returns leftover shares (rounding and refunded allocations) to the shares_vault
+    /// and the account rent to the owner
+    /// After the claim deadline, unclaimed commitments are refunded with
+    /// refund_unclaimed_commitment before closing
+    ///
+    /// # Security
+    /// - Only the business owner can close
+    /// - Every commitment must have been claimed or refunded first
+    pub fn close_subscription(ctx: Context<CloseSubscription>) -> Result<()> {
+        let subscription = &ctx.accounts.subscription;
+
+        // Validation: nobody can still be owed shares or lamports
+        require!(subscription.settled, LocalshareError::SubscriptionNotSettled);
+        require!(
+            subscription.claimed_count == subscription.commitment_count,
+            LocalshareError::AllocationsPending
+        );
+
+        let leftover = ctx.accounts.subscription_vault.amount;
+        let business_key = subscription.business;
+        let subscription_bump = subscription.bump;
+        let signer: &[&[&[u8]]] = &[&[b"subscription", business_key.as_ref(), &[subscription_bump]]];
+
+        if leftover > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.subscription_vault.to_account_info(),
+                        to: ctx.accounts.shares_vault.to_account_info(),
+                        authority: ctx.accounts.subscription.to_account_info(),
+                    },
+                    signer,
+                ),
+                leftover,
+            )?;
+        }
+
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::CloseAccount {
+                account: ctx.accounts.subscription_vault.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: ctx.accounts.subscription.to_account_info(),
+            },
+            signer,
+        ))?;
+
+        msg!("✅ Subscription closed!");
+        msg!("Leftover shares returned to vault: {}", leftover);
+
+        Ok(())
+    }
+
+    /// Opens a buyback: the business offers to repurchase up to `max_shares` at a fixed price
+    /// The escrow (price * max_shares) is funded from the treasury and held in the buyback PDA
+    /// Sold shares go back to the shares_vault, or are burned when `burn` is set
+    ///
+    /// # Security
+    /// - Only the business owner can open a buyback
+    /// - A wallet treasury must sign; a BusinessTreasury only funds the escrow through a
+    ///   withdrawal request naming the buyback, subject to the delay and rate limit
+    /// - One buyback per business at a time (unique PDA)
+    /// - Escrow is fully funded upfront, so every accepted sale can be paid
+    pub fn create_buyback(
+        ctx: Context<CreateBuyback>,
+        price_per_share_lamports: u64,
+        max_shares: u64,
+        expiry_ts: i64,
+        burn: bool,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(price_per_share_lamports > 0, LocalshareError::InvalidPrice);
+        require!(max_shares > 0, LocalshareError::InvalidShareAmount);
+        require!(expiry_ts > now, LocalshareError::InvalidBuyback);
+
+        let escrow = price_per_share_lamports
+            .checked_mul(max_shares)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        // Fund the escrow from the treasury
+        let business_key = ctx.accounts.business.key();
+        let treasury_info = ctx.accounts.treasury.to_account_info();
+        if treasury_info.key() == BusinessTreasury::address(&business_key) {
+            let buyback_key = ctx.accounts.buyback.key();
+            let (Some(business_treasury), Some(withdrawal)) = (
+                ctx.accounts.business_treasury.as_mut(),
+                ctx.accounts.withdrawal.as_ref(),
+            ) else {
+                return err!(LocalshareError::InvalidWithdrawal);
+            };
+
+            // The escrow is a withdrawal like any other: announced, delayed and rate limited
+            require!(
+                withdrawal.treasury == business_treasury.key()
+                    && withdrawal.recipient == buyback_key
+                    && withdrawal.amount == escrow,
+                LocalshareError::InvalidWithdrawal
+            );
+            require!(now >= withdrawal.executable_at, LocalshareError::WithdrawalLocked);
+            business_treasury.record_withdrawal(now, escrow)?;
+
+            require!(
+                escrow <= BusinessTreasury::available_lamports(&treasury_info)?,
+                LocalshareError::InsufficientTreasuryFunds
+            );
+            **treasury_info.try_borrow_mut_lamports()? -= escrow;
+            **ctx.accounts.buyback.to_account_info().try_borrow_mut_lamports()? += escrow;
+
+            emit!(TreasuryWithdrawalExecuted {
+                business: business_key,
+                id: withdrawal.id,
+                recipient: buyback_key,
+                amount: escrow,
+                timestamp: now,
+            });
+        } else {
+            require!(treasury_info.is_signer, LocalshareError::InvalidBusiness);
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: treasury_info,
+                        to: ctx.accounts.buyback.to_account_info(),
+                    },
+                ),
+                escrow,
+            )?;
+        }
+
+        let buyback = &mut ctx.accounts.buyback;
+        buyback.business = business_key;
+        buyback.treasury = ctx.accounts.treasury.key();
+        buyback.price_per_share_lamports = price_per_share_lamports;
+        buyback.max_shares = max_shares;
+        buyback.shares_bought = 0;
+        buyback.escrow_lamports = escrow;
+        buyback.expiry_ts = expiry_ts;
+        buyback.burn = burn;
+        buyback.bump = ctx.bumps.buyback;
+
+        msg!("✅ Buyback opened!");
+        msg!("Price per share: {} lamports", price_per_share_lamports);
+        msg!("Max shares: {}", max_shares);
+        msg!("Escrow: {} lamports", escrow);
+
+        Ok(())
+    }
+
+    /// Sells shares to an open buyback at the posted price
+    /// Shares go to the shares_vault (or are burned) and lamports are paid from the escrow
+    ///
+    /// # Security
+    /// - Rejects sales after expiry or beyond max_shares
+    /// - Seller signs the token transfer/burn from their own account
+    /// - Payment comes from escrowed lamports only, never from rent
+    pub fn sell_to_buyback(ctx: Context<SellToBuyback>, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let buyback = &ctx.accounts.buyback;
+
+        require!(amount > 0, LocalshareError::InvalidShareAmount);
+        require!(now < buyback.expiry_ts, LocalshareError::BuybackExpired);
+        require!(
+            amount <= buyback.max_shares.saturating_sub(buyback.shares_bought),
+            LocalshareError::InsufficientShares
+        );
+
+        let payout = buyback
+            .price_per_share_lamports
+            .checked_mul(amount)
+            .ok_or(LocalshareError::MathOverflow)?;
+        require!(payout <= buyback.escrow_lamports, LocalshareError::InsufficientShares);
+
+        if buyback.burn {
+            token::burn(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Burn {
+                        mint: ctx.accounts.share_mint.to_account_info(),
+                        from: ctx.accounts.seller_shares_ata.to_account_info(),
+                        authority: ctx.accounts.seller.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+
+            let business = &mut ctx.accounts.business;
+            business.total_shares = business
+                .total_shares
+                .checked_sub(amount)
+                .ok_or(LocalshareError::MathOverflow)?;
+        } else {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.seller_shares_ata.to_account_info(),
+                        to: ctx.accounts.shares_vault.to_account_info(),
+                        authority: ctx.accounts.seller.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        }
+
+        // Pay the seller from the escrow
+        **ctx.accounts.buyback.to_account_info().try_borrow_mut_lamports()? -= payout;
+        **ctx.accounts.seller.to_account_info().try_borrow_mut_lamports()? += payout;
+
+        let buyback = &mut ctx.accounts.buyback;
+        buyback.shares_bought = buyback
+            .shares_bought
+            .checked_add(amount)
+            .ok_or(LocalshareError::MathOverflow)?;
+        buyback.escrow_lamports -= payout;
+
+        msg!("✅ Shares sold to buyback: {}", amount);
+        msg!("Paid: {} lamports", payout);
+
+        emit!(SharesBoughtBack {
+            business: ctx.accounts.business.key(),
+            seller: ctx.accounts.seller.key(),
+            amount_shares: amount,
+            price_per_share_lamports: buyback.price_per_share_lamports,
+            total_lamports: payout,
+            burned: buyback.burn,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Closes a buyback after expiry (or once filled) and refunds the remaining escrow
+    /// The escrow goes back to the treasury that funded it and the account rent to the owner
+    ///
+    /// # Security
+    /// - Only the business owner can close the buyback
+    /// - Cannot close while sellers can still use it
+    /// - Escrow funded by a BusinessTreasury returns there, so it stays under withdrawal controls
+    pub fn close_buyback(ctx: Context<CloseBuyback>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let buyback = &ctx.accounts.buyback;
+
+        require!(
+            now >= buyback.expiry_ts || buyback.shares_bought == buyback.max_shares,
+            LocalshareError::BuybackActive
+        );
+
+        **ctx.accounts.buyback.to_account_info().try_borrow_mut_lamports()? -= buyback.escrow_lamports;
+        **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += buyback.escrow_lamports;
+
+        msg!("✅ Buyback closed");
+        msg!("Shares bought back: {}", buyback.shares_bought);
+        msg!("Escrow refunded: {} lamports", buyback.escrow_lamports);
+
+        Ok(())
+    }
+
+    /// Creates the program-controlled treasury of a business and routes all proceeds to it
+    /// Withdrawals must be requested with a purpose, wait `withdrawal_delay_seconds`, and may
+    /// not exceed `max_withdrawal_per_period` per `period_seconds` (0 = no rate limit)
+    ///
+    /// # Security
+    /// - Only the business owner can create the treasury
+    /// - Once set, business.treasury can no longer be pointed elsewhere
+    /// - Withdrawal rules are fixed at creation so investors can rely on them
+    /// - The delay can't be waived, even without a rate limit (see BusinessTreasury::validate_rules)
+    pub fn init_treasury(
+        ctx: Context<InitTreasury>,
+        withdrawal_delay_seconds: i64,
+        max_withdrawal_per_period: u64,
+        period_seconds: i64,
+    ) -> Result<()> {
+        BusinessTreasury::validate_rules(
+            withdrawal_delay_seconds,
+            max_withdrawal_per_period,
+            period_seconds,
+        )?;
+        require!(
+            ctx.accounts.business.treasury != MilestoneEscrow::address(&ctx.accounts.business.key()),
+            LocalshareError::TreasuryLocked
+        );
+
+        let treasury = &mut ctx.accounts.business_treasury;
+        treasury.business = ctx.accounts.business.key();
+        treasury.withdrawal_delay_seconds = withdrawal_delay_seconds;
+        treasury.max_withdrawal_per_period = max_withdrawal_per_period;
+        treasury.period_seconds = period_seconds;
+        treasury.period_start = Clock::get()?.unix_timestamp;
+        treasury.withdrawn_in_period = 0;
+        treasury.total_withdrawn = 0;
+        treasury.withdrawal_count = 0;
+        treasury.bump = ctx.bumps.business_treasury;
+
+        ctx.accounts.business.treasury = treasury.key();
+
+        msg!("✅ Business treasury created: {}", treasury.key());
+        msg!("Withdrawal delay: {} seconds", withdrawal_delay_seconds);
+        msg!("Max per period: {} lamports", max_withdrawal_per_period);
+
+        Ok(())
+    }
+
+    /// Announces a treasury withdrawal with its recipient and purpose
+    /// The request becomes executable after the treasury's withdrawal delay
+    ///
+    /// # Security
+    /// - Only the business owner can request withdrawals
+    /// - Purpose and recipient are public before any funds move
+    pub fn request_withdrawal(
+        ctx: Context<RequestWithdrawal>,
+        amount: u64,
+        recipient: Pubkey,
+        purpose: String,
+    ) -> Result<()> {
+        require!(amount > 0, LocalshareError::InvalidWithdrawal);
+        let purpose = validation::sanitize(&purpose, &PURPOSE_LIMIT)
+            .map_err(|_| LocalshareError::InvalidWithdrawal)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let treasury = &mut ctx.accounts.business_treasury;
+        let request = &mut ctx.accounts.withdrawal;
+        request.treasury = treasury.key();
+        request.id = treasury.withdrawal_count;
+        request.recipient = recipient;
+        request.amount = amount;
+        request.purpose = purpose;
+        request.requested_at = now;
+        request.executable_at = now
+            .checked_add(treasury.withdrawal_delay_seconds)
+            .ok_or(LocalshareError::MathOverflow)?;
+        request.bump = ctx.bumps.withdrawal;
+
+        treasury.withdrawal_count = treasury
+            .withdrawal_count
+            .checked_add(1)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        msg!("✅ Withdrawal #{} requested: {} lamports", request.id, amount);
+        msg!("Purpose: {}", request.purpose);
+        msg!("Executable at: {}", request.executable_at);
+
+        emit!(TreasuryWithdrawalRequested {
+            business: ctx.accounts.business.key(),
+            id: request.id,
+            recipient,
+            amount,
+            purpose: request.purpose.clone(),
+            executable_at: request.executable_at,
+        });
+
+        Ok(())
+    }
+
+    /// Executes a pending withdrawal once its delay has passed
+    ///
+    /// # Security
+    /// - Only the business owner can execute withdrawals
+    /// - Enforces the time delay and the per-period rate limit
+    /// - Never touches the rent-exempt reserve of the treasury
+    pub fn execute_withdrawal(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let amount = ctx.accounts.withdrawal.amount;
+
+        require!(
+            now >= ctx.accounts.withdrawal.executable_at,
+            LocalshareError::WithdrawalLocked
+        );
+
+        ctx.accounts.business_treasury.record_withdrawal(now, amount)?;
+
+        let treasury_info = ctx.accounts.business_treasury.to_account_info();
+        require!(
+            amount <= BusinessTreasury::available_lamports(&treasury_info)?,
+            LocalshareError::InsufficientTreasuryFunds
+        );
+        **treasury_info.try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.recipient.to_account_info().try_borrow_mut_lamports()? += amount;
+
+        msg!("✅ Withdrawal #{} executed: {} lamports", ctx.accounts.withdrawal.id, amount);
+        msg!("Recipient: {}", ctx.accounts.recipient.key());
+
+        emit!(TreasuryWithdrawalExecuted {
+            business: ctx.accounts.business.key(),
+            id: ctx.accounts.withdrawal.id,
+            recipient: ctx.accounts.recipient.key(),
+            amount,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Cancels a pending withdrawal request
+    ///
+    /// # Security
+    /// - Only the business owner can cancel withdrawals
+    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>) -> Result<()> {
+        msg!("✅ Withdrawal #{} cancelled", ctx.accounts.withdrawal.id);
+        Ok(())
+    }
+
+    /// Routes all future proceeds of a business into a milestone escrow
+    /// Funds are released to the current business.treasury only as milestones are approved
+    /// by the protocol admin, an oracle signer, or a shareholder vote
+    ///
+    /// # Security
+    /// - Only the business owner can enable the escrow
+    /// - Once enabled, business.treasury stays pointed at the escrow until a milestone fails
+    /// - An oracle can't be the owner or the release account, and must hold a Verifier
+    ///   role to approve (see approve_milestone)
+    pub fn init_milestone_escrow(
+        ctx: Context<InitMilestoneEscrow>,
+        approval: MilestoneApproval,
+    ) -> Result<()> {
+        if let MilestoneApproval::Oracle { signer } = approval {
+            require!(
+                signer != ctx.accounts.business.owner && signer != ctx.accounts.business.treasury,
+                LocalshareError::InvalidOracle
+            );
+        }
+
+        let escrow = &mut ctx.accounts.milestone_escrow;
+        escrow.business = ctx.accounts.business.key();
+        escrow.release_to = ctx.accounts.business.treasury;
+        escrow.approval = approval;
+        escrow.milestone_count = 0;
+        escrow.released_lamports = 0;
+        escrow.contributed_lamports = 0;
+        escrow.failed = false;
+        escrow.failed_at = 0;
+        escrow.refund_pool = 0;
+        escrow.refund_basis = 0;
+        escrow.bump = ctx.bumps.milestone_escrow;
+
+        ctx.accounts.business.treasury = escrow.key();
+
+        msg!("✅ Milestone escrow enabled: {}", escrow.key());
+        msg!("Releases go to: {}", escrow.release_to);
+
+        Ok(())
+    }
+
+    /// Adds a milestone to the escrow
+    /// `amount` is either a fixed number of lamports or a share (bps) of everything deposited
+    /// With shareholder-vote approval, `proposal_id` names the proposal that decides it
+    ///
+    /// # Security
+    /// - Only the business owner can add milestones
+    /// - No milestones can be added once the escrow has failed
+    pub fn add_milestone(
+        ctx: Context<AddMilestone>,
+        description_hash: [u8; 32],
+        amount: MilestoneAmount,
+        deadline: i64,
+        proposal_id: u64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(!ctx.accounts.milestone_escrow.failed, LocalshareError::MilestoneEscrowFailed);
+        require!(deadline > now, LocalshareError::InvalidMilestone);
+        match amount {
+            MilestoneAmount::Lamports(lamports) => {
+                require!(lamports > 0, LocalshareError::InvalidMilestone)
+            }
+            MilestoneAmount::Bps(bps) => {
+                require!(bps > 0 && bps <= BPS_DENOMINATOR, LocalshareError::InvalidMilestone)
+            }
+        }
+
+        let escrow = &mut ctx.accounts.milestone_escrow;
+        let milestone = &mut ctx.accounts.milestone;
+        milestone.business = ctx.accounts.business.key();
+        milestone.index = escrow.milestone_count;
+        milestone.description_hash = description_hash;
+        milestone.amount = amount;
+        milestone.deadline = deadline;
+        milestone.proposal_id = proposal_id;
+        milestone.created_at = now;
+        milestone.status = MilestoneStatus::Pending;
+        milestone.released_lamports = 0;
+        milestone.bump = ctx.bumps.milestone;
+
+        escrow.milestone_count = escrow
+            .milestone_count
+            .checked_add(1)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        msg!("✅ Milestone #{} added", milestone.index);
+        msg!("Deadline: {}", deadline);
+
+        Ok(())
+    }
+
+    /// Approves a milestone and releases its funds to the escrow's release account
+    ///
+    /// # Security
+    /// - Admin approval requires Config.admin or a Verifier role holder,
+    ///   oracle approval the configured signer, which must still hold a Verifier role
+    /// - Vote approval requires the linked proposal to have succeeded, opened after the
+    ///   milestone was added
+    /// - Only pending milestones before their deadline can be approved
+    pub fn approve_milestone(ctx: Context<ApproveMilestone>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let escrow = &ctx.accounts.milestone_escrow;
+        let milestone = &ctx.accounts.milestone;
+
+        require!(!escrow.failed, LocalshareError::MilestoneEscrowFailed);
+        require!(
+            milestone.status == MilestoneStatus::Pending,
+            LocalshareError::MilestoneNotPending
+        );
+        require!(now <= milestone.deadline, LocalshareError::MilestoneExpired);
+
+        match escrow.approval {
+            MilestoneApproval::Admin => require!(
+                Role::authorizes(
+                    &ctx.accounts.config,
+                    ctx.accounts.role.as_deref(),
+                    &[RoleKind::Verifier],
+                    &ctx.accounts.approver.key()
+                ),
+                LocalshareError::UnauthorizedApprover
+            ),
+            MilestoneApproval::Oracle { signer } => require!(
+                ctx.accounts.approver.key() == signer
+                    && Role::authorizes(
+                        &ctx.accounts.config,
+                        ctx.accounts.role.as_deref(),
+                        &[RoleKind::Verifier],
+                        &signer
+                    ),
+                LocalshareError::UnauthorizedApprover
+            ),
+            MilestoneApproval::ShareholderVote => {
+                let proposal = ctx
+                    .accounts
+                    .proposal
+                    .as_ref()
+                    .ok_or(LocalshareError::UnauthorizedApprover)?;
+                require_keys_eq!(
+                    proposal.business,
+                    escrow.business,
+                    LocalshareError::UnauthorizedApprover
+                );
+                require!(
+                    proposal.id == milestone.proposal_id
+                        && proposal.voting_start >= milestone.created_at
+                        && proposal.status == ProposalStatus::Succeeded,
+                    LocalshareError::UnauthorizedApprover
+                );
+            }
+        }
+
+        // Release the milestone amount (capped by what the escrow holds)
+        let escrow_info = ctx.accounts.milestone_escrow.to_account_info();
+        let available = BusinessTreasury::available_lamports(&escrow_info)?;
+        let deposited = available
+            .checked_add(escrow.released_lamports)
+            .ok_or(LocalshareError::MathOverflow)?;
+        let amount = milestone.amount.lamports(deposited)?.min(available);
+
+        **escrow_info.try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.release_to.to_account_info().try_borrow_mut_lamports()? += amount;
+
+        let escrow = &mut ctx.accounts.milestone_escrow;
+        escrow.released_lamports = escrow
+            .released_lamports
+            .checked_add(amount)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        let milestone = &mut ctx.accounts.milestone;
+        milestone.status = MilestoneStatus::Approved;
+        milestone.released_lamports = amount;
+
+        msg!("✅ Milestone #{} approved", milestone.index);
+        msg!("Released: {} lamports", amount);
+
+        Ok(())
+    }
+
+    /// Marks a milestone as failed and opens investor refunds (permissionless)
+    /// A milestone fails when its deadline passes unapproved, or when its vote was defeated
+    /// The escrow stops releasing, business.treasury is restored, and the remaining escrow
+    /// (up to the credited contributions) becomes the refund pool
+    ///
+    /// # Security
+    /// - Anyone can trigger it once the failure condition is met on-chain
+    /// - Only pending milestones can fail
+    pub fn fail_milestone(ctx: Context<FailMilestone>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let milestone = &ctx.accounts.milestone;
+
+        require!(
+            milestone.status == MilestoneStatus::Pending,
+            LocalshareError::MilestoneNotPending
+        );
+
+        let vote_defeated = ctx.accounts.proposal.as_ref().is_some_and(|proposal| {
+            ctx.accounts.milestone_escrow.approval == MilestoneApproval::ShareholderVote
+                && proposal.business == milestone.business
+                && proposal.id == milestone.proposal_id
+                && proposal.voting_start >= milestone.created_at
+                && proposal.status == ProposalStatus::Defeated
+        });
+        require!(
+            now > milestone.deadline || vote_defeated,
+            LocalshareError::MilestoneStillOpen
+        );
+
+        ctx.accounts.milestone.status = MilestoneStatus::Failed;
+
+        let escrow_info = ctx.accounts.milestone_escrow.to_account_info();
+        let available = BusinessTreasury::available_lamports(&escrow_info)?;
+
+        let escrow = &mut ctx.accounts.milestone_escrow;
+        if !escrow.failed {
+            escrow.failed = true;
+            escrow.failed_at = now;
+            // Refunds are shared by investor contributions only; lamports that reached the
+            // escrow any other way are left for sweep_milestone_escrow
+            escrow.refund_pool = available.min(escrow.contributed_lamports);
+            escrow.refund_basis = escrow.contributed_lamports;
+            ctx.accounts.business.treasury = escrow.release_to;
+        }
+
+        msg!("❌ Milestone #{} failed", ctx.accounts.milestone.index);
+        msg!("Refund pool: {} lamports", escrow.refund_pool);
+
+        Ok(())
+    }
+
+    /// Refunds an investor's pro-rata part of a failed escrow
+    /// refund = refund_pool * position.escrowed_lamports / refund_basis
+    ///
+    /// # Security
+    /// - Only after a milestone failed, until MilestoneEscrow::refund_deadline
+    /// - One refund per investor (claim receipt PDA)
+    pub fn claim_milestone_refund(ctx: Context<ClaimMilestoneRefund>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let escrow = &ctx.accounts.milestone_escrow;
+        require!(escrow.failed, LocalshareError::MilestoneEscrowActive);
+        require!(now < escrow.refund_deadline()?, LocalshareError::RefundPeriodOver);
+        require!(escrow.refund_basis > 0, LocalshareError::NothingToRefund);
+
+        let refund = (escrow.refund_pool as u128)
+            .checked_mul(ctx.accounts.position.escrowed_lamports as u128)
+            .ok_or(LocalshareError::MathOverflow)?
+            / escrow.refund_basis as u128;
+        let refund = u64::try_from(refund).map_err(|_| LocalshareError::MathOverflow)?;
+        require!(refund > 0, LocalshareError::NothingToRefund);
+
+        **ctx.accounts.milestone_escrow.to_account_info().try_borrow_mut_lamports()? -= refund;
+        **ctx.accounts.investor.to_account_info().try_borrow_mut_lamports()? += refund;
+
+        ctx.accounts.refund_receipt.bump = ctx.bumps.refund_receipt;
+
+        msg!("✅ Milestone refund claimed: {} lamports", refund);
+        msg!("Investor: {}", ctx.accounts.investor.key());
+
+        Ok(())
+    }
+
+    /// Sweeps what is left in a failed escrow to its release account (permissionless)
+    /// Covers unclaimed refunds, rounding remainders and lamports that were never credited
+    /// to an investor
+    ///
+    /// # Security
+    /// - Only after MilestoneEscrow::refund_deadline, so investors keep the refund period
+    /// - Funds can only go to escrow.release_to; the rent-exempt reserve stays
+    pub fn sweep_milestone_escrow(ctx: Context<SweepMilestoneEscrow>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let escrow = &ctx.accounts.milestone_escrow;
+        require!(escrow.failed, LocalshareError::MilestoneEscrowActive);
+        require!(now >= escrow.refund_deadline()?, LocalshareError::RefundPeriodOpen);
+
+        let escrow_info = ctx.accounts.milestone_escrow.to_account_info();
+        let remainder = BusinessTreasury::available_lamports(&escrow_info)?;
+        require!(remainder > 0, LocalshareError::NothingToRefund);
+
+        **escrow_info.try_borrow_mut_lamports()? -= remainder;
+        **ctx.accounts.release_to.to_account_info().try_borrow_mut_lamports()? += remainder;
+
+        msg!("✅ Milestone escrow swept: {} lamports", remainder);
+        msg!("Sent to: {}", ctx.accounts.release_to.key());
+
+        Ok(())
+    }
+
+    /// Creates the revenue share of a business on top of its stake pool
+    /// The owner commits `revenue_bps` of revenue (informational) and may name a POS signer
+    /// allowed to deposit revenue on its behalf
+    ///
+    /// # Security
+    /// - Only the business owner can create the revenue share
+    /// - Revenue accrues only to staked shares, so moving tokens between wallets gains nothing
+    /// - From now on, stake changes must settle revenue (enforced via the stake pool)
+    pub fn init_revenue_share(
+        ctx: Context<InitRevenueShare>,
+        revenue_bps: u16,
+        depositor: Pubkey,
+    ) -> Result<()> {
+        require!(
+            revenue_bps > 0 && revenue_bps <= BPS_DENOMINATOR,
+            LocalshareError::InvalidRevenueShare
+        );
+
+        let revenue_share = &mut ctx.accounts.revenue_share;
+        revenue_share.business = ctx.accounts.business.key();
+        revenue_share.depositor = depositor;
+        revenue_share.revenue_bps = revenue_bps;
+        revenue_share.acc_revenue_per_share = 0;
+        revenue_share.total_deposited = 0;
+        revenue_share.total_claimed = 0;
+        revenue_share.bump = ctx.bumps.revenue_share;
+
+        ctx.accounts.stake_pool.revenue_share_enabled = true;
+
+        msg!("✅ Revenue share created: {} bps of revenue", revenue_bps);
+        msg!("Depositor: {}", depositor);
+
+        Ok(())
+    }
+
+    /// Deposits revenue for staked shareholders
+    /// Increases the global revenue-per-share accumulator
+    ///
+    /// # Security
+    /// - Only the business owner or the configured depositor can deposit
+    /// - Requires staked shares, otherwise the revenue would be unclaimable
+    pub fn deposit_revenue(ctx: Context<DepositRevenue>, amount: u64) -> Result<()> {
+        let depositor = ctx.accounts.depositor.key();
+        require!(
+            depositor == ctx.accounts.business.owner
+                || depositor == ctx.accounts.revenue_share.depositor,
+            LocalshareError::UnauthorizedDepositor
+        );
+        require!(amount > 0, LocalshareError::InvalidRevenueShare);
+        let total_staked = ctx.accounts.stake_pool.total_staked;
+        require!(total_staked > 0, LocalshareError::NoStakedShares);
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.depositor.to_account_info(),
+                    to: ctx.accounts.revenue_share.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let revenue_share = &mut ctx.accounts.revenue_share;
+        let increment = (amount as u128)
+            .checked_mul(REWARD_PRECISION)
+            .ok_or(LocalshareError::MathOverflow)?
+            / total_staked as u128;
+        revenue_share.acc_revenue_per_share = revenue_share
+            .acc_revenue_per_share
+            .checked_add(increment)
+            .ok_or(LocalshareError::MathOverflow)?;
+        revenue_share.total_deposited = revenue_share
+            .total_deposited
+            .checked_add(amount)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        msg!("✅ Revenue deposited: {} lamports", amount);
+        msg!("Staked shares: {}", total_staked);
+
+        Ok(())
+    }
+
+    /// Creates the stake pool of a business and its staking vault (permissionless)
+    /// Staked shares are the manipulation-resistant balance used by revenue share and
+    /// staked governance voting
+    pub fn init_stake_pool(ctx: Context<InitStakePool>) -> Result<()> {
+        let pool = &mut ctx.accounts.stake_pool;
+        pool.business = ctx.accounts.business.key();
+        pool.share_mint = ctx.accounts.share_mint.key();
+        pool.total_staked = 0;
+        pool.revenue_share_enabled = false;
+        pool.bump = ctx.bumps.stake_pool;
+
+        msg!("✅ Stake pool created for business {}", pool.business);
+
+        Ok(())
+    }
+
+    /// Stakes shares into the business staking vault
+    ///
+    /// # Security
+    /// - Settles accrued revenue before changing the stake (revenue share required once enabled)
+    /// - Tokens are held by the stake pool PDA until unstaked
+    pub fn stake_shares(ctx: Context<StakeShares>, amount: u64) -> Result<()> {
+        require!(amount > 0, LocalshareError::InvalidShareAmount);
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.holder_shares_ata.to_account_info(),
+                    to: ctx.accounts.stake_vault.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let acc = ctx
+            .accounts
+            .stake_pool
+            .revenue_accumulator(ctx.accounts.revenue_share.as_deref())?;
+        let position = &mut ctx.accounts.stake_position;
+        if position.owner == Pubkey::default() {
+            position.business = ctx.accounts.business.key();
+            position.owner = ctx.accounts.holder.key();
+            position.bump = ctx.bumps.stake_position;
+        }
+        position.settle(acc)?;
+        position.record_stake(amount, Clock::get()?.unix_timestamp)?;
+        position.reward_debt = position.accrued(acc)?;
+
+        let pool = &mut ctx.accounts.stake_pool;
+        pool.total_staked = pool
+            .total_staked
+            .checked_add(amount)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        msg!("✅ Staked {} shares", amount);
+        msg!("Total staked by holder: {}", position.amount);
+
+        Ok(())
+    }
+
+    /// Unstakes shares from the business staking vault
+    /// Accrued revenue stays claimable
+    ///
+    /// # Security
+    /// - Only the position owner can unstake, up to their staked amount
+    /// - Shares backing an open staked vote stay locked until voting ends
+    pub fn unstake_shares(ctx: Context<UnstakeShares>, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(amount > 0, LocalshareError::InvalidShareAmount);
+        require!(
+            amount <= ctx.accounts.stake_position.amount,
+            LocalshareError::InsufficientShares
+        );
+        require!(
+            now >= ctx.accounts.stake_position.locked_until,
+            LocalshareError::StakeLocked
+        );
+
+        let acc = ctx
+            .accounts
+            .stake_pool
+            .revenue_accumulator(ctx.accounts.revenue_share.as_deref())?;
+        let position = &mut ctx.accounts.stake_position;
+        position.settle(acc)?;
+        position.amount -= amount;
+        position.reward_debt = position.accrued(acc)?;
+
+        let pool = &mut ctx.accounts.stake_pool;
+        pool.total_staked = pool
+            .total_staked
+            .checked_sub(amount)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        let business_key = ctx.accounts.business.key();
+        let seeds = &[
+            b"stake_pool",
+            business_key.as_ref(),
+            &[ctx.accounts.stake_pool.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.stake_vault.to_account_info(),
+                    to: ctx.accounts.holder_shares_ata.to_account_info(),
+                    authority: ctx.accounts.stake_pool.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        msg!("✅ Unstaked {} shares", amount);
+        msg!("Remaining stake: {}", ctx.accounts.stake_position.amount);
+
+        Ok(())
+    }
+
+    /// Votes on a proposal with staked shares instead of wallet tokens
+    /// Weight is the stake held since before voting opened (StakePosition::voting_weight);
+    /// the stake stays locked until voting ends
+    ///
+    /// # Security
+    /// - One staked vote per wallet per proposal (unique PDA)
+    /// - Stake can't be withdrawn and re-staked elsewhere to vote twice
+    /// - Shares bought and staked after the proposal opened don't count
+    pub fn cast_staked_vote(ctx: Context<CastStakedVote>, approve: bool) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let proposal = &ctx.accounts.proposal;
+
+        require!(
+            proposal.status == ProposalStatus::Active && now < proposal.voting_end,
+            LocalshareError::VotingClosed
+        );
+
+        let weight = ctx.accounts.stake_position.voting_weight(proposal.voting_start);
+        require!(weight > 0, LocalshareError::InsufficientVotingPower);
+
+        let position = &mut ctx.accounts.stake_position;
+        position.locked_until = position.locked_until.max(proposal.voting_end);
+
+        let proposal = &mut ctx.accounts.proposal;
+        if approve {
+            proposal.yes_votes = proposal
+                .yes_votes
+                .checked_add(weight)
+                .ok_or(LocalshareError::MathOverflow)?;
+        } else {
+            proposal.no_votes = proposal
+                .no_votes
+                .checked_add(weight)
+                .ok_or(LocalshareError::MathOverflow)?;
+        }
+
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.proposal = proposal.key();
+        vote_record.voter = ctx.accounts.voter.key();
+        vote_record.approve = approve;
+        vote_record.weight = weight;
+        vote_record.bump = ctx.bumps.vote_record;
+
+        msg!("🗳️ Staked vote cast on proposal #{}", proposal.id);
+        msg!("Voter: {}", vote_record.voter);
+        msg!("Approve: {} | Weight: {}", approve, weight);
+
+        Ok(())
+    }
+
+    /// Claims revenue accrued by a stake position
+    ///
+    /// # Security
+    /// - Pays only revenue accrued since the position's last settlement
+    pub fn claim_revenue(ctx: Context<ClaimRevenue>) -> Result<()> {
+        let acc = ctx.accounts.revenue_share.acc_revenue_per_share;
+        let position = &mut ctx.accounts.stake_position;
+        position.settle(acc)?;
+        position.reward_debt = position.accrued(acc)?;
+
+        let amount = position.pending_revenue;
+        require!(amount > 0, LocalshareError::NothingToClaim);
+        position.pending_revenue = 0;
+
+        **ctx.accounts.revenue_share.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.holder.to_account_info().try_borrow_mut_lamports()? += amount;
+
+        let revenue_share = &mut ctx.accounts.revenue_share;
+        revenue_share.total_claimed = revenue_share
+            .total_claimed
+            .checked_add(amount)
+            .ok_or(LocalshareError::MathOverflow)?;
+
+        msg!("✅ Revenue claimed: {} lamports", amount);
+        msg!("Holder: {}", ctx.accounts.holder.key());
+
+        Ok(())
+    }
+
+    /// Sets whether list_business requires a Verified business
+    ///
+    /// # Security
+    /// - Only Config.admin or a Verifier role holder can change the policy
+    pub fn set_verification_policy(
+        ctx: Context<SetVerificationPolicy>,
+        require_verified_listing: bool,
+    ) -> Result<()> {
+        require!(
+            Role::authorizes(
+                &ctx.accounts.config,
+                ctx.accounts.role.as_deref(),
+                &[RoleKind::Verifier],
+                &ctx.accounts.authority.key()
+            ),
+            LocalshareError::UnauthorizedRole
+        );
+
+        let policy = &mut ctx.accounts.verification_policy;
+        policy.require_verified_listing = require_verified_listing;
+        policy.bump = ctx.bumps.verification_policy;
+
+        msg!("✅ Verified listing required: {}", require_verified_listing);
+
+        Ok(())
+    }
+
+    /// Grants a privileged role to a wallet
+    ///
+    /// # Security
+    /// - Only Config.admin can grant roles
+    /// - One Role PDA per (kind, grantee): granting twice fails
+    pub fn grant_role(ctx: Context<GrantRole>, kind: RoleKind, grantee: Pubkey) -> Result<()> {
+        let role = &mut ctx.accounts.role;
+        role.kind = kind;
+        role.grantee = grantee;
+        role.granted_by = ctx.accounts.admin.key();
+        role.granted_at = Clock::get()?.unix_timestamp;
+        role.bump = ctx.bumps.role;
+
+        msg!("✅ Role granted: {:?}", kind);
+        msg!("Grantee: {}", grantee);
+
+        Ok(())
+    }
+
+    /// Revokes a privileged role
+    ///
+    /// # Security
+    /// - Only Config.admin can revoke roles
+    pub fn revoke_role(ctx: Context<RevokeRole>) -> Result<()> {
+        msg!("✅ Role revoked: {:?}", ctx.accounts.role.kind);
+        msg!("Grantee: {}", ctx.accounts.role.grantee);
+        Ok(())
+    }
+
+    /// Submits a business for verification (Unverified | Rejected → Pending)
+    ///
+    /// # Security
+    /// - Only the business owner can request verification
+    /// - Verified or suspended businesses cannot re-enter review this way
+    pub fn request_verification(ctx: Context<RequestVerification>) -> Result<()> {
+        let verification = &mut ctx.accounts.verification;
+        if verification.business == Pubkey::default() {
+            verification.business = ctx.accounts.business.key();
+            verification.status = VerificationStatus::Unverified;
+            verification.bump = ctx.bumps.verification;
+        }
+
+        require!(
+            matches!(
+                verification.status,
+                VerificationStatus::Unverified | VerificationStatus::Rejected
+            ),
+            LocalshareError::InvalidVerificationTransition
+        );
+
+        verification.status = VerificationStatus::Pending;
+        verification.reviewer = Pubkey::default();
+        verification.updated_at = Clock::get()?.unix_timestamp;
+
+        msg!("✅ Verification requested for {}", ctx.accounts.business.name);
+
+        Ok(())
+    }
+
+    /// Reviews a pending business (Pending → Verified | Rejected)
+    ///
+    /// # Security
+    /// - Only Config.admin or a Verifier role holder can review
+    pub fn review_verification(ctx: Context<ReviewVerification>, approve: bool) -> Result<()> {
+        require!(
+            Role::authorizes(
+                &ctx.accounts.config,
+                ctx.accounts.role.as_deref(),
+                &[RoleKind::Verifier],
+                &ctx.accounts.reviewer.key()
+            ),
+            LocalshareError::UnauthorizedRole
+        );
+
+        let verification = &mut ctx.accounts.verification;
+        require!(
+            verification.status == VerificationStatus::Pending,
+            LocalshareError::InvalidVerificationTransition
+        );
+
+        verification.status = if approve {
+            VerificationStatus::Verified
+        } else {
+            VerificationStatus::Rejected
+        };
+        verification.reviewer = ctx.accounts.reviewer.key();
+        verification.updated_at = Clock::get()?.unix_timestamp;
+
+        msg!("✅ Business {}", if approve { "verified" } else { "rejected" });
+        msg!("Reviewer: {}", verification.reviewer);
+
+        Ok(())
+    }
+
+    /// Suspends a fraudulent listing: delists it and blocks share purchases and subscriptions
+    ///
+    /// # Security
+    /// - Only Config.admin or a Verifier or Pauser role holder can suspend
+    /// - Works from any status, including businesses never submitted for review;
+    ///   the status is kept so reinstate_business restores it
+    /// - Mirrors the delisting into the business's registry and search index entries
+    pub fn suspend_business(ctx: Context<SuspendBusiness>) -> Result<()> {
+        require!(
+            Role::authorizes(
+                &ctx.accounts.config,
+                ctx.accounts.role.as_deref(),
+                &[RoleKind::Verifier, RoleKind::Pauser],
+                &ctx.accounts.reviewer.key()
+            ),
+            LocalshareError::UnauthorizedRole
+        );
+
+        let verification = &mut ctx.accounts.verification;
+        verification.business = ctx.accounts.business.key();
+        if verification.status != VerificationStatus::Suspended {
+            verification.status_before_suspension = verification.status;
+        }
+        verification.status = VerificationStatus::Suspended;
+        verification.reviewer = ctx.accounts.reviewer.key();
+        verification.updated_at = Clock::get()?.unix_timestamp;
+        verification.bump = ctx.bumps.verification;
+
+        ctx.accounts.business.is_listed = false;
+        ctx.accounts.business.sync_listing(
+            ctx.accounts.business.key(),
+            ctx.accounts.registry_page.as_ref(),
+            ctx.accounts.category_index.as_ref(),
+            ctx.accounts.region_index.as_ref(),
+        )?;
+
+        msg!("⛔ Business suspended: {}", ctx.accounts.business.name);
+        msg!("Reviewer: {}", verification.reviewer);
+
+        Ok(())
+    }
+
+    /// Lifts a suspension, restoring the status held before it
+    /// The owner must list the business again
+    ///
+    /// # Security
+    /// - Only Config.admin or a Verifier or Pauser role holder can reinstate
+    /// - Reinstating never grants Verified: only review_verification does
+    pub fn reinstate_business(ctx: Context<ReinstateBusiness>) -> Result<()> {
+        require!(
+            Role::authorizes(
+                &ctx.accounts.config,
+                ctx.accounts.role.as_deref(),
+                &[RoleKind::Verifier, RoleKind::Pauser],
+                &ctx.accounts.reviewer.key()
+            ),
+            LocalshareError::UnauthorizedRole
+        );
+
+        let verification = &mut ctx.accounts.verification;
+        require!(
+            verification.status == VerificationStatus::Suspended,
+            LocalshareError::InvalidVerificationTransition
+        );
+
+        verification.status = verification.status_before_suspension;
+        verification.reviewer = ctx.accounts.reviewer.key();
+        verification.updated_at = Clock::get()?.unix_timestamp;
+
+        msg!("✅ Business reinstated: {}", verification.business);
+
+        Ok(())
+    }
+
+    /// Emergency freeze of a business: blocks listing, offering changes and share purchases
+    ///
+    /// # Security
+    /// - Only the business owner, Config.admin or a Pauser role holder can freeze
+    /// - The reason code is recorded on-chain and emitted
+    pub fn freeze_business(ctx: Context<FreezeBusiness>, reason_code: u16) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        require!(
+            authority == ctx.accounts.business.owner
+                || Role::authorizes(
+                    &ctx.accounts.config,
+                    ctx.accounts.role.as_deref(),
+                    &[RoleKind::Pauser],
+                    &authority
+                ),
+            LocalshareError::UnauthorizedRole
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let business_freeze = &mut ctx.accounts.business_freeze;
+        require!(!business_freeze.frozen, LocalshareError::BusinessFrozen);
+
+        business_freeze.business = ctx.accounts.business.key();
+        business_freeze.frozen = true;
+        business_freeze.reason_code = reason_code;
+        business_freeze.frozen_by = authority;
+        business_freeze.updated_at = now;
+        business_freeze.bump = ctx.bumps.business_freeze;
+
+        emit!(BusinessFreezeChanged {
+            business: business_freeze.business,
+            frozen: true,
+            reason_code,
+            authority,
+            timestamp: now,
+        });
+
+        msg!("⛔ Business frozen: {}", ctx.accounts.business.name);
+        msg!("Reason code: {}", reason_code);
+
+        Ok(())
+    }
+
+    /// Lifts an emergency freeze
+    ///
+    /// # Security
+    /// - Config.admin or a Pauser role holder can always unfreeze
+    /// - The owner can only lift a freeze they placed themselves
+    /// - The reason code is recorded on-chain and emitted
+    pub fn unfreeze_business(ctx: Context<UnfreezeBusiness>, reason_code: u16) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        let business_freeze = &mut ctx.accounts.business_freeze;
+        require!(business_freeze.frozen, LocalshareError::BusinessNotFrozen);
+        require!(
+            Role::authorizes(
+                &ctx.accounts.config,
+                ctx.accounts.role.as_deref(),
+                &[RoleKind::Pauser],
+                &authority
+            ) || (authority == ctx.accounts.business.owner
+                && business_freeze.frozen_by == ctx.accounts.business.owner),
+            LocalshareError::UnauthorizedRole
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        business_freeze.frozen = false;
+        business_freeze.reason_code = reason_code;
+        business_freeze.frozen_by = Pubkey::default();
+        business_freeze.updated_at = now;
+
+        emit!(BusinessFreezeChanged {
+            business: business_freeze.business,
+            frozen: false,
+            reason_code,
+            authority,
+            timestamp: now,
+        });
+
+        msg!("✅ Business unfrozen: {}", ctx.accounts.business.name);
+        msg!("Reason code: {}", reason_code);
+
+        Ok(())
+    }
+
+    /// Freezes holder token accounts of a frozen business (passed as remaining accounts)
+    /// Uses the freeze authority of share_mint_authority
+    ///
+    /// # Security
+    /// - Only Config.admin or a Pauser role holder can freeze holder accounts
+    /// - The business must be frozen first
+    /// - Every account must hold the business's share mint
+    pub fn freeze_holder_accounts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FreezeHolderAccounts<'info>>,
+    ) -> Result<()> {
+        require!(
+            BusinessFreeze::is_frozen(&ctx.accounts.business_freeze)?,
+            LocalshareError::BusinessNotFrozen
+        );
+        let frozen = ctx.accounts.set_frozen(ctx.remaining_accounts, true)?;
+
+        msg!("⛔ Holder accounts frozen: {}", frozen);
+
+        Ok(())
+    }
+
+    /// Thaws holder token accounts once the business is unfrozen (passed as remaining accounts)
+    ///
+    /// # Security
+    /// - Only Config.admin or a Pauser role holder can thaw holder accounts
+    /// - The business must be unfrozen first
+    /// - Every account must hold the business's share mint
+    pub fn thaw_holder_accounts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FreezeHolderAccounts<'info>>,
+    ) -> Result<()> {
+        require!(
+            !BusinessFreeze::is_frozen(&ctx.accounts.business_freeze)?,
+            LocalshareError::BusinessFrozen
+        );
+        let thawed = ctx.accounts.set_frozen(ctx.remaining_accounts, false)?;
+
+        msg!("✅ Holder accounts thawed: {}", thawed);
+
+        Ok(())
+    }
+
+    /// Upgrades the business account to the current layout (BUSINESS_VERSION)
+    /// Grows the account in place, with the rent top-up paid by the owner
+    ///
+    /// # Security
+    /// - Only the business owner can migrate (PDA derived from the signer)
+    /// - Accounts already at the current version are rejected
+    /// - Listed businesses without listed_at get the migration time (see Business::upgrade)
+    pub fn migrate_business(ctx: Context<MigrateBusiness>) -> Result<()> {
+        let info = ctx.accounts.business.to_account_info();
+        migration::grow(
+            &info,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + Business::INIT_SPACE,
+        )?;
+
+        let mut business: Business = migration::load(&info)?;
+        require_keys_eq!(
+            business.owner,
+            ctx.accounts.owner.key(),
+            LocalshareError::InvalidBusinessOwner
+        );
+        require!(
+            business.version < BUSINESS_VERSION,
+            LocalshareError::AccountAlreadyMigrated
+        );
+
+        let from_version = business.version;
+        business.upgrade(Clock::get()?.unix_timestamp);
+        migration::store(&business, &info)?;
+
+        msg!("✅ Business migrated: v{} → v{}", from_version, BUSINESS_VERSION);
+
+        Ok(())
+    }
+
+    /// Upgrades the global configuration to the current layout (CONFIG_VERSION)
+    /// Grows the account in place, with the rent top-up paid by the admin
+    ///
+    /// # Security
+    /// - Only Config.admin can migrate; no role grants it
+    /// - Accounts already at the current version are rejected
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        let info = ctx.accounts.config.to_account_info();
+        migration::grow(
+            &info,
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + Config::INIT_SPACE,
+        )?;
+
+        let mut config: Config = migration::load(&info)?;
+        require_keys_eq!(config.admin, ctx.accounts.admin.key(), LocalshareError::UnauthorizedRole);
+        require!(
+            config.version < CONFIG_VERSION,
+            LocalshareError::AccountAlreadyMigrated
+        );
+
+        let from_version = config.version;
+        config.version = CONFIG_VERSION;
+        config.reserved = [0; 64];
+        migration::store(&config, &info)?;
+
+        msg!("✅ Config migrated: v{} → v{}", from_version, CONFIG_VERSION);
+
+        Ok(())
+    }
+
+    /// Upgrades an indexed offering to the current layout (OFFERING_VERSION)
+    /// Grows the account in place, with the rent top-up paid by the owner
+    /// Version 2 offerings without round fields are rebuilt (see OfferingV2::upgrade),
+    /// later layouts are zero-extended
+    /// Legacy ["offering", business, mint] accounts use migrate_legacy_offering instead
+    ///
+    /// # Security
+    /// - Only the business owner can migrate
+    /// - Accounts already at the current version are rejected
+    pub fn migrate_offering(ctx: Context<MigrateOffering>, index: u32) -> Result<()> {
+        let info = ctx.accounts.offering.to_account_info();
+        let pre_rounds = OfferingV2::load(&info)?;
+        migration::grow(
+            &info,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + Offering::INIT_SPACE,
+        )?;
+
+        let (from_version, mut offering) = match pre_rounds {
+            Some(v2) => (v2.version, v2.upgrade()?),
+            None => {
+                let offering: Offering = migration::load(&info)?;
+                (offering.version, offering)
+            }
+        };
+        require_keys_eq!(
+            offering.business,
+            ctx.accounts.business.key(),
+            LocalshareError::InvalidBusiness
+        );
+        require!(
+            from_version < OFFERING_VERSION,
+            LocalshareError::AccountAlreadyMigrated
+        );
+
+        offering.version = OFFERING_VERSION;
+        offering.reserved = [0; 64];
+        migration::store(&offering, &info)?;
+
+        msg!("✅ Offering #{} migrated: v{} → v{}", index, from_version, OFFERING_VERSION);
+
+        Ok(())
+    }
+
+    /// Creates a page of the business registry
+    /// Pages are created in order, each once the previous one is full
+    ///
+    /// # Security
+    /// - Permissionless (the payer funds the page's rent)
+    /// - Page `n` requires page `n - 1` to be full, so pages are contiguous
+    pub fn init_registry_page(ctx: Context<InitRegistryPage>, page: u32) -> Result<()> {
+        if page > 0 {
+            let previous = ctx
+                .accounts
+                .previous_page
+                .as_ref()
+                .ok_or(LocalshareError::InvalidRegistryPage)?
+                .load()?;
+            require!(
+                previous.count as usize == REGISTRY_PAGE_CAPACITY,
+                LocalshareError::InvalidRegistryPage
+            );
+        }
+
+        let mut registry_page = ctx.accounts.registry_page.load_init()?;
+        registry_page.page = page;
+        registry_page.count = 0;
+
+        msg!("✅ Registry page {} created", page);
+
+        Ok(())
+    }
+
+    /// Adds a business registered before the registry existed
+    ///
+    /// # Security
+    /// - Permissionless: only appends the business key and mirrors its listing
+    /// - Businesses already in the registry are rejected
+    pub fn add_to_registry(ctx: Context<AddToRegistry>) -> Result<()> {
+        let business_key = ctx.accounts.business.key();
+        let business = &mut ctx.accounts.business;
+        require!(!business.in_registry, LocalshareError::InvalidRegistryPage);
+
+        BusinessRegistry::append(&ctx.accounts.registry_page, business_key, business)?;
+
+        msg!("✅ Business added to registry page {}", business.registry_page);
+
+        Ok(())
+    }
+
+    /// Removes a business from the marketplace
+    ///
+    /// # Security
+    /// - Only the business owner can unlist
+    /// - The registry and search index entries are updated when the business has them
+    pub fn unlist_business(ctx: Context<UnlistBusiness>) -> Result<()> {
+        let business = &mut ctx.accounts.business;
+        require!(business.is_listed, LocalshareError::OfferingNotActive);
+
+        business.is_listed = false;
+        business.sync_listing(
+            business.key(),
+            ctx.accounts.registry_page.as_ref(),
+            ctx.accounts.category_index.as_ref(),
+            ctx.accounts.region_index.as_ref(),
+        )?;
+
+        msg!("✅ Business unlisted: {}", business.name);
+
+        Ok(())
+    }
+
+    /// Creates a page of a category or region search index
+    /// Pages of each index are created in order, each once the previous one is full
+    ///
+    /// # Security
+    /// - Permissionless (the payer funds the page's rent)
+    /// - The key must be a valid category or geohash prefix
+    /// - Page `n` requires page `n - 1` of the same index to be full
+    pub fn init_search_index_page(
+        ctx: Context<InitSearchIndexPage>,
+        kind: IndexKind,
+        key: [u8; SEARCH_INDEX_KEY_LENGTH],
+        page: u32,
+    ) -> Result<()> {
+        require!(kind.is_valid_key(&key), LocalshareError::InvalidSearchIndexPage);
+
+        if page > 0 {
+            let previous = ctx
+                .accounts
+                .previous_page
+                .as_ref()
+                .ok_or(LocalshareError::InvalidSearchIndexPage)?
+                .load()?;
+            require!(
+                previous.count as usize == REGISTRY_PAGE_CAPACITY,
+                LocalshareError::InvalidSearchIndexPage
+            );
+        }
+
+        let mut index_page = ctx.accounts.index_page.load_init()?;
+        index_page.kind = kind as u8;
+        index_page.key = key;
+        index_page.page = page;
+        index_page.count = 0;
+
+        msg!("✅ {:?} index page {} created", kind, page);
+
+        Ok(())
+    }
+
+    /// Publishes a periodic financial report of a business
+    /// The document lives off-chain at `report_uri`; its hash and key metrics are stored on-chain
+    ///
+    /// # Security
+    /// - Only the business owner can publish
+    /// - Periods are months as YYYYMM, from MIN_REPORT_PERIOD up to the current month
+    /// - Periods must increase monotonically (one report per period, no backfilling)
+    /// - URI is trimmed and validated (characters, length)
+    /// - Clears an overdue flag raised by the admin
+    pub fn publish_report(
+        ctx: Context<PublishReport>,
+        period: u32,
+        report_uri: String,
+        content_hash: [u8; 32],
+        key_metrics: KeyMetrics,
+    ) -> Result<()> {
+        let report_uri = validation::sanitize(&report_uri, &REPORT_URI_LIMIT)
+            .map_err(|_| LocalshareError::InvalidReport)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let reporting = &mut ctx.accounts.reporting;
+        require!(
+            Report::is_valid_period(period, now)
+                && (reporting.report_count == 0 || period > reporting.last_period),
+            LocalshareError::InvalidReportPeriod
+        );
+
+        let business_key = ctx.accounts.business.key();
+        let report = &mut ctx.accounts.report;
+        report.set_inner(Report {
+            business: business_key,
+            period,
+            report_uri,
+            content_hash,
+            key_metrics,
+            published_at: now,
+            bump: ctx.bumps.report,
+        });
+
+        reporting.business = business_key;
+        reporting.last_period = period;
+        reporting.report_count = reporting
+            .report_count
+            .checked_add(1)
+            .ok_or(LocalshareError::MathOverflow)?;
+        reporting.last_published_at = now;
+        reporting.overdue = false;
+        reporting.bump = ctx.bumps.reporting;
+
+        emit!(ReportPublished {
+            business: business_key,
+            report: report.key(),
+            period,
+            content_hash,
+            timestamp: now,
+        });
+
+        msg!("📊 Report published for period {}", period);
+        msg!("URI: {}", report.report_uri);
+
+        Ok(())
+    }
+
+    /// Flags a listed business whose reporting is overdue
+    /// The flag is cleared by its next publish_report
+    ///
+    /// # Security
+    /// - Only Config.admin or a Verifier role holder can flag
+    /// - Only listed businesses can be flagged, once REPORT_OVERDUE_AFTER_SECONDS have
+    ///   passed since their last report or, if they never reported, their first listing
+    pub fn flag_overdue_report(ctx: Context<FlagOverdueReport>) -> Result<()> {
+        require!(
+            Role::authorizes(
+                &ctx.accounts.config,
+                ctx.accounts.role.as_deref(),
+                &[RoleKind::Verifier],
+                &ctx.accounts.reviewer.key()
+            ),
+            LocalshareError::UnauthorizedRole
+        );
+        require!(ctx.accounts.business.is_listed, LocalshareError::OfferingNotActive);
+
+        let now = Clock::get()?.unix_timestamp;
+        let reporting = &mut ctx.accounts.reporting;
+        require!(
+            reporting.is_overdue(ctx.accounts.business.listed_at, now),
+            LocalshareError::ReportNotOverdue
+        );
+
+        reporting.business = ctx.accounts.business.key();
+        reporting.overdue = true;
+        reporting.flagged_at = now;
+        reporting.bump = ctx.bumps.reporting;
+
+        emit!(ReportingOverdue {
+            business: reporting.business,
+            last_period: reporting.last_period,
+            report_count: reporting.report_count,
+            timestamp: now,
+        });
+
+        msg!("⚠️ Reporting overdue: {}", ctx.accounts.business.name);
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Account State Structs
+// ============================================================================
+
+/// Current layout version of Config accounts (0 = created before versioning)
+pub const CONFIG_VERSION: u8 = 1;
+
+/// Global protocol configuration
+/// PDA: ["config"]
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    /// Protocol administrator (can update configurations)
+    pub admin: Pubkey,
+    
+    /// Token mint used for payments (e.g., USDC)
+    pub payment_mint: Pubkey,
+    
+    /// PDA bump seed
+    pub bump: u8,
+    
+    /// Account layout version (CONFIG_VERSION)
+    pub version: u8,
+    
+    /// Zeroed space for future fields
+    pub reserved: [u8; 64],
+}
+
+/// Maximum characters of a business name
+pub const MAX_BUSINESS_NAME_CHARS: usize = 50;
+
+/// Bytes reserved for a business name (two-byte UTF-8 on average, e.g. accented Latin)
+pub const MAX_BUSINESS_NAME_BYTES: usize = 100;
+
+/// Validation limits of Business.name
+pub const BUSINESS_NAME_LIMIT: TextLimit = TextLimit {
+    max_chars: MAX_BUSINESS_NAME_CHARS,
+    max_bytes: MAX_BUSINESS_NAME_BYTES,
+    required: true,
+};
+
+/// Current layout version of Business accounts
+/// 0 = created before versioning, 1 = reserved padding,
+/// 2 = registry and search index fields (zeroed until add_to_registry and register_business),
+/// 3 = listed_at
+pub const BUSINESS_VERSION: u8 = 3;
+
+/// Represents a registered business in the protocol
+/// PDA: ["business", owner.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct Business {
+    /// Business owner (entrepreneur)
+    pub owner: Pubkey,
+    
+    /// Business name (maximum 50 characters / 100 bytes, trimmed)
+    #[max_len(MAX_BUSINESS_NAME_BYTES)]
+    pub name: String,
+    
+    /// Share mint of this business (NFT or Fungible Token)
+    pub share_mint: Pubkey,
+    
+    /// Total number of shares issued
+    pub total_shares: u64,
+    
+    /// Price per share in lamports
+    pub price_per_share_lamports: u64,
+    
+    /// Treasury account for receiving payments
+    pub treasury: Pubkey,
+    
+    /// Whether the business is listed on the marketplace
+    pub is_listed: bool,
+    
+    /// PDA bump seed
+    pub bump: u8,
+    
+    /// Account layout version (BUSINESS_VERSION)
+    pub version: u8,
+    
+    /// Whether the business has an entry in the BusinessRegistry
+    pub in_registry: bool,
+    
+    /// Registry page holding the business's entry
+    pub registry_page: u32,
+    
+    /// Position of the entry within its registry page
+    pub registry_slot: u16,
+    
+    /// Category of the business
+    pub category: BusinessCategory,
+    
+    /// Geohash of the business's location (zeroed for businesses registered before regions)
+    pub region: [u8; REGION_CODE_LENGTH],
+    
+    /// Whether the business has entries in the SearchIndex of its category and region
+    pub in_search_index: bool,
+    
+    /// Category index page holding the business's entry
+    pub category_index_page: u32,
+    
+    /// Position of the entry within its category index page
+    pub category_index_slot: u16,
+    
+    /// Region index page holding the business's entry
+    pub region_index_page: u32,
+    
+    /// Position of the entry within its region index page
+    pub region_index_slot: u16,
+    
+    /// Unix timestamp of the first listing (0 = never listed)
+    /// Businesses listed before it was recorded get the time of their migration
+    pub listed_at: i64,
+    
+    /// Zeroed space for future fields
+    pub reserved: [u8; 30],
+}
+
+impl Business {
+    /// Stamps the current layout version on a business loaded from an older layout
+    /// A listed business without listed_at starts its reporting deadline at `now`
+    pub fn upgrade(&mut self, now: i64) {
+        if self.is_listed && self.listed_at == 0 {
+            self.listed_at = now;
+        }
+        self.version = BUSINESS_VERSION;
+        self.reserved = [0; 30];
+    }
+
+    /// Registry and search index entry of the business
+    pub fn index_entry(&self, business_key: Pubkey) -> RegistryEntry {
+        RegistryEntry {
+            business: business_key,
+            listed: self.is_listed as u8,
+            category: self.category as u8,
+            region: self.region,
+            padding: [0; 1],
+        }
+    }
+
+    /// Mirrors the business's listing into its registry and search index entries
+    /// Each page is required only once the business has an entry there
+    pub fn sync_listing(
+        &self,
+        business_key: Pubkey,
+        registry: Option<&AccountLoader<BusinessRegistry>>,
+        category_index: Option<&AccountLoader<SearchIndex>>,
+        region_index: Option<&AccountLoader<SearchIndex>>,
+    ) -> Result<()> {
+        BusinessRegistry::sync(registry, business_key, self)?;
+        if !self.in_search_index {
+            return Ok(());
+        }
+
+        let entry = self.index_entry(business_key);
+        SearchIndex::sync(
+            category_index,
+            IndexKind::Category,
+            self.category.index_key(),
+            self.category_index_page,
+            self.category_index_slot,
+            entry,
+        )?;
+        SearchIndex::sync(
+            region_index,
+            IndexKind::Region,
+            SearchIndex::region_key(&self.region),
+            self.region_index_page,
+            self.region_index_slot,
+            entry,
+        )
+    }
+}
+
+/// Kind of business, used to filter the marketplace
+/// Stored as its index (RegistryEntry.category), so variants are only ever appended
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum BusinessCategory {
+    Uncategorized,
+    Cafe,
+    Restaurant,
+    Bakery,
+    Grocery,
+    Retail,
+    Services,
+    Health,
+    Beauty,
+    Farm,
+    Other,
+}
+
+impl BusinessCategory {
+    /// Number of categories
+    pub const COUNT: u8 = BusinessCategory::Other as u8 + 1;
+
+    /// SearchIndex key of the category: [category, 0, 0, 0]
+    pub fn index_key(self) -> [u8; SEARCH_INDEX_KEY_LENGTH] {
+        [self as u8, 0, 0, 0]
+    }
+}
+
+/// Characters of Business.region (geohash cells of about 5 km)
+pub const REGION_CODE_LENGTH: usize = 5;
+
+/// Maximum length of a slug (folding can turn one character into two, e.g. "ß" → "ss")
+pub const MAX_SLUG_LENGTH: usize = 2 * MAX_BUSINESS_NAME_CHARS;
+
+/// Reservation of a business name slug (lookup of a business by slug)
+/// PDA: ["name", sha256(slug)] (see `slug::seed`)
+#[account]
+#[derive(InitSpace)]
+pub struct NameRecord {
+    /// Business holding the name
+    pub business: Pubkey,
+    
+    /// Normalized slug (maximum MAX_SLUG_LENGTH characters)
+    #[max_len(MAX_SLUG_LENGTH)]
+    pub slug: String,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl NameRecord {
+    /// Address of the NameRecord reserving `slug`
+    pub fn address(slug: &str) -> Pubkey {
+        Pubkey::find_program_address(&[b"name", &slug::seed(slug)], &crate::ID).0
+    }
+
+    /// Loads a name record if the account has been initialized
+    pub fn load(info: &AccountInfo) -> Result<Option<Self>> {
+        if info.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*info.owner, crate::ID, LocalshareError::InvalidBusiness);
+        let data = info.try_borrow_data()?;
+        Ok(Some(Self::try_deserialize(&mut &data[..])?))
+    }
+}
+
+/// Authority for minting business equity share tokens
+/// PDA: ["share_mint_authority", business.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct ShareMintAuthority {
+    /// Business this authority belongs to
+    pub business: Pubkey,
+    /// Bump seed
+    pub bump: u8,
+}
+
+/// Current layout version of Offering accounts
+/// 1 = legacy ["offering", business, mint] accounts, 2 = indexed offerings,
+/// 3 = named, time-boxed rounds, 4 = reserved padding
+/// Rounds were introduced without a version bump: version 2 accounts come in both
+/// layouts and are told apart by size (OFFERING_V2_SPACE)
+pub const OFFERING_VERSION: u8 = 4;
+
+/// Represents a share offering from a business
+/// Shares are moved from the shares_vault into the offering vault at creation
+/// PDA: ["offering", business.key(), index]
+#[account]
+#[derive(InitSpace)]
+pub struct Offering {
+    /// Account layout version (OFFERING_VERSION)
+    pub version: u8,
+    
+    /// Reference to the business that created this offering
+    pub business: Pubkey,
+    
+    /// Mint of the shares being offered (Business.share_mint)
+    pub share_mint: Pubkey,
+    
+    /// Round number within the business
+    pub index: u32,
+    
+    /// Round label, e.g. "Seed" or "Series A" (maximum 32 characters)
+    #[max_len(MAX_ROUND_NAME_LENGTH)]
+    pub round_name: String,
+    
+    /// Price per share in lamports
+    pub price_per_share_lamports: u64,
+    
+    /// Shares moved into the offering at creation
+    pub total_shares: u64,
+    
+    /// Amount of shares still available
+    pub remaining_shares: u64,
+    
+    /// Unix timestamp when the round opens
+    pub start_ts: i64,
+    
+    /// Unix timestamp when the round closes (0 = open-ended)
+    pub end_ts: i64,
+    
+    /// Lamports raised by this round
+    pub raised_lamports: u64,
+    
+    /// Whether the offering is active
+    pub is_active: bool,
+    
+    /// PDA bump seed
+    pub bump: u8,
+    
+    /// Zeroed space for future fields
+    pub reserved: [u8; 64],
+}
+
+impl Offering {
+    /// Whether the round window includes `now`
+    pub fn is_open(&self, now: i64) -> bool {
+        now >= self.start_ts && (self.end_ts == 0 || now < self.end_ts)
+    }
+}
+
+/// Maximum length of a funding round label
+pub const MAX_ROUND_NAME_LENGTH: usize = 32;
+
+/// Validation limits of Offering.round_name
+pub const ROUND_NAME_LIMIT: TextLimit = TextLimit {
+    max_chars: MAX_ROUND_NAME_LENGTH,
+    max_bytes: MAX_ROUND_NAME_LENGTH,
+    required: true,
+};
+
+/// Round label given to offerings created by migrate_legacy_offering
+pub const LEGACY_ROUND_NAME: &str = "Legacy";
+
+/// Size of a version 2 Offering account (indexed, before rounds were named and time-boxed)
+pub const OFFERING_V2_SPACE: usize = 8 + OfferingV2::INIT_SPACE;
+
+/// Layout of indexed Offering accounts created before rounds (version 2, OFFERING_V2_SPACE bytes)
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct OfferingV2 {
+    pub version: u8,
+    pub business: Pubkey,
+    pub share_mint: Pubkey,
+    pub index: u32,
+    pub price_per_share_lamports: u64,
+    pub total_shares: u64,
+    pub remaining_shares: u64,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+impl OfferingV2 {
+    /// Reads a version 2 Offering account, or None if the account has another layout
+    pub fn load(info: &AccountInfo) -> Result<Option<Self>> {
+        require_keys_eq!(*info.owner, crate::ID, LocalshareError::InvalidBusiness);
+        let data = info.try_borrow_data()?;
+        if data.len() != OFFERING_V2_SPACE || data[..8] != *Offering::DISCRIMINATOR {
+            return Ok(None);
+        }
+        Ok(Some(Self::deserialize(&mut &data[8..])?))
+    }
+
+    /// The offering as a current-layout round: open-ended, named after its index,
+    /// with raised_lamports estimated at the round price (version 2 didn't track it)
+    pub fn upgrade(self) -> Result<Offering> {
+        let sold = self.total_shares.saturating_sub(self.remaining_shares);
+        Ok(Offering {
+            version: OFFERING_VERSION,
+            business: self.business,
+            share_mint: self.share_mint,
+            index: self.index,
+            round_name: format!("Round {}", self.index),
+            price_per_share_lamports: self.price_per_share_lamports,
+            total_shares: self.total_shares,
+            remaining_shares: self.remaining_shares,
+            start_ts: 0,
+            end_ts: 0,
+            raised_lamports: sold
+                .checked_mul(self.price_per_share_lamports)
+                .ok_or(LocalshareError::MathOverflow)?,
+            is_active: self.is_active,
+            bump: self.bump,
+            reserved: [0; 64],
+        })
+    }
+}
+
+/// Aggregate funding statistics of a business across all rounds
+/// PDA: ["business_stats", business.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct BusinessStats {
+    /// Business these statistics belong to
+    pub business: Pubkey,
+    
+    /// Lamports raised across all rounds and subscriptions
+    pub total_raised_lamports: u64,
+    
+    /// Shares sold across all rounds and subscriptions
+    pub total_shares_sold: u64,
+    
+    /// Rounds created (including migrated ones)
+    pub rounds_opened: u32,
+    
+    /// Rounds that sold out or were closed (by the owner, or by anyone after end_ts)
+    pub rounds_completed: u32,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Purchase history of an investor in a business (cost basis)
+/// Updated by buy_shares_from_offering and claim_allocation (subscription allocations)
+/// PDA: ["position", business.key(), investor.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct InvestorPosition {
+    /// Business the shares were bought from
+    pub business: Pubkey,
+    
+    /// Investor wallet
+    pub investor: Pubkey,
+    
+    /// Shares bought across all purchases
+    pub shares_bought: u64,
+    
+    /// Lamports paid across all purchases
+    pub lamports_spent: u64,
+    
+    /// Unix timestamp of the first purchase
+    pub first_purchase_ts: i64,
+    
+    /// Unix timestamp of the latest purchase
+    pub last_purchase_ts: i64,
+    
+    /// Number of purchases
+    pub purchase_count: u32,
+    
+    /// Lamports paid into the milestone escrow (refundable if a milestone fails)
+    pub escrowed_lamports: u64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl InvestorPosition {
+    /// Adds a purchase to the position, initializing it on the first one
+    pub fn record(
+        &mut self,
+        business: Pubkey,
+        investor: Pubkey,
+        shares: u64,
+        lamports: u64,
+        now: i64,
+        bump: u8,
+    ) -> Result<()> {
+        if self.purchase_count == 0 {
+            self.business = business;
+            self.investor = investor;
+            self.first_purchase_ts = now;
+            self.bump = bump;
+        }
+        self.shares_bought = self
+            .shares_bought
+            .checked_add(shares)
+            .ok_or(LocalshareError::MathOverflow)?;
+        self.lamports_spent = self
+            .lamports_spent
+            .checked_add(lamports)
+            .ok_or(LocalshareError::MathOverflow)?;
+        self.last_purchase_ts = now;
+        self.purchase_count = self
+            .purchase_count
+            .checked_add(1)
+            .ok_or(LocalshareError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Adds lamports paid into the milestone escrow
+    pub fn add_escrowed(&mut self, lamports: u64) -> Result<()> {
+        self.escrowed_lamports = self
+            .escrowed_lamports
+            .checked_add(lamports)
+            .ok_or(LocalshareError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// Proof of a share purchase, for investors' accounting and paperwork
+/// Created on request by buy_shares_from_offering and never modified or closed
+/// PDA: ["receipt", business.key(), buyer.key(), sequence]
+#[account]
+#[derive(InitSpace)]
+pub struct PurchaseReceipt {
+    /// Buyer of the shares
+    pub buyer: Pubkey,
+    
+    /// Business the shares were bought from
+    pub business: Pubkey,
+    
+    /// Offering the shares came from
+    pub offering: Pubkey,
+    
+    /// Shares bought
+    pub shares: u64,
+    
+    /// Executed price per share (blended average across tiers)
+    pub price_per_share_lamports: u64,
+    
+    /// Total lamports paid
+    pub total_lamports: u64,
+    
+    /// Slot of the purchase
+    pub slot: u64,
+    
+    /// Unix timestamp of the purchase
+    pub timestamp: i64,
+    
+    /// Buyer's purchase number in this business (InvestorPosition.purchase_count before it)
+    pub sequence: u32,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Size of a legacy (version 1) Offering account
+pub const LEGACY_OFFERING_SPACE: usize = 8 + LegacyOffering::INIT_SPACE;
+
+/// Layout of Offering accounts created before offerings were indexed (version 1)
+/// PDA: ["offering", business.key(), legacy_mint.key()]
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct LegacyOffering {
+    pub business: Pubkey,
+    pub share_mint: Pubkey,
+    pub payment_mint: Pubkey,
+    pub price_per_share: u64,
+    pub remaining_shares: u64,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+impl LegacyOffering {
+    /// Reads a legacy Offering account, rejecting anything that isn't one
+    pub fn load(info: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(*info.owner, crate::ID, LocalshareError::InvalidLegacyOffering);
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() == LEGACY_OFFERING_SPACE && data[..8] == *Offering::DISCRIMINATOR,
+            LocalshareError::InvalidLegacyOffering
+        );
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+
+    /// Shares a migration moves out of the business vault: the remaining shares of an active
+    /// legacy offering, and one share for each legacy token held by investors (outside the
+    /// legacy vault), reserved for swap_legacy_shares
+    pub fn migration_shares(&self, legacy_supply: u64, unsold_legacy: u64) -> Result<(u64, u64)> {
+        let offered = if self.is_active { self.remaining_shares } else { 0 };
+        let circulating_legacy = legacy_supply
+            .checked_sub(unsold_legacy)
+            .ok_or(LocalshareError::MathOverflow)?;
+        Ok((offered, circulating_legacy))
+    }
+
+    /// Checks a swap of `amount` legacy tokens against the `reserved` shares left in the
+    /// legacy swap vault
+    pub fn check_swap(reserved: u64, amount: u64) -> Result<()> {
+        require!(amount > 0, LocalshareError::InvalidShareAmount);
+        require!(reserved >= amount, LocalshareError::InsufficientShares);
+        Ok(())
+    }
+}
+
+/// Published commitment to a business cap table at a given slot
+/// PDA: ["cap_table", business.key(), slot]
+#[account]
+#[derive(InitSpace)]
+pub struct CapTableSnapshot {
+    /// Business the snapshot belongs to
+    pub business: Pubkey,
+
+    /// Slot at which the cap table was read
+    pub slot: u64,
+
+    /// Merkle root over (holder, shares) leaves
+    pub merkle_root: [u8; 32],
+
+    /// Number of holders included in the snapshot
+    pub holder_count: u32,
+
+    /// Shares held by investors outside program vaults
+    pub circulating_shares: u64,
+
+    /// Unix timestamp when the snapshot was published
+    pub published_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Merkle-root based distribution of shares to many claimants
+/// PDA: ["airdrop", business.key(), merkle_root]
+#[account]
+#[derive(InitSpace)]
+pub struct ShareAirdrop {
+    /// Business distributing the shares
+    pub business: Pubkey,
+
+    /// Share mint being distributed
+    pub share_mint: Pubkey,
+
+    /// Merkle root over (claimant, amount) leaves
+    pub merkle_root: [u8; 32],
+
+    /// Shares moved into the airdrop vault
+    pub total_amount: u64,
+
+    /// Shares claimed so far
+    pub claimed_amount: u64,
+
+    /// Number of claims processed
+    pub num_claims: u32,
+
+    /// Unix timestamp when the airdrop was created
+    pub created_at: i64,
+
+    /// Unix timestamp after which claims are rejected and the airdrop can be closed
+    pub expiry_ts: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Receipt proving a claimant already claimed from an airdrop
+/// PDA: ["airdrop_claim", airdrop.key(), claimant.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct ClaimReceipt {
+    /// Airdrop the claim belongs to
+    pub airdrop: Pubkey,
+
+    /// Wallet that claimed
+    pub claimant: Pubkey,
+
+    /// Shares claimed
+    pub amount: u64,
+
+    /// Unix timestamp of the claim
+    pub claimed_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Vesting schedule for founder or employee shares
+/// PDA: ["vesting", business.key(), beneficiary]
+#[account]
+#[derive(InitSpace)]
+pub struct VestingSchedule {
+    /// Business granting the shares
+    pub business: Pubkey,
+
+    /// Wallet receiving the vested shares
+    pub beneficiary: Pubkey,
+
+    /// Share mint being vested
+    pub share_mint: Pubkey,
+
+    /// Shares granted (reduced to the vested amount on revocation)
+    pub total_amount: u64,
+
+    /// Shares already released to the beneficiary
+    pub released_amount: u64,
+
+    /// Unix timestamp when vesting starts
+    pub start_ts: i64,
+
+    /// Unix timestamp before which nothing is unlocked
+    pub cliff_ts: i64,
+
+    /// Unix timestamp when everything is unlocked
+    pub end_ts: i64,
+
+    /// Whether the owner can revoke unvested shares
+    pub revocable: bool,
+
+    /// Whether the schedule was revoked
+    pub revoked: bool,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl VestingSchedule {
+    /// Shares unlocked at `now` (linear between start and end, zero before the cliff)
+    pub fn vested_amount(&self, now: i64) -> Result<u64> {
+        if self.revoked || now >= self.end_ts {
+            return Ok(self.total_amount);
+        }
+        if now < self.cliff_ts {
+            return Ok(0);
+        }
+
+        let elapsed = (now - self.start_ts) as u128;
+        let duration = (self.end_ts - self.start_ts) as u128;
+        let vested = (self.total_amount as u128)
+            .checked_mul(elapsed)
+            .ok_or(LocalshareError::MathOverflow)?
+            / duration;
+
+        Ok(vested as u64)
+    }
+}
+
+/// Shareholder governance rules of a business
+/// PDA: ["governance", business.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct Governance {
+    /// Business governed by these rules
+    pub business: Pubkey,
+
+    /// Minimum participation, in basis points of total_shares
+    pub quorum_bps: u16,
+
+    /// Minimum share of yes votes among cast votes, in basis points
+    pub approval_threshold_bps: u16,
+
+    /// Minimum shares a non-owner must hold to create a proposal
+    pub proposal_threshold: u64,
+
+    /// Number of proposals created (next proposal id)
+    pub proposal_count: u64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Lifecycle state of a governance proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum ProposalStatus {
+    /// Voting window open or awaiting finalization
+    Active,
+    /// Quorum reached and approval threshold met
+    Succeeded,
+    /// Quorum not reached or approval threshold not met
+    Defeated,
+}
+
+/// Maximum length of a proposal title
+pub const MAX_PROPOSAL_TITLE_LENGTH: usize = 64;
+
+/// Maximum length of a proposal description URI
+pub const MAX_DESCRIPTION_URI_LENGTH: usize = 200;
+
+/// Validation limits of Proposal.title
+pub const PROPOSAL_TITLE_LIMIT: TextLimit = TextLimit {
+    max_chars: MAX_PROPOSAL_TITLE_LENGTH,
+    max_bytes: MAX_PROPOSAL_TITLE_LENGTH,
+    required: true,
+};
+
+/// Validation limits of Proposal.description_uri (optional)
+pub const DESCRIPTION_URI_LIMIT: TextLimit = TextLimit {
+    max_chars: MAX_DESCRIPTION_URI_LENGTH,
+    max_bytes: MAX_DESCRIPTION_URI_LENGTH,
+    required: false,
+};
+
+/// Governance proposal voted on by shareholders
+/// PDA: ["proposal", business.key(), id]
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    /// Business the proposal belongs to
+    pub business: Pubkey,
+
+    /// Wallet that created the proposal
+    pub proposer: Pubkey,
+
+    /// Sequential proposal id within the business
+    pub id: u64,
+
+    /// Proposal title (maximum 64 bytes)
+    #[max_len(MAX_PROPOSAL_TITLE_LENGTH)]
+    pub title: String,
+
+    /// URI of the full proposal description (maximum 200 bytes)
+    #[max_len(MAX_DESCRIPTION_URI_LENGTH)]
+    pub description_uri: String,
+
+    /// Unix timestamp when voting opened
+    pub voting_start: i64,
+
+    /// Unix timestamp when voting closes
+    pub voting_end: i64,
+
+    /// Votes required for quorum (fixed at creation)
+    pub quorum_votes: u64,
+
+    /// Approval threshold in basis points (fixed at creation)
+    pub approval_threshold_bps: u16,
+
+    /// Shares voted in favor
+    pub yes_votes: u64,
+
+    /// Shares voted against
+    pub no_votes: u64,
+
+    /// Current proposal status
+    pub status: ProposalStatus,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Proposal {
+    /// Outcome of the vote: Succeeded if quorum is reached and the yes votes are at least
+    /// approval_threshold_bps of the votes cast, Defeated otherwise
+    pub fn tally(&self) -> Result<ProposalStatus> {
+        let total_votes = self
+            .yes_votes
+            .checked_add(self.no_votes)
+            .ok_or(LocalshareError::MathOverflow)?;
+        let quorum_reached = total_votes > 0 && total_votes >= self.quorum_votes;
+        let approved = (self.yes_votes as u128) * (BPS_DENOMINATOR as u128)
+            >= (total_votes as u128) * (self.approval_threshold_bps as u128);
+
+        Ok(if quorum_reached && approved {
+            ProposalStatus::Succeeded
+        } else {
+            ProposalStatus::Defeated
+        })
+    }
+}
+
+/// Record of a single wallet's vote on a proposal
+/// PDA: ["vote", proposal.key(), voter.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct VoteRecord {
+    /// Proposal voted on
+    pub proposal: Pubkey,
+
+    /// Wallet that voted
+    pub voter: Pubkey,
+
+    /// Whether the vote was in favor
+    pub approve: bool,
+
+    /// Shares locked for the vote
+    pub weight: u64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Maximum number of early-bird tiers in tiered pricing
+pub const MAX_PRICING_TIERS: usize = 5;
+
+/// Early-bird tranche of tiered pricing
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace)]
+pub struct PricingTier {
+    /// Shares sold at this tier's price
+    pub shares_in_tier: u64,
+    /// Price per share in lamports within the tier
+    pub price_per_share_lamports: u64,
+}
+
+/// Pricing mode of an offering
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PricingMode {
+    /// Fixed Offering.price_per_share_lamports
+    Fixed,
+    /// Price decays linearly from start_price to floor_price over [start_ts, end_ts]
+    DutchAuction {
+        start_price: u64,
+        floor_price: u64,
+        start_ts: i64,
+        end_ts: i64,
+    },
+    /// The first `tier_count` tiers are sold in order, then the fixed price applies
+    Tiered {
+        tiers: [PricingTier; MAX_PRICING_TIERS],
+        tier_count: u8,
+    },
+}
+
+impl PricingMode {
+    /// Validates the mode parameters
+    pub fn validate(&self) -> Result<()> {
+        match *self {
+            PricingMode::Fixed => {}
+            PricingMode::DutchAuction { start_price, floor_price, start_ts, end_ts } => {
+                require!(floor_price > 0, LocalshareError::InvalidPrice);
+                require!(start_price > floor_price, LocalshareError::InvalidPricingMode);
+                require!(end_ts > start_ts, LocalshareError::InvalidPricingMode);
+            }
+            PricingMode::Tiered { tiers, tier_count } => {
+                let tier_count = tier_count as usize;
+                require!(
+                    tier_count > 0 && tier_count <= MAX_PRICING_TIERS,
+                    LocalshareError::InvalidPricingMode
+                );
+                for tier in &tiers[..tier_count] {
+                    require!(tier.shares_in_tier > 0, LocalshareError::InvalidPricingMode);
+                    require!(tier.price_per_share_lamports > 0, LocalshareError::InvalidPrice);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Cost of buying `amount` shares at `now` when `shares_sold` were already sold
+    /// `fixed_price` applies to fixed-price sales and to shares beyond the last tier
+    pub fn quote(&self, now: i64, fixed_price: u64, shares_sold: u64, amount: u64) -> Result<Quote> {
+        match *self {
+            PricingMode::Fixed => Quote::uniform(fixed_price, amount),
+            PricingMode::DutchAuction { start_price, floor_price, start_ts, end_ts } => {
+                require!(now >= start_ts, LocalshareError::AuctionNotStarted);
+                let price = if now >= end_ts {
+                    floor_price
+                } else {
+                    let elapsed = (now - start_ts) as u128;
+                    let duration = (end_ts - start_ts) as u128;
+                    let decay = ((start_price - floor_price) as u128)
+                        .checked_mul(elapsed)
+                        .ok_or(LocalshareError::MathOverflow)?
+                        / duration;
+                    start_price - decay as u64
+                };
+                Quote::uniform(price, amount)
+            }
+            PricingMode::Tiered { tiers, tier_count } => {
+                // Walk the tiers from the current position, splitting the purchase at boundaries
+                let mut cost = 0u64;
+                let mut max_price_per_share = 0u64;
+                let mut remaining = amount;
+                let mut tier_start = 0u64;
+                for tier in &tiers[..tier_count as usize] {
+                    let tier_end = tier_start
+                        .checked_add(tier.shares_in_tier)
+                        .ok_or(LocalshareError::MathOverflow)?;
+                    let position = shares_sold
+                        .checked_add(amount - remaining)
+                        .ok_or(LocalshareError::MathOverflow)?;
+                    if remaining > 0 && position < tier_end {
+                        let in_tier = remaining.min(tier_end - position);
+                        cost = tier
+                            .price_per_share_lamports
+                            .checked_mul(in_tier)
+                            .and_then(|tier_cost| cost.checked_add(tier_cost))
+                            .ok_or(LocalshareError::MathOverflow)?;
+                        max_price_per_share = max_price_per_share.max(tier.price_per_share_lamports);
+                        remaining -= in_tier;
+                    }
+                    tier_start = tier_end;
+                }
+
+                // Shares past the last tier are sold at the fixed price
+                if remaining > 0 {
+                    cost = fixed_price
+                        .checked_mul(remaining)
+                        .and_then(|rest| cost.checked_add(rest))
+                        .ok_or(LocalshareError::MathOverflow)?;
+                    max_price_per_share = max_price_per_share.max(fixed_price);
+                }
+                Ok(Quote {
+                    total_lamports: cost,
+                    max_price_per_share,
+                })
+            }
+        }
+    }
+}
+
+/// Cost of a purchase under a PricingMode
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quote {
+    /// Lamports due for the whole purchase
+    pub total_lamports: u64,
+
+    /// Highest price paid for a single share of the purchase (checked against the buyer's maximum)
+    pub max_price_per_share: u64,
+}
+
+impl Quote {
+    /// Every share at the same price
+    fn uniform(price_per_share: u64, amount: u64) -> Result<Self> {
+        Ok(Self {
+            total_lamports: price_per_share
+                .checked_mul(amount)
+                .ok_or(LocalshareError::MathOverflow)?,
+            max_price_per_share: price_per_share,
+        })
+    }
+}
+
+/// Pricing mode of an offering, set by create_offering
+/// PDA: ["pricing", offering.key()]
+/// Offerings without this account sell at their fixed price_per_share_lamports
+/// Tiers advance with the offering's sold shares (total_shares - remaining_shares)
+#[account]
+#[derive(InitSpace)]
+pub struct OfferingPricing {
+    /// Offering this pricing applies to
+    pub offering: Pubkey,
+
+    /// Pricing mode
+    pub mode: PricingMode,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl OfferingPricing {
+    /// Loads the pricing account if it has been created
+    pub fn load(info: &AccountInfo) -> Result<Option<Self>> {
+        if info.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*info.owner, crate::ID, LocalshareError::InvalidBusiness);
+        let data = info.try_borrow_data()?;
+        Ok(Some(Self::try_deserialize(&mut &data[..])?))
+    }
+}
+
+/// Fair-launch subscription for an oversubscribed offering
+/// PDA: ["subscription", business.key()]
+/// Also escrows committed lamports until they are claimed
+#[account]
+#[derive(InitSpace)]
+pub struct Subscription {
+    /// Business running the subscription
+    pub business: Pubkey,
+
+    /// Share mint being sold
+    pub share_mint: Pubkey,
+
+    /// Price per share in lamports
+    pub price_per_share_lamports: u64,
+
+    /// Shares available in the subscription
+    pub shares_offered: u64,
+
+    /// Unix timestamp when commitments open
+    pub start_ts: i64,
+
+    /// Unix timestamp when commitments close
+    pub end_ts: i64,
+
+    /// Lamports committed by all investors
+    pub total_committed_lamports: u64,
+
+    /// Shares requested by all investors (set at settlement)
+    pub shares_demanded: u64,
+
+    /// Number of investors who committed
+    pub commitment_count: u32,
+
+    /// Number of commitments already claimed or refunded
+    pub claimed_count: u32,
+
+    /// Whether the subscription has been settled
+    pub settled: bool,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Subscription {
+    /// Time after which unclaimed commitments can be refunded (end_ts + SUBSCRIPTION_CLAIM_PERIOD_SECONDS)
+    pub fn claim_deadline(&self) -> Result<i64> {
+        Ok(self
+            .end_ts
+            .checked_add(SUBSCRIPTION_CLAIM_PERIOD_SECONDS)
+            .ok_or(LocalshareError::MathOverflow)?)
+    }
+
+    /// Shares allocated to a commitment of `committed_lamports` (pro-rata when oversubscribed)
+    pub fn allocation_for(&self, committed_lamports: u64) -> Result<u64> {
+        let requested = committed_lamports / self.price_per_share_lamports;
+        if self.shares_demanded <= self.shares_offered {
+            return Ok(requested);
+        }
+
+        let allocation = (requested as u128)
+            .checked_mul(self.shares_offered as u128)
+            .ok_or(LocalshareError::MathOverflow)?
+            / self.shares_demanded as u128;
+        Ok(allocation as u64)
+    }
+}
+
+/// Time investors have after a subscription ends to claim their allocation
+pub const SUBSCRIPTION_CLAIM_PERIOD_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// An investor's commitment to a subscription
+/// Closed when claimed or refunded
+/// PDA: ["commitment", subscription.key(), investor.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct SubscriptionCommitment {
+    /// Subscription committed to
+    pub subscription: Pubkey,
+
+    /// Investor who committed
+    pub investor: Pubkey,
+
+    /// Lamports committed
+    pub committed_lamports: u64,
+
+    /// Whether the allocation has been claimed
+    pub claimed: bool,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Offer by a business to repurchase its shares at a fixed price
+/// Escrowed lamports are held directly in this account
+/// PDA: ["buyback", business.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct Buyback {
+    /// Business buying back its shares
+    pub business: Pubkey,
+    
+    /// Treasury that funded the escrow and receives the refund
+    pub treasury: Pubkey,
+    
+    /// Price paid per share in lamports
+    pub price_per_share_lamports: u64,
+    
+    /// Maximum number of shares the business will buy
+    pub max_shares: u64,
+    
+    /// Shares bought back so far
+    pub shares_bought: u64,
+    
+    /// Lamports still escrowed for sellers (excludes rent)
+    pub escrow_lamports: u64,
+    
+    /// Unix timestamp after which sales are rejected
+    pub expiry_ts: i64,
+    
+    /// Whether bought-back shares are burned instead of returned to the shares_vault
+    pub burn: bool,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Maximum length of a withdrawal purpose
+pub const MAX_PURPOSE_LENGTH: usize = 64;
+
+/// Shortest withdrawal delay of a treasury: every withdrawal is public before it can execute
+pub const MIN_WITHDRAWAL_DELAY_SECONDS: i64 = 60;
+
+/// Validation limits of WithdrawalRequest.purpose
+pub const PURPOSE_LIMIT: TextLimit = TextLimit {
+    max_chars: MAX_PURPOSE_LENGTH,
+    max_bytes: MAX_PURPOSE_LENGTH,
+    required: true,
+};
+
+/// Program-controlled treasury holding the proceeds of a business
+/// Lamports are held directly in this account and leave only through withdrawal requests
+/// PDA: ["treasury", business.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct BusinessTreasury {
+    /// Business this treasury belongs to
+    pub business: Pubkey,
+    
+    /// Seconds between a withdrawal request and its execution
+    pub withdrawal_delay_seconds: i64,
+    
+    /// Maximum lamports withdrawn per period (0 = unlimited)
+    pub max_withdrawal_per_period: u64,
+    
+    /// Length of a rate-limit period in seconds
+    pub period_seconds: i64,
+    
+    /// Start of the current rate-limit period
+    pub period_start: i64,
+    
+    /// Lamports withdrawn in the current period
+    pub withdrawn_in_period: u64,
+    
+    /// Lamports withdrawn since creation
+    pub total_withdrawn: u64,
+    
+    /// Number of withdrawal requests made (next request id)
+    pub withdrawal_count: u64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl BusinessTreasury {
+    /// Address of the treasury PDA of `business`
+    pub fn address(business: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"treasury", business.as_ref()], &crate::ID).0
+    }
+
+    /// Lamports held above the rent-exempt reserve
+    pub fn available_lamports(info: &AccountInfo) -> Result<u64> {
+        let reserve = Rent::get()?.minimum_balance(info.data_len());
+        Ok(info.lamports().saturating_sub(reserve))
+    }
+
+    /// Checks the withdrawal rules of a new treasury
+    /// A rate limit needs a period, while the delay applies with or without one
+    pub fn validate_rules(
+        withdrawal_delay_seconds: i64,
+        max_withdrawal_per_period: u64,
+        period_seconds: i64,
+    ) -> Result<()> {
+        require!(
+            withdrawal_delay_seconds >= MIN_WITHDRAWAL_DELAY_SECONDS,
+            LocalshareError::InvalidTreasuryConfig
+        );
+        require!(
+            max_withdrawal_per_period == 0 || period_seconds > 0,
+            LocalshareError::InvalidTreasuryConfig
+        );
+        Ok(())
+    }
+
+    /// Counts a withdrawal of `amount` at `now` against the per-period rate limit
+    pub fn record_withdrawal(&mut self, now: i64, amount: u64) -> Result<()> {
+        if self.max_withdrawal_per_period > 0 {
+            // Start a new rate-limit window when the previous one has elapsed
+            if now >= self.period_start.saturating_add(self.period_seconds) {
+                self.period_start = now;
+                self.withdrawn_in_period = 0;
+            }
+            let withdrawn = self
+                .withdrawn_in_period
+                .checked_add(amount)
+                .ok_or(LocalshareError::MathOverflow)?;
+            require!(
+                withdrawn <= self.max_withdrawal_per_period,
+                LocalshareError::WithdrawalLimitExceeded
+            );
+            self.withdrawn_in_period = withdrawn;
+        }
+        self.total_withdrawn = self
+            .total_withdrawn
+            .checked_add(amount)
+            .ok_or(LocalshareError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// Pending withdrawal from a business treasury
+/// PDA: ["withdrawal", business_treasury.key(), id]
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawalRequest {
+    /// Treasury the funds are withdrawn from
+    pub treasury: Pubkey,
+    
+    /// Sequential request id within the treasury
+    pub id: u64,
+    
+    /// Account receiving the funds
+    pub recipient: Pubkey,
+    
+    /// Lamports to withdraw
+    pub amount: u64,
+    
+    /// Public purpose of the withdrawal (maximum 64 characters)
+    #[max_len(MAX_PURPOSE_LENGTH)]
+    pub purpose: String,
+    
+    /// Unix timestamp of the request
+    pub requested_at: i64,
+    
+    /// Unix timestamp from which the request can be executed
+    pub executable_at: i64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Who can approve milestone releases
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum MilestoneApproval {
+    /// Protocol administrator (Config.admin)
+    Admin,
+    /// A dedicated oracle signer, independent of the owner and holding a Verifier role
+    Oracle { signer: Pubkey },
+    /// A succeeded governance proposal linked to the milestone
+    ShareholderVote,
+}
+
+/// Amount released by a milestone
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum MilestoneAmount {
+    /// Fixed number of lamports
+    Lamports(u64),
+    /// Basis points of everything deposited into the escrow
+    Bps(u16),
+}
+
+impl MilestoneAmount {
+    /// Lamports to release given the total deposited into the escrow
+    pub fn lamports(&self, deposited: u64) -> Result<u64> {
+        match *self {
+            MilestoneAmount::Lamports(lamports) => Ok(lamports),
+            MilestoneAmount::Bps(bps) => {
+                let amount = deposited as u128 * bps as u128 / BPS_DENOMINATOR as u128;
+                u64::try_from(amount).map_err(|_| LocalshareError::MathOverflow.into())
+            }
+        }
+    }
+}
+
+/// Lifecycle state of a milestone
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum MilestoneStatus {
+    /// Awaiting approval
+    Pending,
+    /// Approved and released
+    Approved,
+    /// Deadline missed or vote defeated
+    Failed,
+}
+
+/// Escrow holding business proceeds until milestones are approved
+/// Lamports are held directly in this account
+/// PDA: ["milestone_escrow", business.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct MilestoneEscrow {
+    /// Business whose proceeds are escrowed
+    pub business: Pubkey,
+    
+    /// Account receiving released funds (business.treasury when the escrow was enabled)
+    pub release_to: Pubkey,
+    
+    /// Who approves milestones
+    pub approval: MilestoneApproval,
+    
+    /// Number of milestones added (next milestone index)
+    pub milestone_count: u32,
+    
+    /// Lamports released so far
+    pub released_lamports: u64,
+    
+    /// Lamports paid in by investors and credited to their positions
+    pub contributed_lamports: u64,
+    
+    /// Whether a milestone failed (escrow is refunding)
+    pub failed: bool,
+    
+    /// Unix timestamp of the failure (start of the refund period)
+    pub failed_at: i64,
+    
+    /// Lamports available for refunds when the escrow failed
+    pub refund_pool: u64,
+    
+    /// Contributions credited when the escrow failed (denominator for refunds)
+    pub refund_basis: u64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl MilestoneEscrow {
+    /// Address of the milestone escrow PDA of `business`
+    pub fn address(business: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"milestone_escrow", business.as_ref()], &crate::ID).0
+    }
+
+    /// Credits an investor contribution to the escrow behind a business treasury account
+    /// Purchase paths only see the escrow as `treasury`, so it is updated in place
+    pub fn record_contribution(info: &AccountInfo, lamports: u64) -> Result<()> {
+        let mut escrow = Self::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        escrow.contributed_lamports = escrow
+            .contributed_lamports
+            .checked_add(lamports)
+            .ok_or(LocalshareError::MathOverflow)?;
+        escrow.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+        Ok(())
+    }
+
+    /// Time after which the rest of a failed escrow can be swept (failed_at + MILESTONE_REFUND_PERIOD_SECONDS)
+    pub fn refund_deadline(&self) -> Result<i64> {
+        Ok(self
+            .failed_at
+            .checked_add(MILESTONE_REFUND_PERIOD_SECONDS)
+            .ok_or(LocalshareError::MathOverflow)?)
+    }
+}
+
+/// Time investors have to claim milestone refunds after a failure (30 days)
+pub const MILESTONE_REFUND_PERIOD_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// A funding milestone of a business
+/// PDA: ["milestone", business.key(), index]
 #[account]
-pub struct Business {
-    /// Business owner (entrepreneur)
-    pub owner: Pubkey,
+#[derive(InitSpace)]
+pub struct Milestone {
+    /// Business the milestone belongs to
+    pub business: Pubkey,
     
-    /// Business name (maximum 50 characters)
-    pub name: String,
+    /// Index within the escrow
+    pub index: u32,
     
-    /// Share mint of this business (NFT or Fungible Token)
+    /// Hash of the off-chain milestone description
+    pub description_hash: [u8; 32],
+    
+    /// Amount released on approval
+    pub amount: MilestoneAmount,
+    
+    /// Unix timestamp by which the milestone must be approved
+    pub deadline: i64,
+    
+    /// Governance proposal deciding the milestone (ShareholderVote approval only)
+    pub proposal_id: u64,
+    
+    /// Unix timestamp when the milestone was added
+    pub created_at: i64,
+    
+    /// Current milestone status
+    pub status: MilestoneStatus,
+    
+    /// Lamports released on approval
+    pub released_lamports: u64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Marker that an investor claimed their milestone refund
+/// PDA: ["milestone_refund", milestone_escrow.key(), investor.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct MilestoneRefund {
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Fixed-point scale of reward-per-share accumulators
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+/// Staking pool of a business: owns the staking vault and tracks total stake
+/// PDA: ["stake_pool", business.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct StakePool {
+    /// Business whose shares are staked
+    pub business: Pubkey,
+    
+    /// Share mint accepted by the staking vault
     pub share_mint: Pubkey,
     
-    /// Total number of shares issued
-    pub total_shares: u64,
+    /// Shares currently staked
+    pub total_staked: u64,
     
-    /// Price per share in lamports
-    pub price_per_share_lamports: u64,
+    /// Whether a RevenueShare accrues on this pool (stake changes must then settle it)
+    pub revenue_share_enabled: bool,
     
-    /// Treasury account for receiving payments
-    pub treasury: Pubkey,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl StakePool {
+    /// Current revenue accumulator for stake changes
+    /// Fails if revenue share is enabled but its account was not supplied
+    pub fn revenue_accumulator(&self, revenue_share: Option<&RevenueShare>) -> Result<u128> {
+        match revenue_share {
+            Some(revenue_share) => Ok(revenue_share.acc_revenue_per_share),
+            None => {
+                require!(!self.revenue_share_enabled, LocalshareError::RevenueShareRequired);
+                Ok(0)
+            }
+        }
+    }
+}
+
+/// Revenue committed by a business to its staked shareholders
+/// Deposited lamports are held directly in this account
+/// PDA: ["revenue_share", business.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct RevenueShare {
+    /// Business sharing its revenue
+    pub business: Pubkey,
     
-    /// Whether the business is listed on the marketplace
-    pub is_listed: bool,
+    /// Additional signer allowed to deposit revenue (e.g. a POS integration)
+    pub depositor: Pubkey,
+    
+    /// Committed share of revenue in basis points (informational)
+    pub revenue_bps: u16,
+    
+    /// Revenue per staked share, scaled by REWARD_PRECISION
+    pub acc_revenue_per_share: u128,
+    
+    /// Lamports deposited since creation
+    pub total_deposited: u64,
+    
+    /// Lamports claimed since creation
+    pub total_claimed: u64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Shares staked by a holder in a business staking vault
+/// PDA: ["stake", business.key(), owner.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct StakePosition {
+    /// Business whose shares are staked
+    pub business: Pubkey,
+    
+    /// Holder owning the stake
+    pub owner: Pubkey,
+    
+    /// Shares staked
+    pub amount: u64,
+    
+    /// Accumulator value already accounted for (amount * acc / REWARD_PRECISION)
+    pub reward_debt: u128,
+    
+    /// Revenue settled but not yet claimed
+    pub pending_revenue: u64,
+    
+    /// Unix timestamp until which the stake backs an open vote
+    pub locked_until: i64,
+    
+    /// Unix timestamp of the latest stake increase
+    pub staked_at: i64,
+    
+    /// Amount staked just before the latest increase
+    pub checkpoint_amount: u64,
+    
+    /// Unix timestamp of the increase before the latest one (checkpoint_amount held since then)
+    pub checkpoint_at: i64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl StakePosition {
+    /// Adds `amount` staked at `now`, moving the checkpoint to the amount held before it
+    /// Increases within the same second share one checkpoint
+    pub fn record_stake(&mut self, amount: u64, now: i64) -> Result<()> {
+        if now != self.staked_at {
+            self.checkpoint_amount = self.amount;
+            self.checkpoint_at = self.staked_at;
+            self.staked_at = now;
+        }
+        self.amount = self
+            .amount
+            .checked_add(amount)
+            .ok_or(LocalshareError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Stake held continuously since before `voting_start` (a lower bound)
+    /// Stake added once a proposal opened never counts on it; a position increased more
+    /// than once since then only counts what the checkpoint proves, possibly nothing
+    pub fn voting_weight(&self, voting_start: i64) -> u64 {
+        if self.staked_at < voting_start {
+            self.amount
+        } else if self.checkpoint_at < voting_start {
+            self.checkpoint_amount.min(self.amount)
+        } else {
+            0
+        }
+    }
+
+    /// Revenue accrued by the current amount at accumulator `acc`
+    pub fn accrued(&self, acc: u128) -> Result<u128> {
+        Ok((self.amount as u128)
+            .checked_mul(acc)
+            .ok_or(LocalshareError::MathOverflow)?
+            / REWARD_PRECISION)
+    }
+
+    /// Moves revenue accrued since the last settlement into pending_revenue
+    pub fn settle(&mut self, acc: u128) -> Result<()> {
+        let accrued = self.accrued(acc)?.saturating_sub(self.reward_debt);
+        self.pending_revenue = self
+            .pending_revenue
+            .checked_add(u64::try_from(accrued).map_err(|_| LocalshareError::MathOverflow)?)
+            .ok_or(LocalshareError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// Verification state of a business
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum VerificationStatus {
+    /// Never reviewed
+    Unverified,
+    /// Awaiting review
+    Pending,
+    /// Approved by a reviewer
+    Verified,
+    /// Rejected by a reviewer (can be resubmitted)
+    Rejected,
+    /// Suspended for fraud; purchases, subscription commitments and claims are blocked
+    Suspended,
+}
+
+/// Verification record of a business
+/// Businesses without this account are Unverified
+/// PDA: ["verification", business.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct BusinessVerification {
+    /// Business being verified
+    pub business: Pubkey,
+    
+    /// Current verification status
+    pub status: VerificationStatus,
+    
+    /// Status held before the current suspension, restored by reinstate_business
+    pub status_before_suspension: VerificationStatus,
+    
+    /// Admin or verifier behind the latest decision
+    pub reviewer: Pubkey,
+    
+    /// Unix timestamp of the latest status change
+    pub updated_at: i64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl BusinessVerification {
+    /// Reads the verification status, treating a missing account as Unverified
+    pub fn status_of(info: &AccountInfo) -> Result<VerificationStatus> {
+        if info.data_is_empty() {
+            return Ok(VerificationStatus::Unverified);
+        }
+        require_keys_eq!(*info.owner, crate::ID, LocalshareError::InvalidBusiness);
+        let data = info.try_borrow_data()?;
+        Ok(Self::try_deserialize(&mut &data[..])?.status)
+    }
+}
+
+/// Protocol-wide verification policy
+/// PDA: ["verification_policy"]
+#[account]
+#[derive(InitSpace)]
+pub struct VerificationPolicy {
+    /// Whether list_business requires a Verified business
+    pub require_verified_listing: bool,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl VerificationPolicy {
+    /// Whether listing requires verification (false if the policy was never set)
+    pub fn requires_verified_listing(info: &AccountInfo) -> Result<bool> {
+        if info.data_is_empty() {
+            return Ok(false);
+        }
+        require_keys_eq!(*info.owner, crate::ID, LocalshareError::InvalidBusiness);
+        let data = info.try_borrow_data()?;
+        Ok(Self::try_deserialize(&mut &data[..])?.require_verified_listing)
+    }
+}
+
+/// Privileged roles delegated by Config.admin
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum RoleKind {
+    /// Reviews business verification and admin-approved milestones
+    Verifier,
+    /// Suspends and freezes fraudulent listings
+    Pauser,
+}
+
+impl RoleKind {
+    /// First PDA seed of a Role of this kind
+    pub fn seed(&self) -> &'static [u8] {
+        match self {
+            RoleKind::Verifier => b"verifier",
+            RoleKind::Pauser => b"pauser",
+        }
+    }
+}
+
+/// Role granted to a wallet by Config.admin
+/// PDA: [kind.seed(), grantee]
+#[account]
+#[derive(InitSpace)]
+pub struct Role {
+    /// Kind of role
+    pub kind: RoleKind,
+    
+    /// Wallet holding the role
+    pub grantee: Pubkey,
+    
+    /// Admin who granted the role
+    pub granted_by: Pubkey,
+    
+    /// Unix timestamp of the grant
+    pub granted_at: i64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Role {
+    /// Whether `signer` is Config.admin or holds one of the `kinds` roles
+    /// The role account's address is checked by the caller's seeds constraint
+    pub fn authorizes(
+        config: &Config,
+        role: Option<&Role>,
+        kinds: &[RoleKind],
+        signer: &Pubkey,
+    ) -> bool {
+        *signer == config.admin
+            || role.is_some_and(|role| role.grantee == *signer && kinds.contains(&role.kind))
+    }
+}
+
+/// Emergency freeze state of a business
+/// Businesses without this account are not frozen
+/// PDA: ["freeze", business.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct BusinessFreeze {
+    /// Business the freeze applies to
+    pub business: Pubkey,
+    
+    /// Whether the business is currently frozen
+    pub frozen: bool,
+    
+    /// Reason code of the latest freeze or unfreeze
+    pub reason_code: u16,
+    
+    /// Wallet that placed the current freeze (default when unfrozen)
+    pub frozen_by: Pubkey,
+    
+    /// Unix timestamp of the latest change
+    pub updated_at: i64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl BusinessFreeze {
+    /// Whether the business is frozen, treating a missing account as not frozen
+    pub fn is_frozen(info: &AccountInfo) -> Result<bool> {
+        if info.data_is_empty() {
+            return Ok(false);
+        }
+        require_keys_eq!(*info.owner, crate::ID, LocalshareError::InvalidBusiness);
+        let data = info.try_borrow_data()?;
+        Ok(Self::try_deserialize(&mut &data[..])?.frozen)
+    }
+}
+
+/// Number of businesses per registry page
+/// Keeps a page within the 10240-byte limit of accounts created by the program
+pub const REGISTRY_PAGE_CAPACITY: usize = 250;
+
+/// One business in the registry
+#[zero_copy]
+pub struct RegistryEntry {
+    /// Business account
+    pub business: Pubkey,
+    
+    /// Whether the business is listed (1) or not (0)
+    pub listed: u8,
+    
+    /// Category of the business (BusinessCategory index, 0 = uncategorized)
+    pub category: u8,
+    
+    /// Geohash of the business (zeroed if unset)
+    pub region: [u8; REGION_CODE_LENGTH],
+    
+    /// Alignment padding
+    pub padding: [u8; 1],
+}
+
+/// Page of the registry of all businesses, for enumeration without getProgramAccounts
+/// Clients read pages 0, 1, ... until one is missing
+/// PDA: ["registry", page]
+#[account(zero_copy)]
+pub struct BusinessRegistry {
+    /// Page number
+    pub page: u32,
+    
+    /// Entries in use
+    pub count: u32,
+    
+    /// Businesses in registration order (only the first `count` are set)
+    pub entries: [RegistryEntry; REGISTRY_PAGE_CAPACITY],
+}
+
+impl BusinessRegistry {
+    /// Appends `business` to the page and records its position in the business
+    pub fn append(
+        loader: &AccountLoader<BusinessRegistry>,
+        business_key: Pubkey,
+        business: &mut Business,
+    ) -> Result<()> {
+        let mut registry = loader.load_mut()?;
+        let slot = registry.count as usize;
+        require!(slot < REGISTRY_PAGE_CAPACITY, LocalshareError::RegistryPageFull);
+
+        registry.entries[slot] = business.index_entry(business_key);
+        registry.count += 1;
+
+        business.in_registry = true;
+        business.registry_page = registry.page;
+        business.registry_slot = slot as u16;
+        Ok(())
+    }
+
+    /// Mirrors the business's listing, category and region into its registry entry
+    /// The page is required only for businesses in the registry
+    pub fn sync(
+        loader: Option<&AccountLoader<BusinessRegistry>>,
+        business_key: Pubkey,
+        business: &Business,
+    ) -> Result<()> {
+        if !business.in_registry {
+            return Ok(());
+        }
+
+        let mut registry = loader.ok_or(LocalshareError::InvalidRegistryPage)?.load_mut()?;
+        require!(
+            registry.page == business.registry_page,
+            LocalshareError::InvalidRegistryPage
+        );
+        let entry = &mut registry.entries[business.registry_slot as usize];
+        require_keys_eq!(entry.business, business_key, LocalshareError::InvalidRegistryPage);
+        *entry = business.index_entry(business_key);
+        Ok(())
+    }
+}
+
+/// Bytes of a SearchIndex key: [category, 0, 0, 0] or a geohash prefix (cells of about 20-40 km,
+/// roughly a city)
+pub const SEARCH_INDEX_KEY_LENGTH: usize = 4;
+
+/// Kind of a SearchIndex
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IndexKind {
+    Category,
+    Region,
+}
+
+impl IndexKind {
+    /// PDA seed prefix of the index pages
+    pub fn seed(&self) -> &'static [u8] {
+        match self {
+            IndexKind::Category => b"category_index",
+            IndexKind::Region => b"region_index",
+        }
+    }
+
+    /// Whether `key` names an index of this kind
+    pub fn is_valid_key(&self, key: &[u8; SEARCH_INDEX_KEY_LENGTH]) -> bool {
+        match self {
+            IndexKind::Category => key[0] < BusinessCategory::COUNT && key[1..] == [0; 3],
+            IndexKind::Region => validation::is_geohash(key),
+        }
+    }
+}
+
+/// Page of the index of businesses in one category or region
+/// Lets clients filter, e.g. listed cafés in a city, by reading the region's pages and
+/// checking each entry's `listed` and `category`
+/// Entries of businesses that moved to another category or region are zeroed, and
+/// their slots reused by the next business added to the page
+/// PDA: ["category_index" | "region_index", key, page]
+#[account(zero_copy)]
+pub struct SearchIndex {
+    /// IndexKind of the index
+    pub kind: u8,
+    
+    /// Category or geohash prefix indexed
+    pub key: [u8; SEARCH_INDEX_KEY_LENGTH],
+    
+    /// Alignment padding
+    pub padding: [u8; 3],
+    
+    /// Page number
+    pub page: u32,
+    
+    /// Slots used so far (including zeroed ones)
+    pub count: u32,
+    
+    /// Indexed businesses (only the first `count` slots are set, zeroed slots are free)
+    pub entries: [RegistryEntry; REGISTRY_PAGE_CAPACITY],
+}
+
+impl SearchIndex {
+    /// Region index key of a geohash
+    pub fn region_key(region: &[u8; REGION_CODE_LENGTH]) -> [u8; SEARCH_INDEX_KEY_LENGTH] {
+        let mut key = [0; SEARCH_INDEX_KEY_LENGTH];
+        key.copy_from_slice(&region[..SEARCH_INDEX_KEY_LENGTH]);
+        key
+    }
+
+    /// Adds `entry` to the page, returning its page and slot
+    /// The first zeroed slot is reused; otherwise the entry is appended
+    pub fn append(
+        loader: &AccountLoader<SearchIndex>,
+        kind: IndexKind,
+        key: [u8; SEARCH_INDEX_KEY_LENGTH],
+        entry: RegistryEntry,
+    ) -> Result<(u32, u16)> {
+        let mut index = loader.load_mut()?;
+        require!(
+            index.kind == kind as u8 && index.key == key,
+            LocalshareError::InvalidSearchIndexPage
+        );
+        let used = index.count as usize;
+        let slot = match index.entries[..used]
+            .iter()
+            .position(|existing| existing.business == Pubkey::default())
+        {
+            Some(free) => free,
+            None => {
+                require!(used < REGISTRY_PAGE_CAPACITY, LocalshareError::RegistryPageFull);
+                index.count += 1;
+                used
+            }
+        };
+
+        index.entries[slot] = entry;
+        Ok((index.page, slot as u16))
+    }
+
+    /// Loads the business's entry at `page`/`slot` for writing
+    fn entry_mut<'a>(
+        loader: Option<&'a AccountLoader<'_, SearchIndex>>,
+        kind: IndexKind,
+        key: [u8; SEARCH_INDEX_KEY_LENGTH],
+        page: u32,
+        slot: u16,
+        business_key: Pubkey,
+    ) -> Result<RefMut<'a, RegistryEntry>> {
+        let index = loader.ok_or(LocalshareError::InvalidSearchIndexPage)?.load_mut()?;
+        require!(
+            index.kind == kind as u8 && index.key == key && index.page == page,
+            LocalshareError::InvalidSearchIndexPage
+        );
+        let entry = RefMut::map(index, |index| &mut index.entries[slot as usize]);
+        require_keys_eq!(entry.business, business_key, LocalshareError::InvalidSearchIndexPage);
+        Ok(entry)
+    }
+
+    /// Overwrites the business's entry at `page`/`slot` with `entry`
+    pub fn sync(
+        loader: Option<&AccountLoader<SearchIndex>>,
+        kind: IndexKind,
+        key: [u8; SEARCH_INDEX_KEY_LENGTH],
+        page: u32,
+        slot: u16,
+        entry: RegistryEntry,
+    ) -> Result<()> {
+        *Self::entry_mut(loader, kind, key, page, slot, entry.business)? = entry;
+        Ok(())
+    }
+
+    /// Zeroes the business's entry at `page`/`slot` (the business left the index)
+    pub fn remove(
+        loader: Option<&AccountLoader<SearchIndex>>,
+        kind: IndexKind,
+        key: [u8; SEARCH_INDEX_KEY_LENGTH],
+        page: u32,
+        slot: u16,
+        business_key: Pubkey,
+    ) -> Result<()> {
+        *Self::entry_mut(loader, kind, key, page, slot, business_key)? = RegistryEntry {
+            business: Pubkey::default(),
+            listed: 0,
+            category: 0,
+            region: [0; REGION_CODE_LENGTH],
+            padding: [0; 1],
+        };
+        Ok(())
+    }
+
+    /// Points the business's entry in a `kind` index at `key`
+    /// `from` is its current key, page and slot (None if not indexed yet); the entry is
+    /// updated in place when the key is unchanged, else zeroed in `previous` and appended
+    /// to `target`. Returns the entry's page and slot
+    pub fn reindex(
+        target: Option<&AccountLoader<SearchIndex>>,
+        previous: Option<&AccountLoader<SearchIndex>>,
+        kind: IndexKind,
+        from: Option<([u8; SEARCH_INDEX_KEY_LENGTH], u32, u16)>,
+        key: [u8; SEARCH_INDEX_KEY_LENGTH],
+        entry: RegistryEntry,
+    ) -> Result<(u32, u16)> {
+        match from {
+            Some((from_key, page, slot)) if from_key == key => {
+                Self::sync(target, kind, key, page, slot, entry)?;
+                Ok((page, slot))
+            }
+            _ => {
+                if let Some((from_key, page, slot)) = from {
+                    Self::remove(previous, kind, from_key, page, slot, entry.business)?;
+                }
+                let target = target.ok_or(LocalshareError::InvalidSearchIndexPage)?;
+                Self::append(target, kind, key, entry)
+            }
+        }
+    }
+}
+
+/// Validation limits of Report.report_uri
+pub const REPORT_URI_LIMIT: TextLimit = TextLimit {
+    max_chars: MAX_DESCRIPTION_URI_LENGTH,
+    max_bytes: MAX_DESCRIPTION_URI_LENGTH,
+    required: true,
+};
+
+/// Age of the last report (or of the first listing, before any report) after which
+/// a listed business can be flagged (quarterly reporting plus a grace period)
+pub const REPORT_OVERDUE_AFTER_SECONDS: i64 = 120 * 24 * 60 * 60;
+
+/// Earliest reporting period accepted (January 2000)
+pub const MIN_REPORT_PERIOD: u32 = 200001;
+
+/// Headline figures of a report (each optional, in the currency stated by the report)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct KeyMetrics {
+    /// Revenue over the period
+    pub revenue: Option<u64>,
+    
+    /// Expenses over the period
+    pub expenses: Option<u64>,
+    
+    /// Employees at the end of the period
+    pub headcount: Option<u32>,
+}
+
+/// Financial disclosure of a business for one period
+/// Periods are months as YYYYMM (e.g. 202403 for March 2024), not after the current
+/// month, and must increase
+/// PDA: ["report", business.key(), period]
+#[account]
+#[derive(InitSpace)]
+pub struct Report {
+    /// Business the report belongs to
+    pub business: Pubkey,
+    
+    /// Reporting period
+    pub period: u32,
+    
+    /// Location of the full report (maximum 200 characters)
+    #[max_len(MAX_DESCRIPTION_URI_LENGTH)]
+    pub report_uri: String,
+    
+    /// SHA-256 of the document at report_uri
+    pub content_hash: [u8; 32],
+    
+    /// Headline figures
+    pub key_metrics: KeyMetrics,
+    
+    /// Unix timestamp of publication
+    pub published_at: i64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Report {
+    /// Month (YYYYMM, UTC) containing the unix timestamp `ts`
+    pub fn period_at(ts: i64) -> u32 {
+        // Civil date from days since the epoch (proleptic Gregorian calendar)
+        let days = ts.div_euclid(24 * 60 * 60) + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        (year * 100 + month).clamp(0, u32::MAX as i64) as u32
+    }
+
+    /// Whether `period` is a YYYYMM month from MIN_REPORT_PERIOD up to the month of `now`
+    pub fn is_valid_period(period: u32, now: i64) -> bool {
+        (1..=12).contains(&(period % 100)) && (MIN_REPORT_PERIOD..=Self::period_at(now)).contains(&period)
+    }
+}
+
+/// Reporting history of a business
+/// PDA: ["reporting", business.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct ReportingStatus {
+    /// Business reporting
+    pub business: Pubkey,
+    
+    /// Period of the latest report
+    pub last_period: u32,
+    
+    /// Reports published
+    pub report_count: u32,
+    
+    /// Unix timestamp of the latest report
+    pub last_published_at: i64,
+    
+    /// Whether the admin flagged the reporting as overdue
+    pub overdue: bool,
+    
+    /// Unix timestamp of the latest overdue flag
+    pub flagged_at: i64,
     
     /// PDA bump seed
     pub bump: u8,
 }
 
-/// Authority for minting business shares
-/// PDA: ["mint_authority", business.key()]
-#[account]
-pub struct MintAuthority {
-    /// Business this authority belongs to
-    pub business: Pubkey,
-    /// Bump seed
-    pub bump: u8,
+impl ReportingStatus {
+    /// Whether more than REPORT_OVERDUE_AFTER_SECONDS have passed since the latest
+    /// report or, before any report, since the business was first listed (`listed_at`)
+    pub fn is_overdue(&self, listed_at: i64, now: i64) -> bool {
+        let since = if self.report_count == 0 { listed_at } else { self.last_published_at };
+        now.saturating_sub(since) > REPORT_OVERDUE_AFTER_SECONDS
+    }
+}
+
+// ============================================================================
+// Context Structs (Accounts) for Instructions
+// ============================================================================
+
+/// Context for initializing the global configuration
+/// Can only be called once to configure the protocol
+#[derive(Accounts)]
+pub struct InitConfig<'info> {
+    /// Config account being initialized as PDA
+    #[account(
+        init,
+        seeds = [b"config"],
+        bump,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Administrator who pays for creation and will be set as admin
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// System program to create the account
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for registering a new business or updating an existing one
+/// Allows entrepreneurs to create their business profile or update business name
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct RegisterBusiness<'info> {
+    /// Business account PDA: ["business", owner.key()]
+    #[account(
+        init_if_needed,
+        seeds = [b"business", owner.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + Business::INIT_SPACE
+    )]
+    pub business: Account<'info, Business>,
+
+    /// Name record PDA: ["name", sha256(slug(name))]
+    #[account(
+        init_if_needed,
+        seeds = [b"name", slug::seed(&slug::slugify(&name)).as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + NameRecord::INIT_SPACE
+    )]
+    pub name_record: Account<'info, NameRecord>,
+
+    /// Name record of the current name, released on rename
+    /// Empty for new businesses and businesses registered before name records
+    /// CHECK: Address enforced by seeds; closed by the handler when the slug changes
+    #[account(
+        mut,
+        seeds = [b"name", slug::seed(&slug::slugify(&business.name)).as_ref()],
+        bump
+    )]
+    pub previous_name_record: UncheckedAccount<'info>,
+
+    /// Registry page with room for a new business (also needed when the category or region changes)
+    #[account(mut)]
+    pub registry_page: Option<AccountLoader<'info, BusinessRegistry>>,
+
+    /// Category and region index pages receiving (or holding) the business
+    /// Not needed when the category and region are unchanged
+    #[account(mut)]
+    pub category_index: Option<AccountLoader<'info, SearchIndex>>,
+
+    #[account(mut)]
+    pub region_index: Option<AccountLoader<'info, SearchIndex>>,
+
+    /// Index pages the business leaves when its category or region key changes
+    #[account(mut)]
+    pub previous_category_index: Option<AccountLoader<'info, SearchIndex>>,
+
+    #[account(mut)]
+    pub previous_region_index: Option<AccountLoader<'info, SearchIndex>>,
+
+    /// Business owner (signer)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for configuring a business offering
+/// Allows the business owner to set offering parameters
+#[derive(Accounts)]
+pub struct ConfigureOffering<'info> {
+    /// Business account being configured
+    /// Must be mutable to update offering parameters
+    /// Uses has_one constraint to ensure only the owner can call this
+    #[account(
+        mut,
+        has_one = owner @ LocalshareError::InvalidBusinessOwner
+    )]
+    pub business: Account<'info, Business>,
+
+    /// Registry page holding the business (required once it is in the registry)
+    #[account(mut)]
+    pub registry_page: Option<AccountLoader<'info, BusinessRegistry>>,
+
+    /// Category and region index pages holding the business (required once it is indexed)
+    #[account(mut)]
+    pub category_index: Option<AccountLoader<'info, SearchIndex>>,
+
+    #[account(mut)]
+    pub region_index: Option<AccountLoader<'info, SearchIndex>>,
+
+    /// Business owner (signer)
+    /// Must match the owner field in the business account
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Freeze PDA: ["freeze", business.key()]
+    /// CHECK: Address enforced by seeds; contents loaded by BusinessFreeze::is_frozen
+    #[account(
+        seeds = [b"freeze", business.key().as_ref()],
+        bump
+    )]
+    pub business_freeze: UncheckedAccount<'info>,
+
+    /// System program (for potential future use)
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for initializing the share mint and vault
+/// Creates an SPL mint for business equity tokens and mints all shares into a vault
+#[derive(Accounts)]
+pub struct InitShareMint<'info> {
+    /// Business account being configured
+    /// Must be mutable to update share_mint field
+    /// Uses has_one constraint to ensure only the owner can call this
+    #[account(
+        mut,
+        has_one = owner @ LocalshareError::InvalidBusinessOwner
+    )]
+    pub business: Account<'info, Business>,
+
+    /// Business owner (signer)
+    /// Must match the owner field in the business account
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Share mint account for business equity tokens
+    /// PDA: ["share_mint", business.key()]
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"share_mint", business.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = share_mint_authority,
+        mint::freeze_authority = share_mint_authority,
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    /// Authority for the share mint
+    /// PDA: ["share_mint_authority", business.key()]
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"share_mint_authority", business.key().as_ref()],
+        bump,
+        space = 8 + ShareMintAuthority::INIT_SPACE
+    )]
+    pub share_mint_authority: Account<'info, ShareMintAuthority>,
+
+    /// Token vault for storing all business shares
+    /// PDA: ["shares_vault", business.key()]
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"shares_vault", business.key().as_ref()],
+        bump,
+        token::mint = share_mint,
+        token::authority = share_mint_authority,
+    )]
+    pub shares_vault: Account<'info, TokenAccount>,
+
+    /// Token program for SPL token operations
+    pub token_program: Program<'info, Token>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for creating a share offering
+/// Allows a business with an initialized share mint to open an indexed offering
+#[derive(Accounts)]
+#[instruction(index: u32)]
+pub struct CreateOffering<'info> {
+    /// Offering account PDA: ["offering", business.key(), index]
+    #[account(
+        init,
+        seeds = [b"offering", business.key().as_ref(), &index.to_le_bytes()],
+        bump,
+        payer = owner,
+        space = 8 + Offering::INIT_SPACE
+    )]
+    pub offering: Account<'info, Offering>,
+
+    /// Business funding statistics PDA: ["business_stats", business.key()]
+    #[account(
+        init_if_needed,
+        seeds = [b"business_stats", business.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + BusinessStats::INIT_SPACE
+    )]
+    pub business_stats: Account<'info, BusinessStats>,
+
+    /// Business creating the offering
+    #[account(
+        has_one = owner @ LocalshareError::InvalidBusinessOwner,
+        has_one = share_mint @ LocalshareError::InvalidBusiness
+    )]
+    pub business: Account<'info, Business>,
+
+    /// Token vault holding the offered shares
+    /// PDA: ["offering_vault", offering.key()]
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"offering_vault", offering.key().as_ref()],
+        bump,
+        token::mint = share_mint,
+        token::authority = offering,
+    )]
+    pub offering_vault: Account<'info, TokenAccount>,
+
+    /// Shares vault PDA that holds the unsold business shares
+    /// PDA: ["shares_vault", business.key()]
+    #[account(
+        mut,
+        seeds = [b"shares_vault", business.key().as_ref()],
+        bump,
+        constraint = shares_vault.mint == share_mint.key() @ LocalshareError::InvalidBusiness
+    )]
+    pub shares_vault: Account<'info, TokenAccount>,
+
+    /// Share mint account for the business
+    pub share_mint: Account<'info, Mint>,
+
+    /// Share mint authority PDA that controls the shares_vault
+    /// PDA: ["share_mint_authority", business.key()]
+    #[account(
+        seeds = [b"share_mint_authority", business.key().as_ref()],
+        bump,
+        constraint = share_mint_authority.business == business.key() @ LocalshareError::InvalidBusiness
+    )]
+    pub share_mint_authority: Account<'info, ShareMintAuthority>,
+
+    /// Optional pricing PDA: ["pricing", offering.key()], required unless the round sells at its fixed price
+    #[account(
+        init,
+        seeds = [b"pricing", offering.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + OfferingPricing::INIT_SPACE
+    )]
+    pub pricing: Option<Account<'info, OfferingPricing>>,
+
+    /// Freeze PDA: ["freeze", business.key()]
+    /// No rounds can be opened while the business is frozen
+    /// CHECK: Address enforced by seeds; contents loaded by BusinessFreeze::is_frozen
+    #[account(
+        seeds = [b"freeze", business.key().as_ref()],
+        bump
+    )]
+    pub business_freeze: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for buying shares
+/// Allows investors to buy shares from an active offering
+#[derive(Accounts)]
+pub struct BuyShares<'info> {
+    /// Offering account from which shares will be purchased
+    /// Must be mutable to update remaining_shares
+    #[account(
+        mut,
+        seeds = [b"offering", business.key().as_ref(), &offering.index.to_le_bytes()],
+        bump = offering.bump,
+        has_one = business @ LocalshareError::InvalidBusiness,
+        has_one = share_mint @ LocalshareError::InvalidBusiness
+    )]
+    pub offering: Account<'info, Offering>,
+
+    /// Business account related to the offering
+    pub business: Account<'info, Business>,
+
+    /// Pricing PDA: ["pricing", offering.key()]
+    /// May be uninitialized, in which case the offering's fixed price applies
+    /// CHECK: Address enforced by seeds; contents loaded by OfferingPricing::load
+    #[account(
+        seeds = [b"pricing", offering.key().as_ref()],
+        bump
+    )]
+    pub pricing: UncheckedAccount<'info>,
+
+    /// Verification record PDA: ["verification", business.key()]
+    /// Purchases are blocked while the business is suspended
+    /// CHECK: Address enforced by seeds; contents loaded by BusinessVerification::status_of
+    #[account(
+        seeds = [b"verification", business.key().as_ref()],
+        bump
+    )]
+    pub verification: UncheckedAccount<'info>,
+
+    /// Freeze PDA: ["freeze", business.key()]
+    /// CHECK: Address enforced by seeds; contents loaded by BusinessFreeze::is_frozen
+    #[account(
+        seeds = [b"freeze", business.key().as_ref()],
+        bump
+    )]
+    pub business_freeze: UncheckedAccount<'info>,
+
+    /// Business funding statistics PDA: ["business_stats", business.key()]
+    #[account(
+        mut,
+        seeds = [b"business_stats", business.key().as_ref()],
+        bump = business_stats.bump
+    )]
+    pub business_stats: Account<'info, BusinessStats>,
+
+    /// Token vault holding the offered shares
+    /// PDA: ["offering_vault", offering.key()]
+    #[account(
+        mut,
+        seeds = [b"offering_vault", offering.key().as_ref()],
+        bump
+    )]
+    pub offering_vault: Account<'info, TokenAccount>,
+
+    /// Buyer's associated token account to receive the shares
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = share_mint,
+        associated_token::authority = buyer,
+    )]
+    pub buyer_shares_ata: Account<'info, TokenAccount>,
+
+    /// Share mint account for the business
+    pub share_mint: Account<'info, Mint>,
+
+    /// Treasury account that receives SOL payments
+    /// Must match business.treasury (a wallet or the BusinessTreasury PDA)
+    /// CHECK: Validated by constraint above
+    #[account(
+        mut,
+        constraint = treasury.key() == business.treasury @ LocalshareError::InvalidBusiness
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Buyer's position in the business PDA: ["position", business.key(), buyer.key()]
+    #[account(
+        init_if_needed,
+        seeds = [b"position", business.key().as_ref(), buyer.key().as_ref()],
+        bump,
+        payer = buyer,
+        space = 8 + InvestorPosition::INIT_SPACE
+    )]
+    pub position: Account<'info, InvestorPosition>,
+
+    /// Optional receipt PDA: ["receipt", business.key(), buyer.key(), position.purchase_count]
+    #[account(
+        init,
+        seeds = [
+            b"receipt",
+            business.key().as_ref(),
+            buyer.key().as_ref(),
+            position.purchase_count.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = buyer,
+        space = 8 + PurchaseReceipt::INIT_SPACE
+    )]
+    pub receipt: Option<Account<'info, PurchaseReceipt>>,
+
+    /// Buyer acquiring the shares
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// Token program (for SPL token operations)
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// System program (for SOL transfers and account creation)
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for closing an offering
+/// Returns unsold shares from the offering vault to the shares_vault
+/// Owner-only until the round's end_ts, permissionless afterwards
+#[derive(Accounts)]
+pub struct CloseOffering<'info> {
+    /// Offering being closed
+    #[account(
+        mut,
+        seeds = [b"offering", business.key().as_ref(), &offering.index.to_le_bytes()],
+        bump = offering.bump,
+        has_one = business @ LocalshareError::InvalidBusiness
+    )]
+    pub offering: Account<'info, Offering>,
+
+    /// Business that created the offering
+    pub business: Account<'info, Business>,
+
+    /// Business funding statistics PDA: ["business_stats", business.key()]
+    #[account(
+        mut,
+        seeds = [b"business_stats", business.key().as_ref()],
+        bump = business_stats.bump
+    )]
+    pub business_stats: Account<'info, BusinessStats>,
+
+    /// Token vault holding the offered shares
+    /// PDA: ["offering_vault", offering.key()]
+    #[account(
+        mut,
+        seeds = [b"offering_vault", offering.key().as_ref()],
+        bump
+    )]
+    pub offering_vault: Account<'info, TokenAccount>,
+
+    /// Shares vault PDA receiving the unsold shares
+    /// PDA: ["shares_vault", business.key()]
+    #[account(
+        mut,
+        seeds = [b"shares_vault", business.key().as_ref()],
+        bump
+    )]
+    pub shares_vault: Account<'info, TokenAccount>,
+
+    /// Business owner, or anyone once the round's end_ts has passed (checked in the handler)
+    pub authority: Signer<'info>,
+
+    /// Token program for SPL token operations
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for migrating a legacy offering
+/// Replaces a ["offering", business, legacy_mint] account with an indexed offering
+#[derive(Accounts)]
+#[instruction(index: u32)]
+pub struct MigrateLegacyOffering<'info> {
+    /// Business that owns the legacy offering
+    #[account(
+        has_one = owner @ LocalshareError::InvalidBusinessOwner,
+        has_one = share_mint @ LocalshareError::InvalidBusiness
+    )]
+    pub business: Account<'info, Business>,
+
+    /// Legacy offering PDA: ["offering", business.key(), legacy_mint.key()]
+    /// CHECK: Address enforced by seeds; layout validated by LegacyOffering::load
+    #[account(
+        mut,
+        seeds = [b"offering", business.key().as_ref(), legacy_mint.key().as_ref()],
+        bump
+    )]
+    pub legacy_offering: UncheckedAccount<'info>,
+
+    /// Legacy business mint PDA: ["mint", business.key()]
+    #[account(
+        mut,
+        seeds = [b"mint", business.key().as_ref()],
+        bump
+    )]
+    pub legacy_mint: Account<'info, Mint>,
+
+    /// Legacy offering vault (ATA of the legacy offering)
+    #[account(
+        mut,
+        associated_token::mint = legacy_mint,
+        associated_token::authority = legacy_offering,
+    )]
+    pub legacy_vault: Account<'info, TokenAccount>,
+
+    /// Vault backing the legacy tokens held by investors (see swap_legacy_shares)
+    /// PDA: ["legacy_swap_vault", business.key()]
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"legacy_swap_vault", business.key().as_ref()],
+        bump,
+        token::mint = share_mint,
+        token::authority = share_mint_authority,
+    )]
+    pub legacy_swap_vault: Account<'info, TokenAccount>,
+
+    /// Offering account PDA: ["offering", business.key(), index]
+    #[account(
+        init,
+        seeds = [b"offering", business.key().as_ref(), &index.to_le_bytes()],
+        bump,
+        payer = owner,
+        space = 8 + Offering::INIT_SPACE
+    )]
+    pub offering: Account<'info, Offering>,
+
+    /// Business funding statistics PDA: ["business_stats", business.key()]
+    #[account(
+        init_if_needed,
+        seeds = [b"business_stats", business.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + BusinessStats::INIT_SPACE
+    )]
+    pub business_stats: Account<'info, BusinessStats>,
+
+    /// Token vault holding the offered shares
+    /// PDA: ["offering_vault", offering.key()]
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"offering_vault", offering.key().as_ref()],
+        bump,
+        token::mint = share_mint,
+        token::authority = offering,
+    )]
+    pub offering_vault: Account<'info, TokenAccount>,
+
+    /// Shares vault PDA that funds the new offering
+    /// PDA: ["shares_vault", business.key()]
+    #[account(
+        mut,
+        seeds = [b"shares_vault", business.key().as_ref()],
+        bump,
+        constraint = shares_vault.mint == share_mint.key() @ LocalshareError::InvalidBusiness
+    )]
+    pub shares_vault: Account<'info, TokenAccount>,
+
+    /// Share mint account for the business
+    pub share_mint: Account<'info, Mint>,
+
+    /// Share mint authority PDA that controls the shares_vault
+    /// PDA: ["share_mint_authority", business.key()]
+    #[account(
+        seeds = [b"share_mint_authority", business.key().as_ref()],
+        bump,
+        constraint = share_mint_authority.business == business.key() @ LocalshareError::InvalidBusiness
+    )]
+    pub share_mint_authority: Account<'info, ShareMintAuthority>,
+
+    /// Freeze PDA: ["freeze", business.key()]
+    /// Legacy offerings can't be reopened while the business is frozen
+    /// CHECK: Address enforced by seeds; contents loaded by BusinessFreeze::is_frozen
+    #[account(
+        seeds = [b"freeze", business.key().as_ref()],
+        bump
+    )]
+    pub business_freeze: UncheckedAccount<'info>,
+
+    /// Business owner (signer, receives the legacy rent)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for swapping legacy-mint tokens for shares
+/// Burns the holder's legacy tokens and pays shares from the legacy swap vault
+#[derive(Accounts)]
+pub struct SwapLegacyShares<'info> {
+    /// Business whose legacy offering was migrated
+    #[account(has_one = share_mint @ LocalshareError::InvalidBusiness)]
+    pub business: Account<'info, Business>,
+
+    /// Legacy business mint PDA: ["mint", business.key()]
+    #[account(
+        mut,
+        seeds = [b"mint", business.key().as_ref()],
+        bump
+    )]
+    pub legacy_mint: Account<'info, Mint>,
+
+    /// Holder's legacy-mint token account (burned from)
+    #[account(
+        mut,
+        token::mint = legacy_mint,
+        token::authority = holder,
+    )]
+    pub holder_legacy_ata: Account<'info, TokenAccount>,
+
+    /// Vault backing the legacy tokens held by investors
+    /// PDA: ["legacy_swap_vault", business.key()]
+    #[account(
+        mut,
+        seeds = [b"legacy_swap_vault", business.key().as_ref()],
+        bump
+    )]
+    pub legacy_swap_vault: Account<'info, TokenAccount>,
+
+    /// Holder's associated token account receiving the shares
+    #[account(
+        init_if_needed,
+        payer = holder,
+        associated_token::mint = share_mint,
+        associated_token::authority = holder,
+    )]
+    pub holder_shares_ata: Account<'info, TokenAccount>,
+
+    /// Share mint account for the business
+    pub share_mint: Account<'info, Mint>,
+
+    /// Share mint authority PDA that controls the legacy swap vault
+    /// PDA: ["share_mint_authority", business.key()]
+    #[account(
+        seeds = [b"share_mint_authority", business.key().as_ref()],
+        bump,
+        constraint = share_mint_authority.business == business.key() @ LocalshareError::InvalidBusiness
+    )]
+    pub share_mint_authority: Account<'info, ShareMintAuthority>,
+
+    /// Legacy token holder (signer, pays for the share account if needed)
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for listing a business on the marketplace
+/// Allows the business owner to make their business available for investment
+#[derive(Accounts)]
+pub struct ListBusiness<'info> {
+    /// Business account being listed
+    /// Must be mutable to update is_listed field
+    /// Uses has_one constraint to ensure only the owner can call this
+    #[account(
+        mut,
+        has_one = owner @ LocalshareError::InvalidBusinessOwner
+    )]
+    pub business: Account<'info, Business>,
+
+    /// Registry page holding the business (required once it is in the registry)
+    #[account(mut)]
+    pub registry_page: Option<AccountLoader<'info, BusinessRegistry>>,
+
+    /// Category and region index pages holding the business (required once it is indexed)
+    #[account(mut)]
+    pub category_index: Option<AccountLoader<'info, SearchIndex>>,
+
+    #[account(mut)]
+    pub region_index: Option<AccountLoader<'info, SearchIndex>>,
+
+    /// Verification record PDA: ["verification", business.key()]
+    /// May be uninitialized, in which case the business is Unverified
+    /// CHECK: Address enforced by seeds; contents loaded by BusinessVerification::status_of
+    #[account(
+        seeds = [b"verification", business.key().as_ref()],
+        bump
+    )]
+    pub verification: UncheckedAccount<'info>,
+
+    /// Freeze PDA: ["freeze", business.key()]
+    /// CHECK: Address enforced by seeds; contents loaded by BusinessFreeze::is_frozen
+    #[account(
+        seeds = [b"freeze", business.key().as_ref()],
+        bump
+    )]
+    pub business_freeze: UncheckedAccount<'info>,
+
+    /// Verification policy PDA: ["verification_policy"]
+    /// May be uninitialized, in which case verification is not required
+    /// CHECK: Address enforced by seeds; contents loaded by VerificationPolicy::requires_verified_listing
+    #[account(
+        seeds = [b"verification_policy"],
+        bump
+    )]
+    pub verification_policy: UncheckedAccount<'info>,
+
+    /// Business owner (signer)
+    /// Must match the owner field in the business account
+    pub owner: Signer<'info>,
+
+    /// System program (for potential future use)
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for publishing a cap table snapshot commitment
+/// Allows the business owner to anchor an off-chain cap table on-chain
+#[derive(Accounts)]
+#[instruction(slot: u64)]
+pub struct PublishCapTable<'info> {
+    /// Business whose cap table is being published
+    #[account(has_one = owner @ LocalshareError::InvalidBusinessOwner)]
+    pub business: Account<'info, Business>,
+
+    /// Snapshot account PDA: ["cap_table", business.key(), slot]
+    #[account(
+        init,
+        seeds = [b"cap_table", business.key().as_ref(), &slot.to_le_bytes()],
+        bump,
+        payer = owner,
+        space = 8 + CapTableSnapshot::INIT_SPACE
+    )]
+    pub cap_table_snapshot: Account<'info, CapTableSnapshot>,
+
+    /// Business owner (signer, pays for the snapshot account)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// System program to create the account
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for creating a share airdrop
+/// Moves shares from the business vault into an airdrop vault controlled by the airdrop PDA
+#[derive(Accounts)]
+#[instruction(merkle_root: [u8; 32])]
+pub struct CreateShareAirdrop<'info> {
+    /// Business distributing the shares
+    #[account(
+        has_one = owner @ LocalshareError::InvalidBusinessOwner,
+        has_one = share_mint @ LocalshareError::InvalidBusiness
+    )]
+    pub business: Account<'info, Business>,
+
+    /// Airdrop account PDA: ["airdrop", business.key(), merkle_root]
+    #[account(
+        init,
+        seeds = [b"airdrop", business.key().as_ref(), merkle_root.as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + ShareAirdrop::INIT_SPACE
+    )]
+    pub airdrop: Account<'info, ShareAirdrop>,
+
+    /// Token vault holding the airdropped shares
+    /// PDA: ["airdrop_vault", airdrop.key()]
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"airdrop_vault", airdrop.key().as_ref()],
+        bump,
+        token::mint = share_mint,
+        token::authority = airdrop,
+    )]
+    pub airdrop_vault: Account<'info, TokenAccount>,
+
+    /// Shares vault PDA that holds the unsold business shares
+    /// PDA: ["shares_vault", business.key()]
+    #[account(
+        mut,
+        seeds = [b"shares_vault", business.key().as_ref()],
+        bump,
+        constraint = shares_vault.mint == share_mint.key() @ LocalshareError::InvalidBusiness
+    )]
+    pub shares_vault: Account<'info, TokenAccount>,
+
+    /// Share mint account for the business
+    pub share_mint: Account<'info, Mint>,
+
+    /// Share mint authority PDA that controls the shares_vault
+    /// PDA: ["share_mint_authority", business.key()]
+    #[account(
+        seeds = [b"share_mint_authority", business.key().as_ref()],
+        bump,
+        constraint = share_mint_authority.business == business.key() @ LocalshareError::InvalidBusiness
+    )]
+    pub share_mint_authority: Account<'info, ShareMintAuthority>,
+
+    /// Business owner (signer, pays for the airdrop accounts)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Token program for SPL token operations
+    pub token_program: Program<'info, Token>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for claiming shares from an airdrop
+/// Allows a claimant included in the Merkle tree to receive their shares once
+#[derive(Accounts)]
+pub struct ClaimAirdrop<'info> {
+    /// Airdrop being claimed from
+    #[account(
+        mut,
+        seeds = [b"airdrop", airdrop.business.as_ref(), airdrop.merkle_root.as_ref()],
+        bump = airdrop.bump,
+        has_one = share_mint @ LocalshareError::InvalidBusiness
+    )]
+    pub airdrop: Account<'info, ShareAirdrop>,
+
+    /// Token vault holding the airdropped shares
+    /// PDA: ["airdrop_vault", airdrop.key()]
+    #[account(
+        mut,
+        seeds = [b"airdrop_vault", airdrop.key().as_ref()],
+        bump
+    )]
+    pub airdrop_vault: Account<'info, TokenAccount>,
+
+    /// Claim receipt PDA: ["airdrop_claim", airdrop.key(), claimant.key()]
+    /// Creation fails if the claimant already claimed
+    #[account(
+        init,
+        seeds = [b"airdrop_claim", airdrop.key().as_ref(), claimant.key().as_ref()],
+        bump,
+        payer = claimant,
+        space = 8 + ClaimReceipt::INIT_SPACE
+    )]
+    pub claim_receipt: Account<'info, ClaimReceipt>,
+
+    /// Claimant's associated token account to receive the shares
+    #[account(
+        init_if_needed,
+        payer = claimant,
+        associated_token::mint = share_mint,
+        associated_token::authority = claimant,
+    )]
+    pub claimant_shares_ata: Account<'info, TokenAccount>,
+
+    /// Share mint account for the business
+    pub share_mint: Account<'info, Mint>,
+
+    /// Claimant (signer, pays for the receipt and ATA)
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    /// Token program for SPL token operations
+    pub token_program: Program<'info, Token>,
+
+    /// Associated token program for ATA creation
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for closing an airdrop
+/// Returns unclaimed shares from the airdrop vault to the shares_vault
+#[derive(Accounts)]
+pub struct CloseAirdrop<'info> {
+    /// Business that created the airdrop
+    #[account(has_one = owner @ LocalshareError::InvalidBusinessOwner)]
+    pub business: Account<'info, Business>,
+
+    /// Airdrop being closed (rent returned to the owner)
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"airdrop", business.key().as_ref(), airdrop.merkle_root.as_ref()],
+        bump = airdrop.bump,
+        has_one = business @ LocalshareError::InvalidBusiness
+    )]
+    pub airdrop: Account<'info, ShareAirdrop>,
+
+    /// Token vault holding the unclaimed shares
+    /// PDA: ["airdrop_vault", airdrop.key()]
+    #[account(
+        mut,
+        seeds = [b"airdrop_vault", airdrop.key().as_ref()],
+        bump
+    )]
+    pub airdrop_vault: Account<'info, TokenAccount>,
+
+    /// Shares vault PDA receiving the unclaimed shares
+    /// PDA: ["shares_vault", business.key()]
+    #[account(
+        mut,
+        seeds = [b"shares_vault", business.key().as_ref()],
+        bump
+    )]
+    pub shares_vault: Account<'info, TokenAccount>,
+
+    /// Business owner (signer, receives the account rent)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Token program for SPL token operations
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for creating a vesting schedule
+/// Moves shares from the business vault into a vault controlled by the vesting PDA
+#[derive(Accounts)]
+#[instruction(beneficiary: Pubkey)]
+pub struct CreateVesting<'info> {
+    /// Business granting the shares
+    #[account(
+        has_one = owner @ LocalshareError::InvalidBusinessOwner,
+        has_one = share_mint @ LocalshareError::InvalidBusiness
+    )]
+    pub business: Account<'info, Business>,
+
+    /// Vesting schedule PDA: ["vesting", business.key(), beneficiary]
+    #[account(
+        init,
+        seeds = [b"vesting", business.key().as_ref(), beneficiary.as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + VestingSchedule::INIT_SPACE
+    )]
+    pub vesting: Account<'info, VestingSchedule>,
+
+    /// Token vault holding the unreleased vesting shares
+    /// PDA: ["vesting_vault", vesting.key()]
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"vesting_vault", vesting.key().as_ref()],
+        bump,
+        token::mint = share_mint,
+        token::authority = vesting,
+    )]
+    pub vesting_vault: Account<'info, TokenAccount>,
+
+    /// Shares vault PDA that holds the unsold business shares
+    /// PDA: ["shares_vault", business.key()]
+    #[account(
+        mut,
+        seeds = [b"shares_vault", business.key().as_ref()],
+        bump,
+        constraint = shares_vault.mint == share_mint.key() @ LocalshareError::InvalidBusiness
+    )]
+    pub shares_vault: Account<'info, TokenAccount>,
+
+    /// Share mint account for the business
+    pub share_mint: Account<'info, Mint>,
+
+    /// Share mint authority PDA that controls the shares_vault
+    /// PDA: ["share_mint_authority", business.key()]
+    #[account(
+        seeds = [b"share_mint_authority", business.key().as_ref()],
+        bump,
+        constraint = share_mint_authority.business == business.key() @ LocalshareError::InvalidBusiness
+    )]
+    pub share_mint_authority: Account<'info, ShareMintAuthority>,
+
+    /// Business owner (signer, pays for the vesting accounts)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Token program for SPL token operations
+    pub token_program: Program<'info, Token>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for releasing vested shares
+/// Allows the beneficiary to withdraw the unlocked portion of their grant
+#[derive(Accounts)]
+pub struct ReleaseVested<'info> {
+    /// Vesting schedule being released from
+    #[account(
+        mut,
+        seeds = [b"vesting", vesting.business.as_ref(), beneficiary.key().as_ref()],
+        bump = vesting.bump,
+        has_one = beneficiary @ LocalshareError::InvalidBeneficiary,
+        has_one = share_mint @ LocalshareError::InvalidBusiness
+    )]
+    pub vesting: Account<'info, VestingSchedule>,
+
+    /// Token vault holding the unreleased vesting shares
+    /// PDA: ["vesting_vault", vesting.key()]
+    #[account(
+        mut,
+        seeds = [b"vesting_vault", vesting.key().as_ref()],
+        bump
+    )]
+    pub vesting_vault: Account<'info, TokenAccount>,
+
+    /// Beneficiary's associated token account to receive the shares
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = share_mint,
+        associated_token::authority = beneficiary,
+    )]
+    pub beneficiary_shares_ata: Account<'info, TokenAccount>,
+
+    /// Share mint account for the business
+    pub share_mint: Account<'info, Mint>,
+
+    /// Beneficiary (signer)
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    /// Token program for SPL token operations
+    pub token_program: Program<'info, Token>,
+
+    /// Associated token program for ATA creation
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for revoking a vesting schedule
+/// Allows the business owner to reclaim unvested shares into the shares_vault
+#[derive(Accounts)]
+pub struct RevokeVesting<'info> {
+    /// Business that granted the shares
+    #[account(has_one = owner @ LocalshareError::InvalidBusinessOwner)]
+    pub business: Account<'info, Business>,
+
+    /// Vesting schedule being revoked
+    #[account(
+        mut,
+        seeds = [b"vesting", business.key().as_ref(), vesting.beneficiary.as_ref()],
+        bump = vesting.bump,
+        constraint = vesting.business == business.key() @ LocalshareError::InvalidBusiness
+    )]
+    pub vesting: Account<'info, VestingSchedule>,
+
+    /// Token vault holding the unreleased vesting shares
+    /// PDA: ["vesting_vault", vesting.key()]
+    #[account(
+        mut,
+        seeds = [b"vesting_vault", vesting.key().as_ref()],
+        bump
+    )]
+    pub vesting_vault: Account<'info, TokenAccount>,
+
+    /// Shares vault PDA receiving the unvested shares
+    /// PDA: ["shares_vault", business.key()]
+    #[account(
+        mut,
+        seeds = [b"shares_vault", business.key().as_ref()],
+        bump
+    )]
+    pub shares_vault: Account<'info, TokenAccount>,
+
+    /// Business owner (signer)
+    pub owner: Signer<'info>,
+
+    /// Token program for SPL token operations
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for configuring shareholder governance
+/// Creates the governance account on first call and updates it afterwards
+#[derive(Accounts)]
+pub struct ConfigureGovernance<'info> {
+    /// Business being governed
+    #[account(has_one = owner @ LocalshareError::InvalidBusinessOwner)]
+    pub business: Account<'info, Business>,
+
+    /// Governance account PDA: ["governance", business.key()]
+    #[account(
+        init_if_needed,
+        seeds = [b"governance", business.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + Governance::INIT_SPACE
+    )]
+    pub governance: Account<'info, Governance>,
+
+    /// Business owner (signer)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// System program to create the account
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for creating a governance proposal
+/// Creates the proposal and the vault that locks voting shares
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    /// Business the proposal belongs to
+    #[account(has_one = share_mint @ LocalshareError::InvalidBusiness)]
+    pub business: Account<'info, Business>,
+
+    /// Governance rules of the business
+    #[account(
+        mut,
+        seeds = [b"governance", business.key().as_ref()],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    /// Proposal account PDA: ["proposal", business.key(), governance.proposal_count]
+    #[account(
+        init,
+        seeds = [b"proposal", business.key().as_ref(), &governance.proposal_count.to_le_bytes()],
+        bump,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Token vault locking the shares used to vote
+    /// PDA: ["vote_vault", proposal.key()]
+    #[account(
+        init,
+        payer = proposer,
+        seeds = [b"vote_vault", proposal.key().as_ref()],
+        bump,
+        token::mint = share_mint,
+        token::authority = proposal,
+    )]
+    pub vote_vault: Account<'info, TokenAccount>,
+
+    /// Proposer's share token account (required unless the proposer is the owner)
+    #[account(
+        constraint = proposer_shares_ata.owner == proposer.key() @ LocalshareError::InsufficientVotingPower,
+        constraint = proposer_shares_ata.mint == share_mint.key() @ LocalshareError::InvalidBusiness
+    )]
+    pub proposer_shares_ata: Option<Account<'info, TokenAccount>>,
+
+    /// Share mint account for the business
+    pub share_mint: Account<'info, Mint>,
+
+    /// Business vault holding the unissued shares (excluded from quorum)
+    /// PDA: ["shares_vault", business.key()]
+    #[account(
+        seeds = [b"shares_vault", business.key().as_ref()],
+        bump
+    )]
+    pub shares_vault: Account<'info, TokenAccount>,
+
+    /// Proposer (signer, pays for the proposal accounts)
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    /// Token program for SPL token operations
+    pub token_program: Program<'info, Token>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateProposal<'info> {
+    /// Shares held by investors: mint supply minus the business vault and the unsold shares
+    /// of every offering vault in `offerings`, given as (offering, offering_vault) pairs
+    fn circulating_supply(&self, offerings: &'info [AccountInfo<'info>]) -> Result<u64> {
+        require!(offerings.len() % 2 == 0, LocalshareError::InvalidOfferingVault);
+
+        let mut unsold = self.shares_vault.amount;
+        let mut last_index: Option<u32> = None;
+        for pair in offerings.chunks(2) {
+            let offering = Account::<Offering>::try_from(&pair[0])?;
+            require_keys_eq!(offering.business, self.business.key(), LocalshareError::InvalidOfferingVault);
+            // Strictly increasing indexes so no vault is subtracted twice
+            require!(
+                last_index.is_none_or(|last| offering.index > last),
+                LocalshareError::InvalidOfferingVault
+            );
+            last_index = Some(offering.index);
+
+            let (vault_key, _) =
+                Pubkey::find_program_address(&[b"offering_vault", pair[0].key.as_ref()], &crate::ID);
+            require_keys_eq!(pair[1].key(), vault_key, LocalshareError::InvalidOfferingVault);
+            let vault = Account::<TokenAccount>::try_from(&pair[1])?;
+            unsold = unsold
+                .checked_add(vault.amount)
+                .ok_or(LocalshareError::MathOverflow)?;
+        }
+
+        Ok(self.share_mint.supply.saturating_sub(unsold))
+    }
+}
+
+/// Context for casting a vote
+/// Locks the voter's shares in the proposal vote vault and records the vote
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    /// Proposal being voted on
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.business.as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Vote record PDA: ["vote", proposal.key(), voter.key()]
+    /// Creation fails if the voter already voted
+    #[account(
+        init,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump,
+        payer = voter,
+        space = 8 + VoteRecord::INIT_SPACE
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    /// Token vault locking the shares used to vote
+    /// PDA: ["vote_vault", proposal.key()]
+    #[account(
+        mut,
+        seeds = [b"vote_vault", proposal.key().as_ref()],
+        bump
+    )]
+    pub vote_vault: Account<'info, TokenAccount>,
+
+    /// Voter's share token account
+    #[account(
+        mut,
+        constraint = voter_shares_ata.owner == voter.key() @ LocalshareError::InsufficientVotingPower,
+        constraint = voter_shares_ata.mint == vote_vault.mint @ LocalshareError::InvalidBusiness
+    )]
+    pub voter_shares_ata: Account<'info, TokenAccount>,
+
+    /// Voter (signer, pays for the vote record)
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    /// Token program for SPL token operations
+    pub token_program: Program<'info, Token>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for finalizing a proposal
+/// Permissionless tally once the voting window has closed
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    /// Proposal being finalized
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.business.as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+/// Context for withdrawing shares locked by a vote
+/// Returns the shares and closes the vote record
+#[derive(Accounts)]
+pub struct WithdrawVoteTokens<'info> {
+    /// Proposal that was voted on
+    #[account(
+        seeds = [b"proposal", proposal.business.as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Vote record being closed (rent returned to the voter)
+    #[account(
+        mut,
+        close = voter,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    /// Token vault locking the shares used to vote
+    /// PDA: ["vote_vault", proposal.key()]
+    #[account(
+        mut,
+        seeds = [b"vote_vault", proposal.key().as_ref()],
+        bump
+    )]
+    pub vote_vault: Account<'info, TokenAccount>,
+
+    /// Voter's share token account receiving the unlocked shares
+    #[account(
+        mut,
+        constraint = voter_shares_ata.owner == voter.key() @ LocalshareError::InsufficientVotingPower,
+        constraint = voter_shares_ata.mint == vote_vault.mint @ LocalshareError::InvalidBusiness
+    )]
+    pub voter_shares_ata: Account<'info, TokenAccount>,
+
+    /// Voter (signer)
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    /// Token program for SPL token operations
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for opening a subscription
+/// Moves shares from the business vault into a vault controlled by the subscription PDA
+#[derive(Accounts)]
+pub struct CreateSubscription<'info> {
+    /// Business running the subscription
+    #[account(
+        has_one = owner @ LocalshareError::InvalidBusinessOwner,
+        has_one = share_mint @ LocalshareError::InvalidBusiness
+    )]
+    pub business: Account<'info, Business>,
+
+    /// Subscription account PDA: ["subscription", business.key()]
+    #[account(
+        init,
+        seeds = [b"subscription", business.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + Subscription::INIT_SPACE
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    /// Token vault holding the subscribed shares
+    /// PDA: ["subscription_vault", subscription.key()]
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"subscription_vault", subscription.key().as_ref()],
+        bump,
+        token::mint = share_mint,
+        token::authority = subscription,
+    )]
+    pub subscription_vault: Account<'info, TokenAccount>,
+
+    /// Shares vault PDA that holds the unsold business shares
+    /// PDA: ["shares_vault", business.key()]
+    #[account(
+        mut,
+        seeds = [b"shares_vault", business.key().as_ref()],
+        bump,
+        constraint = shares_vault.mint == share_mint.key() @ LocalshareError::InvalidBusiness
+    )]
+    pub shares_vault: Account<'info, TokenAccount>,
+
+    /// Share mint account for the business
+    pub share_mint: Account<'info, Mint>,
+
+    /// Share mint authority PDA that controls the shares_vault
+    /// PDA: ["share_mint_authority", business.key()]
+    #[account(
+        seeds = [b"share_mint_authority", business.key().as_ref()],
+        bump,
+        constraint = share_mint_authority.business == business.key() @ LocalshareError::InvalidBusiness
+    )]
+    pub share_mint_authority: Account<'info, ShareMintAuthority>,
+
+    /// Freeze PDA: ["freeze", business.key()]
+    /// Subscriptions can't be opened while the business is frozen
+    /// CHECK: Address enforced by seeds; contents loaded by BusinessFreeze::is_frozen
+    #[account(
+        seeds = [b"freeze", business.key().as_ref()],
+        bump
+    )]
+    pub business_freeze: UncheckedAccount<'info>,
+
+    /// Business owner (signer, pays for the subscription accounts)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Token program for SPL token operations
+    pub token_program: Program<'info, Token>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for committing SOL to a subscription
+/// Creates or tops up the investor's commitment
+#[derive(Accounts)]
+pub struct CommitSubscription<'info> {
+    /// Subscription being committed to (receives the escrowed lamports)
+    #[account(
+        mut,
+        seeds = [b"subscription", subscription.business.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    /// Commitment account PDA: ["commitment", subscription.key(), investor.key()]
+    #[account(
+        init_if_needed,
+        seeds = [b"commitment", subscription.key().as_ref(), investor.key().as_ref()],
+        bump,
+        payer = investor,
+        space = 8 + SubscriptionCommitment::INIT_SPACE
+    )]
+    pub commitment: Account<'info, SubscriptionCommitment>,
+
+    /// Verification record PDA: ["verification", subscription.business]
+    /// Commitments are blocked while the business is suspended
+    /// CHECK: Address enforced by seeds; contents loaded by BusinessVerification::status_of
+    #[account(
+        seeds = [b"verification", subscription.business.as_ref()],
+        bump
+    )]
+    pub verification: UncheckedAccount<'info>,
+
+    /// Freeze PDA: ["freeze", subscription.business]
+    /// Commitments are blocked while the business is frozen
+    /// CHECK: Address enforced by seeds; contents loaded by BusinessFreeze::is_frozen
+    #[account(
+        seeds = [b"freeze", subscription.business.as_ref()],
+        bump
+    )]
+    pub business_freeze: UncheckedAccount<'info>,
+
+    /// Investor committing SOL
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    /// System program for SOL transfers and account creation
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for settling a subscription
+/// Permissionless once the commitment window has closed
+#[derive(Accounts)]
+pub struct SettleSubscription<'info> {
+    /// Subscription being settled
+    #[account(
+        mut,
+        seeds = [b"subscription", subscription.business.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    /// Token vault holding the subscribed shares
+    /// PDA: ["subscription_vault", subscription.key()]
+    #[account(
+        mut,
+        seeds = [b"subscription_vault", subscription.key().as_ref()],
+        bump
+    )]
+    pub subscription_vault: Account<'info, TokenAccount>,
+
+    /// Shares vault PDA receiving unsubscribed shares
+    /// PDA: ["shares_vault", subscription.business]
+    #[account(
+        mut,
+        seeds = [b"shares_vault", subscription.business.as_ref()],
+        bump
+    )]
+    pub shares_vault: Account<'info, TokenAccount>,
+
+    /// Token program for SPL token operations
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for claiming a subscription allocation
+/// Delivers shares to the investor, proceeds to the treasury and refunds the excess
+#[derive(Accounts)]
+pub struct ClaimAllocation<'info> {
+    /// Business that ran the subscription
+    #[account(
+        constraint = business.key() == subscription.business @ LocalshareError::InvalidBusiness
+    )]
+    pub business: Account<'info, Business>,
+
+    /// Settled subscription (pays out the escrowed lamports)
+    #[account(
+        mut,
+        seeds = [b"subscription", business.key().as_ref()],
+        bump = subscription.bump,
+        has_one = share_mint @ LocalshareError::InvalidBusiness
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    /// Investor's commitment (closed, rent returned to the investor)
+    #[account(
+        mut,
+        close = investor,
+        seeds = [b"commitment", subscription.key().as_ref(), investor.key().as_ref()],
+        bump = commitment.bump
+    )]
+    pub commitment: Account<'info, SubscriptionCommitment>,
+
+    /// Business funding statistics PDA: ["business_stats", business.key()]
+    #[account(
+        init_if_needed,
+        seeds = [b"business_stats", business.key().as_ref()],
+        bump,
+        payer = investor,
+        space = 8 + BusinessStats::INIT_SPACE
+    )]
+    pub business_stats: Account<'info, BusinessStats>,
+
+    /// Investor's position in the business PDA: ["position", business.key(), investor.key()]
+    #[account(
+        init_if_needed,
+        seeds = [b"position", business.key().as_ref(), investor.key().as_ref()],
+        bump,
+        payer = investor,
+        space = 8 + InvestorPosition::INIT_SPACE
+    )]
+    pub position: Account<'info, InvestorPosition>,
+
+    /// Verification record PDA: ["verification", business.key()]
+    /// Allocations can't be claimed while the business is suspended
+    /// CHECK: Address enforced by seeds; contents loaded by BusinessVerification::status_of
+    #[account(
+        seeds = [b"verification", business.key().as_ref()],
+        bump
+    )]
+    pub verification: UncheckedAccount<'info>,
+
+    /// Freeze PDA: ["freeze", business.key()]
+    /// Allocations can't be claimed while the business is frozen
+    /// CHECK: Address enforced by seeds; contents loaded by BusinessFreeze::is_frozen
+    #[account(
+        seeds = [b"freeze", business.key().as_ref()],
+        bump
+    )]
+    pub business_freeze: UncheckedAccount<'info>,
+
+    /// Token vault holding the subscribed shares
+    /// PDA: ["subscription_vault", subscription.key()]
+    #[account(
+        mut,
+        seeds = [b"subscription_vault", subscription.key().as_ref()],
+        bump
+    )]
+    pub subscription_vault: Account<'info, TokenAccount>,
+
+    /// Investor's associated token account to receive the shares
+    #[account(
+        init_if_needed,
+        payer = investor,
+        associated_token::mint = share_mint,
+        associated_token::authority = investor,
+    )]
+    pub investor_shares_ata: Account<'info, TokenAccount>,
+
+    /// Treasury account that receives the proceeds
+    /// Must match business.treasury (a wallet or the BusinessTreasury PDA)
+    /// CHECK: Validated by constraint above
+    #[account(
+        mut,
+        constraint = treasury.key() == business.treasury @ LocalshareError::InvalidBusiness
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Share mint account for the business
+    pub share_mint: Account<'info, Mint>,
+
+    /// Investor claiming the allocation (receives the refund)
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    /// Token program for SPL token operations
+    pub token_program: Program<'info, Token>,
+
+    /// Associated token program for ATA creation
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for refunding an unclaimed commitment after the claim deadline
+/// Permissionless; the refund and the commitment rent go to the investor
+#[derive(Accounts)]
+pub struct RefundUnclaimedCommitment<'info> {
+    /// Settled subscription (pays out the escrowed lamports)
+    #[account(
+        mut,
+        seeds = [b"subscription", subscription.business.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    /// Unclaimed commitment (closed, rent returned to the investor)
+    #[account(
+        mut,
+        close = investor,
+        seeds = [b"commitment", subscription.key().as_ref(), investor.key().as_ref()],
+        bump = commitment.bump
+    )]
+    pub commitment: Account<'info, SubscriptionCommitment>,
+
+    /// Investor who committed (receives the refund)
+    /// CHECK: Address bound to the commitment by its seeds
+    #[account(mut)]
+    pub investor: UncheckedAccount<'info>,
+}
+
+/// Context for closing a subscription
+/// Allows the business owner to reclaim leftovers once every commitment is claimed or refunded
+#[derive(Accounts)]
+pub struct CloseSubscription<'info> {
+    /// Business that ran the subscription
+    #[account(has_one = owner @ LocalshareError::InvalidBusinessOwner)]
+    pub business: Account<'info, Business>,
+
+    /// Subscription being closed (rent returned to the owner)
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"subscription", business.key().as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    /// Token vault holding the rounding leftovers
+    /// PDA: ["subscription_vault", subscription.key()]
+    #[account(
+        mut,
+        seeds = [b"subscription_vault", subscription.key().as_ref()],
+        bump
+    )]
+    pub subscription_vault: Account<'info, TokenAccount>,
+
+    /// Shares vault PDA receiving the leftovers
+    /// PDA: ["shares_vault", business.key()]
+    #[account(
+        mut,
+        seeds = [b"shares_vault", business.key().as_ref()],
+        bump
+    )]
+    pub shares_vault: Account<'info, TokenAccount>,
+
+    /// Business owner (signer)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Token program for SPL token operations
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for opening a share buyback
+/// The treasury funds the escrow and the owner pays the account rent
+/// A BusinessTreasury also needs its state and an executable withdrawal request
+#[derive(Accounts)]
+pub struct CreateBuyback<'info> {
+    /// Business buying back its shares
+    #[account(has_one = owner @ LocalshareError::InvalidBusinessOwner)]
+    pub business: Account<'info, Business>,
+
+    /// Buyback account PDA: ["buyback", business.key()]
+    #[account(
+        init,
+        seeds = [b"buyback", business.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + Buyback::INIT_SPACE
+    )]
+    pub buyback: Account<'info, Buyback>,
+
+    /// Treasury funding the escrow
+    /// Must match business.treasury; signs unless it is the BusinessTreasury PDA
+    /// CHECK: Validated by constraint above and in the handler
+    #[account(
+        mut,
+        constraint = treasury.key() == business.treasury @ LocalshareError::InvalidBusiness
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Treasury state, when business.treasury is the BusinessTreasury PDA
+    /// PDA: ["treasury", business.key()]
+    #[account(
+        mut,
+        seeds = [b"treasury", business.key().as_ref()],
+        bump = business_treasury.bump
+    )]
+    pub business_treasury: Option<Account<'info, BusinessTreasury>>,
+
+    /// Withdrawal request funding the escrow from a BusinessTreasury (rent returned to the owner)
+    /// Must name the buyback as recipient and the escrow as amount (checked in the handler)
+    #[account(mut, close = owner)]
+    pub withdrawal: Option<Account<'info, WithdrawalRequest>>,
+
+    /// Business owner (signer, pays the account rent)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for selling shares to a buyback
+#[derive(Accounts)]
+pub struct SellToBuyback<'info> {
+    /// Business buying back its shares
+    /// Mutable to reduce total_shares when shares are burned
+    #[account(
+        mut,
+        has_one = share_mint @ LocalshareError::InvalidBusiness
+    )]
+    pub business: Account<'info, Business>,
+
+    /// Buyback being filled
+    #[account(
+        mut,
+        seeds = [b"buyback", business.key().as_ref()],
+        bump = buyback.bump,
+        has_one = business @ LocalshareError::InvalidBusiness
+    )]
+    pub buyback: Account<'info, Buyback>,
+
+    /// Seller's share token account
+    #[account(
+        mut,
+        constraint = seller_shares_ata.owner == seller.key() @ LocalshareError::InvalidBeneficiary,
+        constraint = seller_shares_ata.mint == share_mint.key() @ LocalshareError::InvalidBusiness
+    )]
+    pub seller_shares_ata: Account<'info, TokenAccount>,
+
+    /// Shares vault PDA receiving the shares (unless burned)
+    /// PDA: ["shares_vault", business.key()]
+    #[account(
+        mut,
+        seeds = [b"shares_vault", business.key().as_ref()],
+        bump
+    )]
+    pub shares_vault: Account<'info, TokenAccount>,
+
+    /// Share mint account for the business (mutable for burns)
+    #[account(mut)]
+    pub share_mint: Account<'info, Mint>,
+
+    /// Shareholder selling the shares
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// Token program for SPL token operations
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for closing a buyback
+#[derive(Accounts)]
+pub struct CloseBuyback<'info> {
+    /// Business that opened the buyback
+    #[account(has_one = owner @ LocalshareError::InvalidBusinessOwner)]
+    pub business: Account<'info, Business>,
+
+    /// Buyback being closed (rent returned to the owner)
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"buyback", business.key().as_ref()],
+        bump = buyback.bump,
+        has_one = treasury @ LocalshareError::InvalidBusiness
+    )]
+    pub buyback: Account<'info, Buyback>,
+
+    /// Treasury that funded the escrow, receiving the refund
+    /// CHECK: Must match buyback.treasury (has_one above)
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Business owner (signer)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Context for creating the program-controlled treasury of a business
+#[derive(Accounts)]
+pub struct InitTreasury<'info> {
+    /// Business whose proceeds are routed to the treasury
+    #[account(
+        mut,
+        has_one = owner @ LocalshareError::InvalidBusinessOwner
+    )]
+    pub business: Account<'info, Business>,
+
+    /// Treasury account PDA: ["treasury", business.key()]
+    #[account(
+        init,
+        seeds = [b"treasury", business.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + BusinessTreasury::INIT_SPACE
+    )]
+    pub business_treasury: Account<'info, BusinessTreasury>,
+
+    /// Business owner (signer)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for requesting a treasury withdrawal
+#[derive(Accounts)]
+pub struct RequestWithdrawal<'info> {
+    /// Business owning the treasury
+    #[account(has_one = owner @ LocalshareError::InvalidBusinessOwner)]
+    pub business: Account<'info, Business>,
+
+    /// Treasury the funds will be withdrawn from
+    #[account(
+        mut,
+        seeds = [b"treasury", business.key().as_ref()],
+        bump = business_treasury.bump
+    )]
+    pub business_treasury: Account<'info, BusinessTreasury>,
+
+    /// Withdrawal request PDA: ["withdrawal", business_treasury.key(), id]
+    #[account(
+        init,
+        seeds = [
+            b"withdrawal",
+            business_treasury.key().as_ref(),
+            &business_treasury.withdrawal_count.to_le_bytes()
+        ],
+        bump,
+        payer = owner,
+        space = 8 + WithdrawalRequest::INIT_SPACE
+    )]
+    pub withdrawal: Account<'info, WithdrawalRequest>,
+
+    /// Business owner (signer)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for executing a treasury withdrawal
+#[derive(Accounts)]
+pub struct ExecuteWithdrawal<'info> {
+    /// Business owning the treasury
+    #[account(has_one = owner @ LocalshareError::InvalidBusinessOwner)]
+    pub business: Account<'info, Business>,
+
+    /// Treasury the funds are withdrawn from
+    #[account(
+        mut,
+        seeds = [b"treasury", business.key().as_ref()],
+        bump = business_treasury.bump
+    )]
+    pub business_treasury: Account<'info, BusinessTreasury>,
+
+    /// Withdrawal being executed (rent returned to the owner)
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"withdrawal", business_treasury.key().as_ref(), &withdrawal.id.to_le_bytes()],
+        bump = withdrawal.bump,
+        has_one = recipient @ LocalshareError::InvalidWithdrawal
+    )]
+    pub withdrawal: Account<'info, WithdrawalRequest>,
+
+    /// Recipient named in the request
+    /// CHECK: Must match withdrawal.recipient (has_one above); only receives lamports
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Business owner (signer)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Context for cancelling a treasury withdrawal
+#[derive(Accounts)]
+pub struct CancelWithdrawal<'info> {
+    /// Business owning the treasury
+    #[account(has_one = owner @ LocalshareError::InvalidBusinessOwner)]
+    pub business: Account<'info, Business>,
+
+    /// Treasury of the business
+    #[account(
+        seeds = [b"treasury", business.key().as_ref()],
+        bump = business_treasury.bump
+    )]
+    pub business_treasury: Account<'info, BusinessTreasury>,
+
+    /// Withdrawal being cancelled (rent returned to the owner)
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"withdrawal", business_treasury.key().as_ref(), &withdrawal.id.to_le_bytes()],
+        bump = withdrawal.bump
+    )]
+    pub withdrawal: Account<'info, WithdrawalRequest>,
+
+    /// Business owner (signer)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Context for enabling the milestone escrow of a business
+#[derive(Accounts)]
+pub struct InitMilestoneEscrow<'info> {
+    /// Business whose proceeds are escrowed
+    #[account(
+        mut,
+        has_one = owner @ LocalshareError::InvalidBusinessOwner
+    )]
+    pub business: Account<'info, Business>,
+
+    /// Milestone escrow PDA: ["milestone_escrow", business.key()]
+    #[account(
+        init,
+        seeds = [b"milestone_escrow", business.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + MilestoneEscrow::INIT_SPACE
+    )]
+    pub milestone_escrow: Account<'info, MilestoneEscrow>,
+
+    /// Business owner (signer)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for adding a milestone
+#[derive(Accounts)]
+pub struct AddMilestone<'info> {
+    /// Business the milestone belongs to
+    #[account(has_one = owner @ LocalshareError::InvalidBusinessOwner)]
+    pub business: Account<'info, Business>,
+
+    /// Milestone escrow of the business
+    #[account(
+        mut,
+        seeds = [b"milestone_escrow", business.key().as_ref()],
+        bump = milestone_escrow.bump
+    )]
+    pub milestone_escrow: Account<'info, MilestoneEscrow>,
+
+    /// Milestone PDA: ["milestone", business.key(), index]
+    #[account(
+        init,
+        seeds = [
+            b"milestone",
+            business.key().as_ref(),
+            &milestone_escrow.milestone_count.to_le_bytes()
+        ],
+        bump,
+        payer = owner,
+        space = 8 + Milestone::INIT_SPACE
+    )]
+    pub milestone: Account<'info, Milestone>,
+
+    /// Business owner (signer)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for approving a milestone
+#[derive(Accounts)]
+pub struct ApproveMilestone<'info> {
+    /// Global configuration (admin approval)
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Approver's Verifier role (admin approval by a delegate, and oracle approval)
+    #[account(
+        seeds = [RoleKind::Verifier.seed(), approver.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, Role>>,
+
+    /// Milestone escrow releasing the funds
+    #[account(
+        mut,
+        seeds = [b"milestone_escrow", milestone.business.as_ref()],
+        bump = milestone_escrow.bump,
+        has_one = release_to @ LocalshareError::InvalidBusiness
+    )]
+    pub milestone_escrow: Account<'info, MilestoneEscrow>,
+
+    /// Milestone being approved
+    #[account(
+        mut,
+        seeds = [b"milestone", milestone.business.as_ref(), &milestone.index.to_le_bytes()],
+        bump = milestone.bump
+    )]
+    pub milestone: Account<'info, Milestone>,
+
+    /// Proposal deciding the milestone (ShareholderVote approval only)
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    /// Account receiving the released funds
+    /// CHECK: Must match milestone_escrow.release_to (has_one above); only receives lamports
+    #[account(mut)]
+    pub release_to: UncheckedAccount<'info>,
+
+    /// Admin or oracle approving the milestone (any signer for vote approval)
+    pub approver: Signer<'info>,
+}
+
+/// Context for failing a milestone
+#[derive(Accounts)]
+pub struct FailMilestone<'info> {
+    /// Business whose treasury is restored
+    #[account(mut)]
+    pub business: Account<'info, Business>,
+
+    /// Milestone escrow of the business
+    #[account(
+        mut,
+        seeds = [b"milestone_escrow", business.key().as_ref()],
+        bump = milestone_escrow.bump
+    )]
+    pub milestone_escrow: Account<'info, MilestoneEscrow>,
+
+    /// Milestone that failed
+    #[account(
+        mut,
+        seeds = [b"milestone", business.key().as_ref(), &milestone.index.to_le_bytes()],
+        bump = milestone.bump
+    )]
+    pub milestone: Account<'info, Milestone>,
+
+    /// Defeated proposal (only needed before the deadline)
+    pub proposal: Option<Account<'info, Proposal>>,
+}
+
+/// Context for claiming a milestone refund
+#[derive(Accounts)]
+pub struct ClaimMilestoneRefund<'info> {
+    /// Failed milestone escrow paying the refund
+    #[account(
+        mut,
+        seeds = [b"milestone_escrow", milestone_escrow.business.as_ref()],
+        bump = milestone_escrow.bump
+    )]
+    pub milestone_escrow: Account<'info, MilestoneEscrow>,
+
+    /// Investor's position recording escrowed contributions
+    /// PDA: ["position", business.key(), investor.key()]
+    #[account(
+        seeds = [b"position", milestone_escrow.business.as_ref(), investor.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, InvestorPosition>,
+
+    /// Refund receipt PDA: ["milestone_refund", milestone_escrow.key(), investor.key()]
+    #[account(
+        init,
+        seeds = [b"milestone_refund", milestone_escrow.key().as_ref(), investor.key().as_ref()],
+        bump,
+        payer = investor,
+        space = 8 + MilestoneRefund::INIT_SPACE
+    )]
+    pub refund_receipt: Account<'info, MilestoneRefund>,
+
+    /// Investor claiming the refund
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for sweeping a failed milestone escrow
+#[derive(Accounts)]
+pub struct SweepMilestoneEscrow<'info> {
+    /// Failed milestone escrow being swept
+    #[account(
+        mut,
+        seeds = [b"milestone_escrow", milestone_escrow.business.as_ref()],
+        bump = milestone_escrow.bump,
+        has_one = release_to @ LocalshareError::InvalidBusiness
+    )]
+    pub milestone_escrow: Account<'info, MilestoneEscrow>,
+
+    /// Account named when the escrow was enabled, receiving the remainder
+    /// CHECK: Must match milestone_escrow.release_to (has_one above); only receives lamports
+    #[account(mut)]
+    pub release_to: UncheckedAccount<'info>,
+}
+
+/// Context for creating the stake pool of a business
+#[derive(Accounts)]
+pub struct InitStakePool<'info> {
+    /// Business whose shares are staked
+    #[account(has_one = share_mint @ LocalshareError::InvalidBusiness)]
+    pub business: Account<'info, Business>,
+
+    /// Stake pool PDA: ["stake_pool", business.key()]
+    #[account(
+        init,
+        seeds = [b"stake_pool", business.key().as_ref()],
+        bump,
+        payer = payer,
+        space = 8 + StakePool::INIT_SPACE
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// Staking vault holding staked shares
+    /// PDA: ["stake_vault", business.key()]
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"stake_vault", business.key().as_ref()],
+        bump,
+        token::mint = share_mint,
+        token::authority = stake_pool,
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    /// Share mint account for the business
+    pub share_mint: Account<'info, Mint>,
+
+    /// Anyone paying for the pool accounts
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for creating the revenue share of a business
+#[derive(Accounts)]
+pub struct InitRevenueShare<'info> {
+    /// Business sharing its revenue
+    #[account(has_one = owner @ LocalshareError::InvalidBusinessOwner)]
+    pub business: Account<'info, Business>,
+
+    /// Stake pool the revenue accrues to
+    #[account(
+        mut,
+        seeds = [b"stake_pool", business.key().as_ref()],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// Revenue share PDA: ["revenue_share", business.key()]
+    #[account(
+        init,
+        seeds = [b"revenue_share", business.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + RevenueShare::INIT_SPACE
+    )]
+    pub revenue_share: Account<'info, RevenueShare>,
+
+    /// Business owner (signer)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
-/// Authority for minting business equity share tokens
-/// PDA: ["share_mint_authority", business.key()]
-#[account]
-pub struct ShareMintAuthority {
-    /// Business this authority belongs to
-    pub business: Pubkey,
-    /// Bump seed
-    pub bump: u8,
+/// Context for depositing revenue
+#[derive(Accounts)]
+pub struct DepositRevenue<'info> {
+    /// Business sharing its revenue
+    pub business: Account<'info, Business>,
+
+    /// Stake pool the revenue accrues to
+    #[account(
+        seeds = [b"stake_pool", business.key().as_ref()],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// Revenue share receiving the deposit
+    #[account(
+        mut,
+        seeds = [b"revenue_share", business.key().as_ref()],
+        bump = revenue_share.bump
+    )]
+    pub revenue_share: Account<'info, RevenueShare>,
+
+    /// Business owner or configured depositor (signer)
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
-/// Represents a share offering from a business
-/// PDA: ["offering", business.key(), share_mint.key()]
-#[account]
-pub struct Offering {
-    /// Reference to the business that created this offering
-    pub business: Pubkey,
-    
-    /// Mint of the shares being offered
-    pub share_mint: Pubkey,
-    
-    /// Mint of the token accepted as payment
-    pub payment_mint: Pubkey,
-    
-    /// Price per share (in lamports of payment_mint)
-    pub price_per_share: u64,
-    
-    /// Amount of shares still available
-    pub remaining_shares: u64,
-    
-    /// Whether the offering is active
-    pub is_active: bool,
-    
-    /// PDA bump seed
-    pub bump: u8,
+/// Context for staking shares
+#[derive(Accounts)]
+pub struct StakeShares<'info> {
+    /// Business whose shares are staked
+    pub business: Account<'info, Business>,
+
+    /// Stake pool owning the staking vault
+    #[account(
+        mut,
+        seeds = [b"stake_pool", business.key().as_ref()],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// Revenue share settled on stake changes (required once enabled)
+    #[account(
+        seeds = [b"revenue_share", business.key().as_ref()],
+        bump = revenue_share.bump
+    )]
+    pub revenue_share: Option<Account<'info, RevenueShare>>,
+
+    /// Staking vault PDA: ["stake_vault", business.key()]
+    #[account(
+        mut,
+        seeds = [b"stake_vault", business.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    /// Holder's stake position PDA: ["stake", business.key(), holder.key()]
+    #[account(
+        init_if_needed,
+        seeds = [b"stake", business.key().as_ref(), holder.key().as_ref()],
+        bump,
+        payer = holder,
+        space = 8 + StakePosition::INIT_SPACE
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    /// Holder's share token account
+    #[account(
+        mut,
+        constraint = holder_shares_ata.owner == holder.key() @ LocalshareError::InvalidBeneficiary,
+        constraint = holder_shares_ata.mint == stake_pool.share_mint @ LocalshareError::InvalidBusiness
+    )]
+    pub holder_shares_ata: Account<'info, TokenAccount>,
+
+    /// Holder staking the shares
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
-// ============================================================================
-// Context Structs (Accounts) for Instructions
-// ============================================================================
+/// Context for unstaking shares
+#[derive(Accounts)]
+pub struct UnstakeShares<'info> {
+    /// Business whose shares are staked
+    pub business: Account<'info, Business>,
 
-/// Context for initializing the global configuration
-/// Can only be called once to configure the protocol
+    /// Stake pool owning the staking vault
+    #[account(
+        mut,
+        seeds = [b"stake_pool", business.key().as_ref()],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// Revenue share settled on stake changes (required once enabled)
+    #[account(
+        seeds = [b"revenue_share", business.key().as_ref()],
+        bump = revenue_share.bump
+    )]
+    pub revenue_share: Option<Account<'info, RevenueShare>>,
+
+    /// Staking vault PDA: ["stake_vault", business.key()]
+    #[account(
+        mut,
+        seeds = [b"stake_vault", business.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    /// Holder's stake position
+    #[account(
+        mut,
+        seeds = [b"stake", business.key().as_ref(), holder.key().as_ref()],
+        bump = stake_position.bump
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    /// Holder's share token account receiving the shares
+    #[account(
+        mut,
+        constraint = holder_shares_ata.owner == holder.key() @ LocalshareError::InvalidBeneficiary,
+        constraint = holder_shares_ata.mint == stake_pool.share_mint @ LocalshareError::InvalidBusiness
+    )]
+    pub holder_shares_ata: Account<'info, TokenAccount>,
+
+    /// Holder unstaking the shares
+    pub holder: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for claiming accrued revenue
 #[derive(Accounts)]
-pub struct InitConfig<'info> {
-    /// Config account being initialized as PDA
-    /// Space: 8 (discriminator) + 32 (admin) + 32 (payment_mint) + 1 (bump) = 73 bytes
+pub struct ClaimRevenue<'info> {
+    /// Business sharing its revenue
+    pub business: Account<'info, Business>,
+
+    /// Revenue share paying the claim
+    #[account(
+        mut,
+        seeds = [b"revenue_share", business.key().as_ref()],
+        bump = revenue_share.bump
+    )]
+    pub revenue_share: Account<'info, RevenueShare>,
+
+    /// Holder's stake position
+    #[account(
+        mut,
+        seeds = [b"stake", business.key().as_ref(), holder.key().as_ref()],
+        bump = stake_position.bump
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    /// Holder claiming the revenue
+    #[account(mut)]
+    pub holder: Signer<'info>,
+}
+
+/// Context for voting with staked shares
+#[derive(Accounts)]
+pub struct CastStakedVote<'info> {
+    /// Proposal being voted on
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.business.as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Staked vote record PDA: ["staked_vote", proposal.key(), voter.key()]
+    /// Creation fails if the voter already voted with staked shares
+    #[account(
+        init,
+        seeds = [b"staked_vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump,
+        payer = voter,
+        space = 8 + VoteRecord::INIT_SPACE
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    /// Voter's stake position in the proposal's business
+    #[account(
+        mut,
+        seeds = [b"stake", proposal.business.as_ref(), voter.key().as_ref()],
+        bump = stake_position.bump
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    /// Voter (signer, pays for the vote record)
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for setting the verification policy
+#[derive(Accounts)]
+pub struct SetVerificationPolicy<'info> {
+    /// Global configuration
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Authority's Verifier role (not needed for Config.admin)
+    #[account(
+        seeds = [RoleKind::Verifier.seed(), authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, Role>>,
+
+    /// Verification policy PDA: ["verification_policy"]
+    #[account(
+        init_if_needed,
+        seeds = [b"verification_policy"],
+        bump,
+        payer = authority,
+        space = 8 + VerificationPolicy::INIT_SPACE
+    )]
+    pub verification_policy: Account<'info, VerificationPolicy>,
+
+    /// Config.admin or Verifier (signer, pays for a new policy)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for granting a role
+#[derive(Accounts)]
+#[instruction(kind: RoleKind, grantee: Pubkey)]
+pub struct GrantRole<'info> {
+    /// Global configuration
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ LocalshareError::UnauthorizedRole
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Role PDA: [kind.seed(), grantee]
     #[account(
         init,
+        seeds = [kind.seed(), grantee.as_ref()],
+        bump,
+        payer = admin,
+        space = 8 + Role::INIT_SPACE
+    )]
+    pub role: Account<'info, Role>,
+
+    /// Protocol administrator (signer)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for revoking a role
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    /// Global configuration
+    #[account(
         seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ LocalshareError::UnauthorizedRole
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Role being revoked (rent returned to the admin)
+    #[account(
+        mut,
+        close = admin,
+        seeds = [role.kind.seed(), role.grantee.as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, Role>,
+
+    /// Protocol administrator (signer)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+/// Context for requesting verification
+#[derive(Accounts)]
+pub struct RequestVerification<'info> {
+    /// Business being submitted
+    #[account(has_one = owner @ LocalshareError::InvalidBusinessOwner)]
+    pub business: Account<'info, Business>,
+
+    /// Verification record PDA: ["verification", business.key()]
+    #[account(
+        init_if_needed,
+        seeds = [b"verification", business.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + BusinessVerification::INIT_SPACE
+    )]
+    pub verification: Account<'info, BusinessVerification>,
+
+    /// Business owner (signer)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for reviewing a verification request
+#[derive(Accounts)]
+pub struct ReviewVerification<'info> {
+    /// Global configuration
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Reviewer's Verifier role (not needed for Config.admin)
+    #[account(
+        seeds = [RoleKind::Verifier.seed(), reviewer.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, Role>>,
+
+    /// Verification record being reviewed
+    #[account(
+        mut,
+        seeds = [b"verification", verification.business.as_ref()],
+        bump = verification.bump
+    )]
+    pub verification: Account<'info, BusinessVerification>,
+
+    /// Admin or verifier (signer)
+    pub reviewer: Signer<'info>,
+}
+
+/// Context for suspending a business
+#[derive(Accounts)]
+pub struct SuspendBusiness<'info> {
+    /// Global configuration
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Reviewer's Verifier or Pauser role (not needed for Config.admin)
+    #[account(
+        seeds = [role.kind.seed(), reviewer.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, Role>>,
+
+    /// Business being suspended (delisted)
+    #[account(mut)]
+    pub business: Account<'info, Business>,
+
+    /// Registry page holding the business (required once it is in the registry)
+    #[account(mut)]
+    pub registry_page: Option<AccountLoader<'info, BusinessRegistry>>,
+
+    /// Category and region index pages holding the business (required once it is indexed)
+    #[account(mut)]
+    pub category_index: Option<AccountLoader<'info, SearchIndex>>,
+
+    #[account(mut)]
+    pub region_index: Option<AccountLoader<'info, SearchIndex>>,
+
+    /// Verification record PDA: ["verification", business.key()]
+    #[account(
+        init_if_needed,
+        seeds = [b"verification", business.key().as_ref()],
         bump,
-        payer = admin,
-        space = 8 + 32 + 32 + 1
+        payer = reviewer,
+        space = 8 + BusinessVerification::INIT_SPACE
     )]
-    pub config: Account<'info, Config>,
+    pub verification: Account<'info, BusinessVerification>,
 
-    /// Administrator who pays for creation and will be set as admin
+    /// Admin or verifier (signer, pays for a new verification record)
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub reviewer: Signer<'info>,
 
-    /// System program to create the account
     pub system_program: Program<'info, System>,
 }
 
-/// Context for registering a new business or updating an existing one
-/// Allows entrepreneurs to create their business profile or update business name
+/// Context for reinstating a suspended business
 #[derive(Accounts)]
-pub struct RegisterBusiness<'info> {
-    /// Business account PDA: ["business", owner.key()]
-    /// Space: 8 (discriminator) + 32 (owner) + (4 + 50) (name) + 32 (share_mint) + 8 (total_shares) + 8 (price_per_share_lamports) + 32 (treasury) + 1 (is_listed) + 1 (bump) = 176 bytes
+pub struct ReinstateBusiness<'info> {
+    /// Global configuration
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Reviewer's Verifier or Pauser role (not needed for Config.admin)
     #[account(
-        init_if_needed,
-        seeds = [b"business", owner.key().as_ref()],
-        bump,
-        payer = owner,
-        space = 8 + 32 + (4 + 50) + 32 + 8 + 8 + 32 + 1 + 1
+        seeds = [role.kind.seed(), reviewer.key().as_ref()],
+        bump = role.bump
     )]
-    pub business: Account<'info, Business>,
+    pub role: Option<Account<'info, Role>>,
 
-    /// Mint account for business shares
-    /// PDA: ["mint", business.key()]
+    /// Verification record of the suspended business
     #[account(
-        init_if_needed,
-        seeds = [b"mint", business.key().as_ref()],
-        bump,
-        payer = owner,
-        mint::decimals = 0,
-        mint::authority = mint_authority.key(),
-        mint::freeze_authority = mint_authority.key(),
+        mut,
+        seeds = [b"verification", verification.business.as_ref()],
+        bump = verification.bump
     )]
-    pub mint: Account<'info, Mint>,
+    pub verification: Account<'info, BusinessVerification>,
+
+    /// Config.admin, Verifier or Pauser (signer)
+    pub reviewer: Signer<'info>,
+}
+
+/// Context for freezing a business
+#[derive(Accounts)]
+pub struct FreezeBusiness<'info> {
+    /// Global configuration
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
 
-    /// Mint authority PDA for the business
-    /// PDA: ["mint_authority", business.key()]
+    /// Authority's Pauser role (not needed for the owner or Config.admin)
     #[account(
-        init_if_needed,
-        seeds = [b"mint_authority", business.key().as_ref()],
-        bump,
-        payer = owner,
-        space = 8 + 32 + 1
+        seeds = [RoleKind::Pauser.seed(), authority.key().as_ref()],
+        bump = role.bump
     )]
-    pub mint_authority: Account<'info, MintAuthority>,
+    pub role: Option<Account<'info, Role>>,
+
+    /// Business being frozen
+    pub business: Account<'info, Business>,
 
-    /// Owner's token account for receiving shares
+    /// Freeze PDA: ["freeze", business.key()]
     #[account(
         init_if_needed,
-        payer = owner,
-        associated_token::mint = mint,
-        associated_token::authority = owner,
+        seeds = [b"freeze", business.key().as_ref()],
+        bump,
+        payer = authority,
+        space = 8 + BusinessFreeze::INIT_SPACE
     )]
-    pub owner_token_account: Account<'info, TokenAccount>,
+    pub business_freeze: Account<'info, BusinessFreeze>,
 
-    /// Business owner (signer)
+    /// Owner, admin or pauser (signer, pays for a new freeze record)
     #[account(mut)]
-    pub owner: Signer<'info>,
+    pub authority: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
-/// Context for configuring a business offering
-/// Allows the business owner to set offering parameters
+/// Context for unfreezing a business
 #[derive(Accounts)]
-pub struct ConfigureOffering<'info> {
-    /// Business account being configured
-    /// Must be mutable to update offering parameters
-    /// Uses has_one constraint to ensure only the owner can call this
+pub struct UnfreezeBusiness<'info> {
+    /// Global configuration
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Authority's Pauser role (not needed for the owner or Config.admin)
     #[account(
-        mut,
-        has_one = owner @ LocalshareError::InvalidBusinessOwner
+        seeds = [RoleKind::Pauser.seed(), authority.key().as_ref()],
+        bump = role.bump
     )]
+    pub role: Option<Account<'info, Role>>,
+
+    /// Business being unfrozen
     pub business: Account<'info, Business>,
 
-    /// Business owner (signer)
-    /// Must match the owner field in the business account
-    #[account(mut)]
-    pub owner: Signer<'info>,
+    /// Freeze record of the business
+    #[account(
+        mut,
+        seeds = [b"freeze", business.key().as_ref()],
+        bump = business_freeze.bump
+    )]
+    pub business_freeze: Account<'info, BusinessFreeze>,
 
-    /// System program (for potential future use)
-    pub system_program: Program<'info, System>,
+    /// Owner, admin or pauser (signer)
+    pub authority: Signer<'info>,
 }
 
-/// Context for initializing the share mint and vault
-/// Creates an SPL mint for business equity tokens and mints all shares into a vault
+/// Context for freezing or thawing holder token accounts
+/// Holder token accounts are passed as writable remaining accounts
 #[derive(Accounts)]
-pub struct InitShareMint<'info> {
-    /// Business account being configured
-    /// Must be mutable to update share_mint field
-    /// Uses has_one constraint to ensure only the owner can call this
+pub struct FreezeHolderAccounts<'info> {
+    /// Global configuration
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Authority's Pauser role (not needed for Config.admin)
     #[account(
-        mut,
-        has_one = owner @ LocalshareError::InvalidBusinessOwner
+        seeds = [RoleKind::Pauser.seed(), authority.key().as_ref()],
+        bump = role.bump
     )]
-    pub business: Account<'info, Business>,
+    pub role: Option<Account<'info, Role>>,
 
-    /// Business owner (signer)
-    /// Must match the owner field in the business account
-    #[account(mut)]
-    pub owner: Signer<'info>,
+    /// Business whose holders are frozen or thawed
+    pub business: Account<'info, Business>,
 
-    /// Share mint account for business equity tokens
-    /// PDA: ["share_mint", business.key()]
+    /// Freeze PDA: ["freeze", business.key()]
+    /// CHECK: Address enforced by seeds; contents loaded by BusinessFreeze::is_frozen
     #[account(
-        init,
-        payer = owner,
-        seeds = [b"share_mint", business.key().as_ref()],
-        bump,
-        mint::decimals = 0,
-        mint::authority = share_mint_authority,
-        mint::freeze_authority = share_mint_authority,
+        seeds = [b"freeze", business.key().as_ref()],
+        bump
     )]
+    pub business_freeze: UncheckedAccount<'info>,
+
+    /// Share mint of the business
+    #[account(address = business.share_mint @ LocalshareError::InvalidBusiness)]
     pub share_mint: Account<'info, Mint>,
 
-    /// Authority for the share mint
-    /// PDA: ["share_mint_authority", business.key()]
+    /// Freeze authority of the share mint
     #[account(
-        init,
-        payer = owner,
         seeds = [b"share_mint_authority", business.key().as_ref()],
-        bump,
-        space = 8 + 32 + 1
+        bump = share_mint_authority.bump
     )]
     pub share_mint_authority: Account<'info, ShareMintAuthority>,
 
-    /// Token vault for storing all business shares
-    /// PDA: ["shares_vault", business.key()]
-    #[account(
-        init,
-        payer = owner,
-        seeds = [b"shares_vault", business.key().as_ref()],
-        bump,
-        token::mint = share_mint,
-        token::authority = share_mint_authority,
-    )]
-    pub shares_vault: Account<'info, TokenAccount>,
+    /// Admin or pauser (signer)
+    pub authority: Signer<'info>,
 
-    /// Token program for SPL token operations
     pub token_program: Program<'info, Token>,
-
-    /// System program for account creation
-    pub system_program: Program<'info, System>,
 }
 
-/// Context for creating a business share mint
-/// Creates the SPL token mint and mint authority for a business
-
+impl<'info> FreezeHolderAccounts<'info> {
+    /// Freezes or thaws each holder account, skipping those already in the target state
+    /// Returns the number of accounts changed
+    fn set_frozen(&self, holders: &'info [AccountInfo<'info>], frozen: bool) -> Result<u32> {
+        require!(
+            Role::authorizes(
+                &self.config,
+                self.role.as_deref(),
+                &[RoleKind::Pauser],
+                &self.authority.key()
+            ),
+            LocalshareError::UnauthorizedRole
+        );
 
-/// Context for creating a share offering
-/// Allows a registered business to create a share offering
-#[derive(Accounts)]
-pub struct CreateOffering<'info> {
-    #[account(
-        init,
-        seeds = [b"offering", business.key().as_ref(), business.share_mint.as_ref()],
-        bump,
-        payer = owner,
-        space = 8 + 32 + 32 + 32 + 8 + 8 + 1 + 1
-    )]
-    pub offering: Account<'info, Offering>,
+        let business_key = self.business.key();
+        let seeds = &[
+            b"share_mint_authority",
+            business_key.as_ref(),
+            &[self.share_mint_authority.bump],
+        ];
+        let signer = &[&seeds[..]];
 
-    #[account(has_one = owner)]
-    pub business: Account<'info, Business>,
+        let mut changed = 0u32;
+        for holder in holders {
+            let account = Account::<TokenAccount>::try_from(holder)?;
+            require_keys_eq!(account.mint, self.share_mint.key(), LocalshareError::InvalidBusiness);
+            if account.is_frozen() == frozen {
+                continue;
+            }
 
-    #[account(
-        seeds = [b"config"],
-        bump = config.bump
-    )]
-    pub config: Account<'info, Config>,
+            let cpi_program = self.token_program.to_account_info();
+            let mint = self.share_mint.to_account_info();
+            let authority = self.share_mint_authority.to_account_info();
+            if frozen {
+                token::freeze_account(CpiContext::new_with_signer(
+                    cpi_program,
+                    token::FreezeAccount { account: holder.clone(), mint, authority },
+                    signer,
+                ))?;
+            } else {
+                token::thaw_account(CpiContext::new_with_signer(
+                    cpi_program,
+                    token::ThawAccount { account: holder.clone(), mint, authority },
+                    signer,
+                ))?;
+            }
+            changed += 1;
+        }
 
-    #[account(
-        constraint = business.share_mint == mint.key() @ LocalshareError::InvalidBusiness
-    )]
-    pub mint: Account<'info, Mint>,
+        Ok(changed)
+    }
+}
 
+/// Context for migrating a business account in place
+#[derive(Accounts)]
+pub struct MigrateBusiness<'info> {
+    /// Business account PDA: ["business", owner.key()]
+    /// CHECK: Address enforced by seeds; loaded by migration::load after growing
     #[account(
         mut,
-        constraint = owner_token_account.owner == owner.key() @ LocalshareError::InvalidBusinessOwner,
-        constraint = owner_token_account.mint == mint.key() @ LocalshareError::InvalidBusiness
-    )]
-    pub owner_token_account: Account<'info, TokenAccount>,
-
-    #[account(
-        init_if_needed,
-        payer = owner,
-        associated_token::mint = mint,
-        associated_token::authority = offering,
-        constraint = offering_vault.owner == offering.key() @ LocalshareError::InvalidBusiness,
+        seeds = [b"business", owner.key().as_ref()],
+        bump
     )]
-    pub offering_vault: Account<'info, TokenAccount>,
+    pub business: UncheckedAccount<'info>,
 
+    /// Business owner (signer, pays the rent top-up)
     #[account(mut)]
     pub owner: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
-/// Context for buying shares
-/// Allows investors to buy shares from an active offering
+/// Context for migrating the global configuration in place
 #[derive(Accounts)]
-pub struct BuyShares<'info> {
-    /// Offering account from which shares will be purchased
-    /// Must be mutable to update remaining_shares
+pub struct MigrateConfig<'info> {
+    /// Config PDA: ["config"]
+    /// CHECK: Address enforced by seeds; loaded by migration::load after growing
     #[account(
         mut,
-        constraint = offering.business == business.key() @ LocalshareError::InvalidBusiness
+        seeds = [b"config"],
+        bump
     )]
-    pub offering: Account<'info, Offering>,
+    pub config: UncheckedAccount<'info>,
 
-    /// Business account related to the offering
+    /// Protocol administrator (signer, pays the rent top-up)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for migrating an indexed offering in place
+#[derive(Accounts)]
+#[instruction(index: u32)]
+pub struct MigrateOffering<'info> {
+    /// Business that owns the offering
     #[account(has_one = owner @ LocalshareError::InvalidBusinessOwner)]
     pub business: Account<'info, Business>,
 
-    /// Mint account for the business shares
+    /// Offering PDA: ["offering", business.key(), index]
+    /// CHECK: Address enforced by seeds; loaded by migration::load after growing
     #[account(
         mut,
-        constraint = offering.share_mint == mint.key() @ LocalshareError::InvalidBusiness
+        seeds = [b"offering", business.key().as_ref(), &index.to_le_bytes()],
+        bump
     )]
-    pub mint: Account<'info, Mint>,
+    pub offering: UncheckedAccount<'info>,
+
+    /// Business owner (signer, pays the rent top-up)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
 
+/// Context for creating a registry page
+#[derive(Accounts)]
+#[instruction(page: u32)]
+pub struct InitRegistryPage<'info> {
+    /// Registry page PDA: ["registry", page]
     #[account(
-        mut,
-        constraint = offering_vault.mint == mint.key() @ LocalshareError::InvalidBusiness,
+        init,
+        seeds = [b"registry", page.to_le_bytes().as_ref()],
+        bump,
+        payer = payer,
+        space = 8 + std::mem::size_of::<BusinessRegistry>()
     )]
-    pub offering_vault: Account<'info, TokenAccount>,
+    pub registry_page: AccountLoader<'info, BusinessRegistry>,
 
-    /// Buyer's token account to receive the shares
+    /// Previous page, which must be full (omitted for page 0)
     #[account(
-        init_if_needed,
-        payer = buyer,
-        associated_token::mint = mint,
-        associated_token::authority = buyer,
+        seeds = [b"registry", page.saturating_sub(1).to_le_bytes().as_ref()],
+        bump
     )]
-    pub buyer_token_account: Account<'info, TokenAccount>,
-
-    /// Business owner (receives payment)
-    /// Referenced as 'owner' by the has_one constraint
-    /// CHECK: Validated by has_one constraint in business
-    #[account(mut)]
-    pub owner: UncheckedAccount<'info>,
+    pub previous_page: Option<AccountLoader<'info, BusinessRegistry>>,
 
-    /// Buyer acquiring the shares
+    /// Pays for the page
     #[account(mut)]
-    pub buyer: Signer<'info>,
-
-    /// Token program (for SPL token operations)
-    pub token_program: Program<'info, Token>,
+    pub payer: Signer<'info>,
 
-    pub associated_token_program: Program<'info, AssociatedToken>,
-
-    /// System program (for SOL transfers and account creation)
     pub system_program: Program<'info, System>,
+}
 
-    /// Rent sysvar (for PDA validation)
-    pub rent: Sysvar<'info, Rent>,
+/// Context for adding an existing business to the registry
+#[derive(Accounts)]
+pub struct AddToRegistry<'info> {
+    /// Business being added
+    #[account(mut)]
+    pub business: Account<'info, Business>,
+
+    /// Registry page with room for the business
+    #[account(mut)]
+    pub registry_page: AccountLoader<'info, BusinessRegistry>,
 }
 
-/// Context for listing a business on the marketplace
-/// Allows the business owner to make their business available for investment
+/// Context for unlisting a business
 #[derive(Accounts)]
-pub struct ListBusiness<'info> {
-    /// Business account being listed
-    /// Must be mutable to update is_listed field
-    /// Uses has_one constraint to ensure only the owner can call this
+pub struct UnlistBusiness<'info> {
+    /// Business being unlisted
     #[account(
         mut,
         has_one = owner @ LocalshareError::InvalidBusinessOwner
     )]
     pub business: Account<'info, Business>,
 
+    /// Registry page holding the business (required once it is in the registry)
+    #[account(mut)]
+    pub registry_page: Option<AccountLoader<'info, BusinessRegistry>>,
+
+    /// Category and region index pages holding the business (required once it is indexed)
+    #[account(mut)]
+    pub category_index: Option<AccountLoader<'info, SearchIndex>>,
+
+    #[account(mut)]
+    pub region_index: Option<AccountLoader<'info, SearchIndex>>,
+
     /// Business owner (signer)
-    /// Must match the owner field in the business account
     pub owner: Signer<'info>,
-
-    /// System program (for potential future use)
-    pub system_program: Program<'info, System>,
 }
 
-/// Context for buying shares directly from a listed business
-/// Allows investors to purchase shares by transferring SOL to treasury and receiving tokens from vault
+/// Context for creating a search index page
 #[derive(Accounts)]
-pub struct BuySharesFromBusiness<'info> {
-    /// Buyer who is purchasing the shares
-    #[account(mut)]
-    pub buyer: Signer<'info>,
+#[instruction(kind: IndexKind, key: [u8; SEARCH_INDEX_KEY_LENGTH], page: u32)]
+pub struct InitSearchIndexPage<'info> {
+    /// Index page PDA: [kind.seed(), key, page]
+    #[account(
+        init,
+        seeds = [kind.seed(), key.as_ref(), page.to_le_bytes().as_ref()],
+        bump,
+        payer = payer,
+        space = 8 + std::mem::size_of::<SearchIndex>()
+    )]
+    pub index_page: AccountLoader<'info, SearchIndex>,
 
-    /// Business account from which shares are being purchased
-    /// Must be mutable to potentially track state changes
+    /// Previous page of the same index, which must be full (omitted for page 0)
     #[account(
-        mut,
-        constraint = business.share_mint == share_mint.key() @ LocalshareError::InvalidBusiness
+        seeds = [kind.seed(), key.as_ref(), page.saturating_sub(1).to_le_bytes().as_ref()],
+        bump
     )]
+    pub previous_page: Option<AccountLoader<'info, SearchIndex>>,
+
+    /// Pays for the page
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for publishing a report
+#[derive(Accounts)]
+#[instruction(period: u32)]
+pub struct PublishReport<'info> {
+    /// Business publishing the report
+    #[account(has_one = owner @ LocalshareError::InvalidBusinessOwner)]
     pub business: Account<'info, Business>,
 
-    /// Shares vault PDA that holds all the business shares
-    /// PDA: ["shares_vault", business.key()]
+    /// Reporting status PDA: ["reporting", business.key()]
     #[account(
-        mut,
-        seeds = [b"shares_vault", business.key().as_ref()],
+        init_if_needed,
+        seeds = [b"reporting", business.key().as_ref()],
         bump,
-        constraint = shares_vault.mint == share_mint.key() @ LocalshareError::InvalidBusiness
+        payer = owner,
+        space = 8 + ReportingStatus::INIT_SPACE
     )]
-    pub shares_vault: Account<'info, TokenAccount>,
+    pub reporting: Account<'info, ReportingStatus>,
 
-    /// Treasury account that receives SOL payments
-    /// Must match business.treasury
+    /// Report PDA: ["report", business.key(), period]
     #[account(
-        mut,
-        constraint = treasury.key() == business.treasury @ LocalshareError::InvalidBusiness
+        init,
+        seeds = [b"report", business.key().as_ref(), period.to_le_bytes().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + Report::INIT_SPACE
     )]
-    /// CHECK: Validated by constraint above
-    pub treasury: SystemAccount<'info>,
+    pub report: Account<'info, Report>,
 
-    /// Buyer's associated token account to receive the share tokens
-    #[account(
-        init_if_needed,
-        payer = buyer,
-        associated_token::mint = share_mint,
-        associated_token::authority = buyer,
-    )]
-    pub buyer_shares_ata: Account<'info, TokenAccount>,
+    /// Business owner (signer, pays for the report)
+    #[account(mut)]
+    pub owner: Signer<'info>,
 
-    /// Share mint account for the business
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for flagging overdue reporting
+#[derive(Accounts)]
+pub struct FlagOverdueReport<'info> {
+    /// Global configuration
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Reviewer's Verifier role (not needed for Config.admin)
     #[account(
-        constraint = share_mint.key() == business.share_mint @ LocalshareError::InvalidBusiness
+        seeds = [RoleKind::Verifier.seed(), reviewer.key().as_ref()],
+        bump = role.bump
     )]
-    pub share_mint: Account<'info, Mint>,
+    pub role: Option<Account<'info, Role>>,
 
-    /// Share mint authority PDA that controls the shares_vault
-    /// PDA: ["share_mint_authority", business.key()]
+    /// Listed business being flagged
+    pub business: Account<'info, Business>,
+
+    /// Reporting status PDA: ["reporting", business.key()]
     #[account(
-        seeds = [b"share_mint_authority", business.key().as_ref()],
+        init_if_needed,
+        seeds = [b"reporting", business.key().as_ref()],
         bump,
-        constraint = share_mint_authority.business == business.key() @ LocalshareError::InvalidBusiness
+        payer = reviewer,
+        space = 8 + ReportingStatus::INIT_SPACE
     )]
-    pub share_mint_authority: Account<'info, ShareMintAuthority>,
+    pub reporting: Account<'info, ReportingStatus>,
 
-    /// Token program for SPL token operations
-    pub token_program: Program<'info, Token>,
+    /// Config.admin or Verifier (signer, pays for a new reporting status)
+    #[account(mut)]
+    pub reviewer: Signer<'info>,
 
-    /// System program for SOL transfers
     pub system_program: Program<'info, System>,
+}
 
-    /// Associated token program for ATA creation
-    pub associated_token_program: Program<'info, AssociatedToken>,
+// ============================================================================
+// Events
+// ============================================================================
+
+/// Emitted by buy_shares_from_offering with the executed price
+#[event]
+pub struct SharesPurchased {
+    /// Business the shares were bought from
+    pub business: Pubkey,
+    /// Buyer of the shares
+    pub buyer: Pubkey,
+    /// Shares bought
+    pub amount_shares: u64,
+    /// Executed price per share (blended average across tiers)
+    pub price_per_share_lamports: u64,
+    /// Total lamports paid (blended cost)
+    pub total_cost_lamports: u64,
+    /// Unix timestamp of the purchase
+    pub timestamp: i64,
+}
+
+/// Emitted by sell_to_buyback for each accepted sale
+#[event]
+pub struct SharesBoughtBack {
+    /// Business that bought the shares back
+    pub business: Pubkey,
+    /// Shareholder who sold
+    pub seller: Pubkey,
+    /// Shares sold
+    pub amount_shares: u64,
+    /// Buyback price per share in lamports
+    pub price_per_share_lamports: u64,
+    /// Lamports paid to the seller
+    pub total_lamports: u64,
+    /// Whether the shares were burned
+    pub burned: bool,
+    /// Unix timestamp of the sale
+    pub timestamp: i64,
+}
+
+/// Emitted by request_withdrawal so investors can follow planned use of funds
+#[event]
+pub struct TreasuryWithdrawalRequested {
+    /// Business owning the treasury
+    pub business: Pubkey,
+    /// Request id within the treasury
+    pub id: u64,
+    /// Account that will receive the funds
+    pub recipient: Pubkey,
+    /// Lamports requested
+    pub amount: u64,
+    /// Public purpose of the withdrawal
+    pub purpose: String,
+    /// Unix timestamp from which the request can be executed
+    pub executable_at: i64,
+}
+
+/// Emitted by execute_withdrawal when funds leave the treasury
+#[event]
+pub struct TreasuryWithdrawalExecuted {
+    /// Business owning the treasury
+    pub business: Pubkey,
+    /// Request id within the treasury
+    pub id: u64,
+    /// Account that received the funds
+    pub recipient: Pubkey,
+    /// Lamports withdrawn
+    pub amount: u64,
+    /// Unix timestamp of the withdrawal
+    pub timestamp: i64,
+}
+
+/// Emitted by freeze_business and unfreeze_business
+#[event]
+pub struct BusinessFreezeChanged {
+    /// Business frozen or unfrozen
+    pub business: Pubkey,
+    /// New freeze state
+    pub frozen: bool,
+    /// Reason code recorded with the change
+    pub reason_code: u16,
+    /// Wallet that made the change
+    pub authority: Pubkey,
+    /// Unix timestamp of the change
+    pub timestamp: i64,
+}
 
-    /// Rent sysvar (for PDA validation)
-    pub rent: Sysvar<'info, Rent>,
+/// Emitted by publish_report
+#[event]
+pub struct ReportPublished {
+    /// Business that published the report
+    pub business: Pubkey,
+    /// Report account
+    pub report: Pubkey,
+    /// Reporting period
+    pub period: u32,
+    /// SHA-256 of the report document
+    pub content_hash: [u8; 32],
+    /// Unix timestamp of publication
+    pub timestamp: i64,
+}
+
+/// Emitted by flag_overdue_report
+#[event]
+pub struct ReportingOverdue {
+    /// Business flagged
+    pub business: Pubkey,
+    /// Period of the latest report (0 if none)
+    pub last_period: u32,
+    /// Reports published so far
+    pub report_count: u32,
+    /// Unix timestamp of the flag
+    pub timestamp: i64,
 }
 
 // ============================================================================
@@ -923,7 +8776,7 @@ pub enum LocalshareError {
     #[msg("Business name cannot be empty")]
     EmptyBusinessName,
     
-    #[msg("Business name cannot be longer than 50 characters")]
+    #[msg("Business name cannot be longer than 50 characters (100 bytes)")]
     BusinessNameTooLong,
     
     #[msg("Price per share must be greater than zero")]
@@ -952,4 +8805,945 @@ pub enum LocalshareError {
     
     #[msg("Business is already listed on the marketplace")]
     BusinessAlreadyListed,
+    
+    #[msg("Cap table snapshot is invalid")]
+    InvalidSnapshot,
+    
+    #[msg("Merkle proof is invalid")]
+    InvalidMerkleProof,
+    
+    #[msg("Airdrop does not have enough shares left")]
+    AirdropExhausted,
+    
+    #[msg("Airdrop has expired")]
+    AirdropExpired,
+    
+    #[msg("Airdrop can still be claimed")]
+    AirdropActive,
+    
+    #[msg("Vesting schedule is invalid")]
+    InvalidVestingSchedule,
+    
+    #[msg("No vested shares available to release")]
+    NothingToRelease,
+    
+    #[msg("Vesting schedule cannot be revoked")]
+    VestingNotRevocable,
+    
+    #[msg("Signer is not the beneficiary of this schedule")]
+    InvalidBeneficiary,
+    
+    #[msg("Governance configuration is invalid")]
+    InvalidGovernanceConfig,
+    
+    #[msg("Proposal parameters are invalid")]
+    InvalidProposal,
+    
+    #[msg("Not enough shares to perform this governance action")]
+    InsufficientVotingPower,
+    
+    #[msg("Voting is closed for this proposal")]
+    VotingClosed,
+    
+    #[msg("Voting is still open for this proposal")]
+    VotingStillOpen,
+    
+    #[msg("Proposal has already been finalized")]
+    ProposalFinalized,
+    
+    #[msg("Offering vaults must be (offering, vault) pairs of this business in index order")]
+    InvalidOfferingVault,
+    
+    #[msg("Pricing mode parameters are invalid")]
+    InvalidPricingMode,
+    
+    #[msg("Auction has not started yet")]
+    AuctionNotStarted,
+    
+    #[msg("A share of the purchase is priced above the buyer's maximum price per share")]
+    PriceAboveMax,
+    
+    #[msg("Subscription parameters are invalid")]
+    InvalidSubscription,
+    
+    #[msg("Subscription window is closed")]
+    SubscriptionClosed,
+    
+    #[msg("Subscription window is still open")]
+    SubscriptionOpen,
+    
+    #[msg("Subscription has not been settled yet")]
+    SubscriptionNotSettled,
+    
+    #[msg("Subscription has already been settled")]
+    SubscriptionSettled,
+    
+    #[msg("Allocation has already been claimed")]
+    AllocationClaimed,
+    
+    #[msg("Some allocations have not been claimed or refunded yet")]
+    AllocationsPending,
+    
+    #[msg("Allocations can still be claimed")]
+    ClaimPeriodOpen,
+    
+    #[msg("Account is not a legacy offering of this business")]
+    InvalidLegacyOffering,
+    
+    #[msg("Funding round parameters are invalid")]
+    InvalidRound,
+    
+    #[msg("Funding round is not open")]
+    RoundNotOpen,
+    
+    #[msg("Buyback parameters are invalid")]
+    InvalidBuyback,
+    
+    #[msg("Buyback has expired")]
+    BuybackExpired,
+    
+    #[msg("Buyback is still open to sellers")]
+    BuybackActive,
+    
+    #[msg("Treasury is program-controlled and cannot be changed")]
+    TreasuryLocked,
+    
+    #[msg("Treasury configuration is invalid")]
+    InvalidTreasuryConfig,
+    
+    #[msg("Withdrawal request is invalid")]
+    InvalidWithdrawal,
+    
+    #[msg("Withdrawal delay has not passed yet")]
+    WithdrawalLocked,
+    
+    #[msg("Withdrawal exceeds the limit for this period")]
+    WithdrawalLimitExceeded,
+    
+    #[msg("Treasury does not hold enough lamports")]
+    InsufficientTreasuryFunds,
+    
+    #[msg("Milestone parameters are invalid")]
+    InvalidMilestone,
+    
+    #[msg("Milestone is not pending")]
+    MilestoneNotPending,
+    
+    #[msg("Milestone deadline has passed")]
+    MilestoneExpired,
+    
+    #[msg("Milestone can still be approved")]
+    MilestoneStillOpen,
+    
+    #[msg("Signer cannot approve this milestone")]
+    UnauthorizedApprover,
+    
+    #[msg("Milestone oracle can't be the business owner or its treasury")]
+    InvalidOracle,
+    
+    #[msg("Milestone escrow has failed and is refunding")]
+    MilestoneEscrowFailed,
+    
+    #[msg("Milestone escrow has not failed")]
+    MilestoneEscrowActive,
+    
+    #[msg("Nothing to refund")]
+    NothingToRefund,
+    
+    #[msg("Milestone refunds can still be claimed")]
+    RefundPeriodOpen,
+    
+    #[msg("Milestone refund period has ended")]
+    RefundPeriodOver,
+    
+    #[msg("Revenue share parameters are invalid")]
+    InvalidRevenueShare,
+    
+    #[msg("Signer cannot deposit revenue for this business")]
+    UnauthorizedDepositor,
+    
+    #[msg("No shares are staked")]
+    NoStakedShares,
+    
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+    
+    #[msg("Revenue share account must be provided")]
+    RevenueShareRequired,
+    
+    #[msg("Staked shares are locked by an open vote")]
+    StakeLocked,
+    
+    #[msg("Signer is not the admin and lacks the required role")]
+    UnauthorizedRole,
+    
+    #[msg("Verification status change is not allowed")]
+    InvalidVerificationTransition,
+    
+    #[msg("Business must be verified to be listed")]
+    BusinessNotVerified,
+    
+    #[msg("Business is suspended")]
+    BusinessSuspended,
+    
+    #[msg("Business is frozen")]
+    BusinessFrozen,
+    
+    #[msg("Business is not frozen")]
+    BusinessNotFrozen,
+    
+    #[msg("Business name is already taken")]
+    BusinessNameTaken,
+    
+    #[msg("Business name contains control or invisible characters")]
+    InvalidBusinessName,
+    
+    #[msg("Business name letters and digits must be Latin (accents allowed)")]
+    UnsupportedBusinessName,
+    
+    #[msg("Account is already at the current layout version")]
+    AccountAlreadyMigrated,
+    
+    #[msg("Registry page is missing or does not hold this business")]
+    InvalidRegistryPage,
+    
+    #[msg("Registry page is full")]
+    RegistryPageFull,
+    
+    #[msg("Region must be a lowercase geohash")]
+    InvalidRegion,
+    
+    #[msg("Search index page is missing, does not match or does not hold this business")]
+    InvalidSearchIndexPage,
+    
+    #[msg("Report URI is empty, too long or contains invalid characters")]
+    InvalidReport,
+    
+    #[msg("Report period must be a YYYYMM month, not in the future, after the last published period")]
+    InvalidReportPeriod,
+    
+    #[msg("Business reporting is not overdue")]
+    ReportNotOverdue,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiered(tiers: &[(u64, u64)]) -> PricingMode {
+        let mut table = [PricingTier::default(); MAX_PRICING_TIERS];
+        for (slot, &(shares_in_tier, price_per_share_lamports)) in table.iter_mut().zip(tiers) {
+            *slot = PricingTier { shares_in_tier, price_per_share_lamports };
+        }
+        PricingMode::Tiered { tiers: table, tier_count: tiers.len() as u8 }
+    }
+
+    fn quote(mode: &PricingMode, shares_sold: u64, amount: u64) -> Quote {
+        mode.quote(0, 300, shares_sold, amount).unwrap()
+    }
+
+    #[test]
+    fn fixed_price_quotes_every_share_alike() {
+        let quote = quote(&PricingMode::Fixed, 1_000, 7);
+        assert_eq!(quote, Quote { total_lamports: 2_100, max_price_per_share: 300 });
+        assert!(PricingMode::Fixed.quote(0, u64::MAX, 0, 2).is_err());
+    }
+
+    const DUTCH: PricingMode = PricingMode::DutchAuction {
+        start_price: 10_000,
+        floor_price: 1_000,
+        start_ts: 100,
+        end_ts: 190,
+    };
+
+    #[test]
+    fn dutch_auction_decays_linearly() {
+        let at = |now| DUTCH.quote(now, 300, 0, 2).unwrap();
+        assert_eq!(at(100), Quote { total_lamports: 20_000, max_price_per_share: 10_000 });
+        assert_eq!(at(130), Quote { total_lamports: 14_000, max_price_per_share: 7_000 });
+        assert_eq!(at(145), Quote { total_lamports: 11_000, max_price_per_share: 5_500 });
+        assert_eq!(at(189), Quote { total_lamports: 2_200, max_price_per_share: 1_100 });
+    }
+
+    #[test]
+    fn dutch_auction_stops_at_the_floor() {
+        for now in [190, 191, i64::MAX] {
+            assert_eq!(
+                DUTCH.quote(now, 300, 0, 3).unwrap(),
+                Quote { total_lamports: 3_000, max_price_per_share: 1_000 }
+            );
+        }
+    }
+
+    #[test]
+    fn dutch_auction_rejects_purchases_before_the_start() {
+        assert!(DUTCH.quote(99, 300, 0, 1).is_err());
+        assert!(DUTCH.quote(i64::MIN, 300, 0, 1).is_err());
+    }
+
+    #[test]
+    fn dutch_auction_validation() {
+        assert!(DUTCH.validate().is_ok());
+        let dutch = |start_price, floor_price, start_ts, end_ts| PricingMode::DutchAuction {
+            start_price,
+            floor_price,
+            start_ts,
+            end_ts,
+        };
+        assert!(dutch(1_000, 1_000, 100, 190).validate().is_err());
+        assert!(dutch(1_000, 0, 100, 190).validate().is_err());
+        assert!(dutch(10_000, 1_000, 190, 190).validate().is_err());
+    }
+
+    #[test]
+    fn tiered_purchase_within_a_tier() {
+        let mode = tiered(&[(10, 100), (10, 200)]);
+        assert_eq!(quote(&mode, 0, 4), Quote { total_lamports: 400, max_price_per_share: 100 });
+        assert_eq!(quote(&mode, 12, 3), Quote { total_lamports: 600, max_price_per_share: 200 });
+    }
+
+    #[test]
+    fn tiered_purchase_crossing_a_boundary_is_split() {
+        let mode = tiered(&[(10, 100), (10, 200)]);
+
+        // 2 shares left in the first tier, 3 in the second
+        assert_eq!(quote(&mode, 8, 5), Quote { total_lamports: 2 * 100 + 3 * 200, max_price_per_share: 200 });
+
+        // Every tier plus shares at the fixed price
+        assert_eq!(
+            quote(&mode, 0, 25),
+            Quote { total_lamports: 10 * 100 + 10 * 200 + 5 * 300, max_price_per_share: 300 }
+        );
+    }
+
+    #[test]
+    fn tiered_purchase_at_an_exact_boundary() {
+        let mode = tiered(&[(10, 100), (10, 200)]);
+
+        // Filling the first tier exactly doesn't touch the second
+        assert_eq!(quote(&mode, 0, 10), Quote { total_lamports: 1_000, max_price_per_share: 100 });
+
+        // Starting exactly at the boundary is priced entirely in the second tier
+        assert_eq!(quote(&mode, 10, 10), Quote { total_lamports: 2_000, max_price_per_share: 200 });
+    }
+
+    #[test]
+    fn tiered_purchase_after_the_last_tier_uses_the_fixed_price() {
+        let mode = tiered(&[(10, 100), (10, 200)]);
+        assert_eq!(quote(&mode, 20, 4), Quote { total_lamports: 1_200, max_price_per_share: 300 });
+        assert_eq!(quote(&mode, 19, 2), Quote { total_lamports: 500, max_price_per_share: 300 });
+    }
+
+    #[test]
+    fn version_2_offerings_upgrade_to_open_ended_rounds() {
+        let business = Pubkey::new_unique();
+        let share_mint = Pubkey::new_unique();
+        let mut data = Offering::DISCRIMINATOR.to_vec();
+        data.push(2);
+        data.extend_from_slice(business.as_ref());
+        data.extend_from_slice(share_mint.as_ref());
+        data.extend_from_slice(&7u32.to_le_bytes());
+        for value in [1_000u64, 500, 200] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(&[1, 254]);
+        assert_eq!(data.len(), OFFERING_V2_SPACE);
+
+        let v2 = OfferingV2::deserialize(&mut &data[8..]).unwrap();
+        assert_eq!(v2.version, 2);
+        let offering = v2.upgrade().unwrap();
+        assert_eq!(offering.business, business);
+        assert_eq!(offering.share_mint, share_mint);
+        assert_eq!(offering.index, 7);
+        assert_eq!(offering.round_name, "Round 7");
+        assert_eq!(offering.price_per_share_lamports, 1_000);
+        assert_eq!((offering.total_shares, offering.remaining_shares), (500, 200));
+        assert_eq!(offering.raised_lamports, 300 * 1_000);
+        assert!(offering.is_active && offering.is_open(i64::MAX));
+        assert_eq!(offering.bump, 254);
+
+        // The rebuilt account round-trips in the current layout
+        let mut stored = Vec::new();
+        offering.try_serialize(&mut stored).unwrap();
+        assert!(stored.len() <= 8 + Offering::INIT_SPACE);
+        let loaded = Offering::try_deserialize(&mut &stored[..]).unwrap();
+        assert_eq!(loaded.version, OFFERING_VERSION);
+        assert_eq!(loaded.round_name, "Round 7");
+    }
+
+    /// Zero-extends `legacy` to `space` bytes, as migration::grow does, loads it with
+    /// migration::load, applies `stamp` and stores it back
+    /// Returns the account as loaded before stamping and as read back after storing
+    fn migrate_in_place<T: AccountSerialize + AccountDeserialize>(
+        legacy: &[u8],
+        space: usize,
+        stamp: impl FnOnce(&mut T),
+    ) -> (T, T) {
+        assert!(legacy.len() < space);
+        let mut data = legacy.to_vec();
+        data.resize(space, 0);
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+
+        let loaded: T = migration::load(&info).unwrap();
+        let mut upgraded: T = migration::load(&info).unwrap();
+        stamp(&mut upgraded);
+        migration::store(&upgraded, &info).unwrap();
+        (loaded, migration::load(&info).unwrap())
+    }
+
+    #[test]
+    fn pre_version_configs_migrate_in_place() {
+        let admin = Pubkey::new_unique();
+        let payment_mint = Pubkey::new_unique();
+        let mut data = Config::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(admin.as_ref());
+        data.extend_from_slice(payment_mint.as_ref());
+        data.push(253);
+
+        let (loaded, migrated) = migrate_in_place::<Config>(&data, 8 + Config::INIT_SPACE, |config| {
+            config.version = CONFIG_VERSION;
+        });
+        assert_eq!(loaded.version, 0);
+        assert_eq!((migrated.admin, migrated.payment_mint, migrated.bump), (admin, payment_mint, 253));
+        assert_eq!(migrated.version, CONFIG_VERSION);
+        assert_eq!(migrated.reserved, [0; 64]);
+    }
+
+    #[test]
+    fn pre_version_businesses_migrate_in_place() {
+        let owner = Pubkey::new_unique();
+        let share_mint = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let mut data = Business::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(owner.as_ref());
+        data.extend_from_slice(&4u32.to_le_bytes());
+        data.extend_from_slice(b"Cafe");
+        data.extend_from_slice(share_mint.as_ref());
+        for value in [1_000u64, 50] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(treasury.as_ref());
+        data.extend_from_slice(&[1, 252]);
+        // Businesses registered before the 100-byte name budget were allocated 176 bytes
+        data.resize(176, 0);
+
+        let (loaded, migrated) = migrate_in_place::<Business>(&data, 8 + Business::INIT_SPACE, |business| {
+            business.upgrade(1_700_000_000);
+        });
+        assert_eq!(loaded.version, 0);
+        assert_eq!((migrated.owner, migrated.name.as_str()), (owner, "Cafe"));
+        assert_eq!((migrated.share_mint, migrated.treasury), (share_mint, treasury));
+        assert_eq!((migrated.total_shares, migrated.price_per_share_lamports), (1_000, 50));
+        assert!(migrated.is_listed);
+        assert_eq!((migrated.bump, migrated.version), (252, BUSINESS_VERSION));
+
+        // Fields added since read as "not registered, not indexed"
+        assert!(!migrated.in_registry && !migrated.in_search_index);
+        assert_eq!(migrated.category, BusinessCategory::Uncategorized);
+        assert_eq!(migrated.region, [0; REGION_CODE_LENGTH]);
+        assert_eq!(migrated.reserved, [0; 30]);
+
+        // The listed business's reporting deadline starts at the migration
+        assert_eq!(migrated.listed_at, 1_700_000_000);
+    }
+
+    #[test]
+    fn versioned_businesses_migrate_to_the_current_layout() {
+        let owner = Pubkey::new_unique();
+        let business = Business {
+            owner,
+            name: "Cafe".to_string(),
+            share_mint: Pubkey::default(),
+            total_shares: 0,
+            price_per_share_lamports: 0,
+            treasury: owner,
+            is_listed: false,
+            bump: 255,
+            version: 1,
+            in_registry: false,
+            registry_page: 0,
+            registry_slot: 0,
+            category: BusinessCategory::Uncategorized,
+            region: [0; REGION_CODE_LENGTH],
+            in_search_index: false,
+            category_index_page: 0,
+            category_index_slot: 0,
+            region_index_page: 0,
+            region_index_slot: 0,
+            listed_at: 0,
+            reserved: [0; 30],
+        };
+        let mut data = Vec::new();
+        business.try_serialize(&mut data).unwrap();
+
+        let (loaded, migrated) = migrate_in_place::<Business>(&data, 8 + Business::INIT_SPACE, |business| {
+            business.upgrade(1_700_000_000);
+        });
+        assert!(loaded.version < BUSINESS_VERSION);
+        assert_eq!(migrated.version, BUSINESS_VERSION);
+        assert_eq!((migrated.owner, migrated.name.as_str()), (owner, "Cafe"));
+        // Never listed: reporting isn't due yet
+        assert_eq!(migrated.listed_at, 0);
+    }
+
+    #[test]
+    fn pre_padding_offerings_migrate_in_place() {
+        let business = Pubkey::new_unique();
+        let share_mint = Pubkey::new_unique();
+        let mut data = Offering::DISCRIMINATOR.to_vec();
+        data.push(2);
+        data.extend_from_slice(business.as_ref());
+        data.extend_from_slice(share_mint.as_ref());
+        data.extend_from_slice(&3u32.to_le_bytes());
+        data.extend_from_slice(&4u32.to_le_bytes());
+        data.extend_from_slice(b"Seed");
+        for value in [1_000u64, 500, 200] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        for value in [10i64, 20] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(&300_000u64.to_le_bytes());
+        data.extend_from_slice(&[1, 251]);
+        // Version 2 rounds were allocated with the full 32-byte round name budget
+        data.resize(8 + 1 + 32 + 32 + 4 + (4 + MAX_ROUND_NAME_LENGTH) + 6 * 8 + 1 + 1, 0);
+        assert_ne!(data.len(), OFFERING_V2_SPACE);
+
+        let (loaded, migrated) = migrate_in_place::<Offering>(&data, 8 + Offering::INIT_SPACE, |offering| {
+            offering.version = OFFERING_VERSION;
+        });
+        assert_eq!(loaded.version, 2);
+        assert_eq!((migrated.business, migrated.share_mint, migrated.index), (business, share_mint, 3));
+        assert_eq!(migrated.round_name, "Seed");
+        assert_eq!((migrated.price_per_share_lamports, migrated.total_shares, migrated.remaining_shares), (1_000, 500, 200));
+        assert_eq!((migrated.start_ts, migrated.end_ts, migrated.raised_lamports), (10, 20, 300_000));
+        assert!(migrated.is_active);
+        assert_eq!((migrated.bump, migrated.version), (251, OFFERING_VERSION));
+        assert_eq!(migrated.reserved, [0; 64]);
+    }
+
+    fn proposal(quorum_votes: u64, approval_threshold_bps: u16, yes_votes: u64, no_votes: u64) -> Proposal {
+        Proposal {
+            business: Pubkey::default(),
+            proposer: Pubkey::default(),
+            id: 0,
+            title: String::new(),
+            description_uri: String::new(),
+            voting_start: 0,
+            voting_end: 0,
+            quorum_votes,
+            approval_threshold_bps,
+            yes_votes,
+            no_votes,
+            status: ProposalStatus::Active,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn proposals_need_quorum() {
+        assert_eq!(proposal(100, 5_001, 99, 0).tally().unwrap(), ProposalStatus::Defeated);
+        assert_eq!(proposal(100, 5_001, 60, 40).tally().unwrap(), ProposalStatus::Succeeded);
+        assert_eq!(proposal(0, 5_001, 0, 0).tally().unwrap(), ProposalStatus::Defeated);
+    }
+
+    #[test]
+    fn proposals_need_the_approval_threshold() {
+        // Strict majority: a tie is defeated, one more yes vote passes
+        assert_eq!(proposal(0, 5_001, 50, 50).tally().unwrap(), ProposalStatus::Defeated);
+        assert_eq!(proposal(0, 5_001, 51, 50).tally().unwrap(), ProposalStatus::Succeeded);
+
+        // Exactly at the threshold passes
+        assert_eq!(proposal(0, 6_000, 60, 40).tally().unwrap(), ProposalStatus::Succeeded);
+        assert_eq!(proposal(0, 6_000, 59, 41).tally().unwrap(), ProposalStatus::Defeated);
+    }
+
+    #[test]
+    fn unanimous_threshold_is_reachable() {
+        assert_eq!(proposal(10, 10_000, 10, 0).tally().unwrap(), ProposalStatus::Succeeded);
+        assert_eq!(proposal(10, 10_000, 10, 1).tally().unwrap(), ProposalStatus::Defeated);
+    }
+
+    fn subscription(shares_offered: u64, shares_demanded: u64) -> Subscription {
+        Subscription {
+            business: Pubkey::default(),
+            share_mint: Pubkey::default(),
+            price_per_share_lamports: 100,
+            shares_offered,
+            start_ts: 0,
+            end_ts: 1_000,
+            total_committed_lamports: shares_demanded * 100,
+            shares_demanded,
+            commitment_count: 0,
+            claimed_count: 0,
+            settled: true,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn undersubscribed_commitments_get_every_share_requested() {
+        let sale = subscription(100, 60);
+        assert_eq!(sale.allocation_for(4_000).unwrap(), 40);
+        // Lamports short of a whole share are refunded, not allocated
+        assert_eq!(sale.allocation_for(2_050).unwrap(), 20);
+    }
+
+    #[test]
+    fn oversubscribed_commitments_are_allocated_pro_rata() {
+        // 300 shares demanded for 100: each commitment gets a third, rounded down
+        let sale = subscription(100, 300);
+        assert_eq!(sale.allocation_for(15_000).unwrap(), 50);
+        assert_eq!(sale.allocation_for(10_000).unwrap(), 33);
+        assert_eq!(sale.allocation_for(5_000).unwrap(), 16);
+
+        // Rounding never allocates more than offered
+        let total: u64 = [15_000, 10_000, 5_000]
+            .iter()
+            .map(|&lamports| sale.allocation_for(lamports).unwrap())
+            .sum();
+        assert!(total <= 100);
+    }
+
+    #[test]
+    fn unclaimed_commitments_are_refundable_after_the_claim_period() {
+        let sale = subscription(100, 60);
+        assert_eq!(sale.claim_deadline().unwrap(), 1_000 + SUBSCRIPTION_CLAIM_PERIOD_SECONDS);
+
+        let mut sale = sale;
+        sale.end_ts = i64::MAX;
+        assert!(sale.claim_deadline().is_err());
+    }
+
+    fn vesting(total_amount: u64, revoked: bool) -> VestingSchedule {
+        VestingSchedule {
+            business: Pubkey::default(),
+            beneficiary: Pubkey::default(),
+            share_mint: Pubkey::default(),
+            total_amount,
+            released_amount: 0,
+            start_ts: 100,
+            cliff_ts: 125,
+            end_ts: 200,
+            revocable: true,
+            revoked,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn vesting_unlocks_linearly_after_the_cliff() {
+        let schedule = vesting(1_000, false);
+        assert_eq!(schedule.vested_amount(0).unwrap(), 0);
+        assert_eq!(schedule.vested_amount(124).unwrap(), 0);
+        // At the cliff everything accrued since the start unlocks at once
+        assert_eq!(schedule.vested_amount(125).unwrap(), 250);
+        assert_eq!(schedule.vested_amount(133).unwrap(), 330);
+        assert_eq!(schedule.vested_amount(200).unwrap(), 1_000);
+        assert_eq!(schedule.vested_amount(i64::MAX).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn revoked_vesting_keeps_only_the_vested_amount() {
+        // Revocation lowers total_amount to what had vested; all of it stays releasable
+        let schedule = vesting(250, true);
+        assert_eq!(schedule.vested_amount(0).unwrap(), 250);
+        assert_eq!(schedule.vested_amount(150).unwrap(), 250);
+    }
+
+    #[test]
+    fn failed_escrows_can_be_swept_after_the_refund_period() {
+        let escrow = MilestoneEscrow {
+            business: Pubkey::default(),
+            release_to: Pubkey::default(),
+            approval: MilestoneApproval::Admin,
+            milestone_count: 1,
+            released_lamports: 0,
+            contributed_lamports: 14_000,
+            failed: true,
+            failed_at: 500,
+            refund_pool: 14_000,
+            refund_basis: 14_000,
+            bump: 0,
+        };
+        assert_eq!(escrow.refund_deadline().unwrap(), 500 + MILESTONE_REFUND_PERIOD_SECONDS);
+    }
+
+    fn treasury(max_withdrawal_per_period: u64, period_seconds: i64) -> BusinessTreasury {
+        BusinessTreasury {
+            business: Pubkey::default(),
+            withdrawal_delay_seconds: MIN_WITHDRAWAL_DELAY_SECONDS,
+            max_withdrawal_per_period,
+            period_seconds,
+            period_start: 1_000,
+            withdrawn_in_period: 0,
+            total_withdrawn: 0,
+            withdrawal_count: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn treasury_rules_always_keep_a_delay() {
+        assert!(BusinessTreasury::validate_rules(0, 0, 0).is_err());
+        assert!(BusinessTreasury::validate_rules(MIN_WITHDRAWAL_DELAY_SECONDS - 1, 5_000, 3_600).is_err());
+        assert!(BusinessTreasury::validate_rules(MIN_WITHDRAWAL_DELAY_SECONDS, 5_000, 0).is_err());
+        assert!(BusinessTreasury::validate_rules(MIN_WITHDRAWAL_DELAY_SECONDS, 5_000, 3_600).is_ok());
+        // No rate limit: the delay alone protects investors
+        assert!(BusinessTreasury::validate_rules(MIN_WITHDRAWAL_DELAY_SECONDS, 0, 0).is_ok());
+    }
+
+    #[test]
+    fn withdrawals_over_the_period_limit_are_rejected() {
+        let mut treasury = treasury(5_000, 3_600);
+        treasury.record_withdrawal(1_000, 4_000).unwrap();
+        assert!(treasury.record_withdrawal(4_599, 1_001).is_err());
+        assert_eq!(treasury.withdrawn_in_period, 4_000);
+
+        treasury.record_withdrawal(4_599, 1_000).unwrap();
+        assert_eq!(treasury.withdrawn_in_period, 5_000);
+        assert!(treasury.record_withdrawal(4_599, 1).is_err());
+        assert_eq!(treasury.total_withdrawn, 5_000);
+    }
+
+    #[test]
+    fn treasury_limit_resets_when_the_period_rolls_over() {
+        let mut treasury = treasury(5_000, 3_600);
+        treasury.record_withdrawal(1_000, 5_000).unwrap();
+
+        // The next window starts at the first withdrawal after the period has elapsed
+        treasury.record_withdrawal(4_600, 3_000).unwrap();
+        assert_eq!(treasury.period_start, 4_600);
+        assert_eq!(treasury.withdrawn_in_period, 3_000);
+        assert!(treasury.record_withdrawal(8_199, 2_001).is_err());
+
+        treasury.record_withdrawal(8_200, 5_000).unwrap();
+        assert_eq!(treasury.period_start, 8_200);
+        assert_eq!(treasury.total_withdrawn, 13_000);
+    }
+
+    #[test]
+    fn unlimited_treasuries_only_track_the_total() {
+        let mut treasury = treasury(0, 0);
+        treasury.record_withdrawal(1_000, u64::MAX / 2).unwrap();
+        treasury.record_withdrawal(1_000, u64::MAX / 2).unwrap();
+        assert_eq!(treasury.withdrawn_in_period, 0);
+        assert!(treasury.record_withdrawal(1_000, 2).is_err());
+    }
+
+    fn legacy_offering(remaining_shares: u64, is_active: bool) -> LegacyOffering {
+        LegacyOffering {
+            business: Pubkey::new_unique(),
+            share_mint: Pubkey::new_unique(),
+            payment_mint: Pubkey::default(),
+            price_per_share: 1_000,
+            remaining_shares,
+            is_active,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn legacy_holders_can_swap_every_circulating_token() {
+        // 100 legacy tokens minted, 40 still unsold in the legacy vault
+        let (offered, mut reserved) = legacy_offering(40, true).migration_shares(100, 40).unwrap();
+        assert_eq!((offered, reserved), (40, 60));
+
+        for swap in [25, 35] {
+            LegacyOffering::check_swap(reserved, swap).unwrap();
+            reserved -= swap;
+        }
+        assert!(LegacyOffering::check_swap(reserved, 1).is_err());
+        assert!(LegacyOffering::check_swap(10, 0).is_err());
+        assert!(LegacyOffering::check_swap(10, 11).is_err());
+
+        // A closed legacy offering offers nothing, but its holders still swap
+        assert_eq!(legacy_offering(40, false).migration_shares(100, 40).unwrap(), (0, 60));
+        assert!(legacy_offering(40, true).migration_shares(40, 41).is_err());
+    }
+
+    #[test]
+    fn legacy_offerings_are_identified_by_owner_size_and_discriminator() {
+        let legacy = legacy_offering(40, true);
+        let mut data = Offering::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(legacy.business.as_ref());
+        data.extend_from_slice(legacy.share_mint.as_ref());
+        data.extend_from_slice(legacy.payment_mint.as_ref());
+        data.extend_from_slice(&legacy.price_per_share.to_le_bytes());
+        data.extend_from_slice(&legacy.remaining_shares.to_le_bytes());
+        data.extend_from_slice(&[1, legacy.bump]);
+        assert_eq!(data.len(), LEGACY_OFFERING_SPACE);
+
+        let load = |owner: &Pubkey, data: &mut [u8]| {
+            let key = Pubkey::new_unique();
+            let mut lamports = 0;
+            let info = AccountInfo::new(&key, false, false, &mut lamports, data, owner, false, 0);
+            LegacyOffering::load(&info)
+        };
+
+        let loaded = load(&crate::ID, &mut data.clone()).unwrap();
+        assert_eq!(loaded.business, legacy.business);
+        assert_eq!(loaded.remaining_shares, 40);
+        assert!(loaded.is_active);
+
+        assert!(load(&Pubkey::new_unique(), &mut data.clone()).is_err());
+        let mut current = data.clone();
+        current.push(0);
+        assert!(load(&crate::ID, &mut current).is_err());
+        let mut other = data.clone();
+        other[..8].copy_from_slice(Business::DISCRIMINATOR);
+        assert!(load(&crate::ID, &mut other).is_err());
+    }
+
+    fn stake_position(amount: u64, acc: u128) -> StakePosition {
+        let mut position = StakePosition {
+            business: Pubkey::default(),
+            owner: Pubkey::default(),
+            amount,
+            reward_debt: 0,
+            pending_revenue: 0,
+            locked_until: 0,
+            staked_at: 0,
+            checkpoint_amount: 0,
+            checkpoint_at: 0,
+            bump: 0,
+        };
+        position.reward_debt = position.accrued(acc).unwrap();
+        position
+    }
+
+    #[test]
+    fn staked_votes_count_only_stake_held_before_voting_opened() {
+        let mut position = stake_position(0, 0);
+        position.record_stake(100, 10).unwrap();
+        assert_eq!(position.voting_weight(11), 100);
+        // Staked in the same second the proposal opened: not before it
+        assert_eq!(position.voting_weight(10), 0);
+
+        // Proposal opens at 20; stake added at 25 only counts on later proposals
+        position.record_stake(50, 25).unwrap();
+        assert_eq!(position.amount, 150);
+        assert_eq!(position.voting_weight(20), 100);
+        assert_eq!(position.voting_weight(26), 150);
+        // A second increase in the same second keeps the checkpoint
+        position.record_stake(10, 25).unwrap();
+        assert_eq!(position.voting_weight(20), 100);
+
+        // Another increase moves the checkpoint past 20: nothing is proven for that proposal
+        position.record_stake(40, 30).unwrap();
+        assert_eq!(position.voting_weight(20), 0);
+        assert_eq!(position.voting_weight(27), 160);
+        assert_eq!(position.voting_weight(31), 200);
+
+        // Unstaking lowers every weight to what is still staked
+        position.amount = 120;
+        assert_eq!(position.voting_weight(27), 120);
+    }
+
+    /// Accumulator increment of a deposit, as in deposit_revenue
+    fn deposit(acc: u128, amount: u64, total_staked: u64) -> u128 {
+        acc + amount as u128 * REWARD_PRECISION / total_staked as u128
+    }
+
+    #[test]
+    fn revenue_accrues_pro_rata_to_stake() {
+        let mut alice = stake_position(30, 0);
+        let mut bob = stake_position(10, 0);
+        let acc = deposit(0, 4_000, 40);
+
+        alice.settle(acc).unwrap();
+        bob.settle(acc).unwrap();
+        assert_eq!(alice.pending_revenue, 3_000);
+        assert_eq!(bob.pending_revenue, 1_000);
+    }
+
+    #[test]
+    fn late_stakers_only_accrue_later_revenue() {
+        let mut alice = stake_position(30, 0);
+        let acc = deposit(0, 3_000, 30);
+
+        // Carol stakes after the first deposit: her reward debt covers it
+        let mut carol = stake_position(30, acc);
+        let acc = deposit(acc, 6_000, 60);
+
+        alice.settle(acc).unwrap();
+        carol.settle(acc).unwrap();
+        assert_eq!(alice.pending_revenue, 6_000);
+        assert_eq!(carol.pending_revenue, 3_000);
+    }
+
+    #[test]
+    fn stake_changes_settle_before_resetting_the_debt() {
+        let mut alice = stake_position(10, 0);
+        let acc = deposit(0, 1_000, 10);
+
+        // Unstaking half settles what the full stake earned, as in unstake_shares
+        alice.settle(acc).unwrap();
+        alice.amount -= 5;
+        alice.reward_debt = alice.accrued(acc).unwrap();
+        assert_eq!(alice.pending_revenue, 1_000);
+
+        // Settling again without a new deposit adds nothing
+        alice.settle(acc).unwrap();
+        assert_eq!(alice.pending_revenue, 1_000);
+
+        let acc = deposit(acc, 500, 5);
+        alice.settle(acc).unwrap();
+        assert_eq!(alice.pending_revenue, 1_500);
+    }
+
+    #[test]
+    fn tiered_validation() {
+        assert!(tiered(&[(10, 100)]).validate().is_ok());
+        assert!(tiered(&[]).validate().is_err());
+        assert!(tiered(&[(0, 100)]).validate().is_err());
+        assert!(tiered(&[(10, 0)]).validate().is_err());
+    }
+
+    fn reporting(report_count: u32, last_published_at: i64) -> ReportingStatus {
+        ReportingStatus {
+            business: Pubkey::default(),
+            last_period: 0,
+            report_count,
+            last_published_at,
+            overdue: false,
+            flagged_at: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn report_periods_are_months_up_to_the_current_one() {
+        // 2024-03-15T12:00:00Z
+        let now = 1_710_504_000;
+        assert_eq!(Report::period_at(now), 202403);
+        assert_eq!(Report::period_at(0), 197001);
+        // 2024-02-29T23:59:59Z and 2024-03-01T00:00:00Z
+        assert_eq!(Report::period_at(1_709_251_199), 202402);
+        assert_eq!(Report::period_at(1_709_251_200), 202403);
+        // 2099-12-31T23:59:59Z
+        assert_eq!(Report::period_at(4_102_444_799), 209912);
+
+        for period in [MIN_REPORT_PERIOD, 202312, 202402, 202403] {
+            assert!(Report::is_valid_period(period, now), "{period}");
+        }
+        // Invalid months, before MIN_REPORT_PERIOD, future months and other formats
+        for period in [202400, 202413, 199912, 202404, 209912, 2024, 20240301] {
+            assert!(!Report::is_valid_period(period, now), "{period}");
+        }
+    }
+
+    #[test]
+    fn reporting_is_overdue_from_the_first_listing_then_from_the_last_report() {
+        let listed_at = 1_000_000;
+        let never_reported = reporting(0, 0);
+        assert!(!never_reported.is_overdue(listed_at, listed_at + REPORT_OVERDUE_AFTER_SECONDS));
+        assert!(never_reported.is_overdue(listed_at, listed_at + REPORT_OVERDUE_AFTER_SECONDS + 1));
+
+        let reported = reporting(1, listed_at + 50);
+        assert!(!reported.is_overdue(listed_at, listed_at + REPORT_OVERDUE_AFTER_SECONDS + 1));
+        assert!(reported.is_overdue(listed_at, listed_at + 50 + REPORT_OVERDUE_AFTER_SECONDS + 1));
+    }
 }
@@ -0,0 +1,136 @@
+//! Merkle tree helpers
+//! Shared by cap table snapshot commitments and share airdrops
+//!
+//! Leaves and inner nodes are domain-separated (0x00 / 0x01 prefix) and
+//! sibling pairs are hashed in sorted order, so proofs don't need direction bits.
+
+use solana_sha256_hasher::hashv;
+
+/// Prefix applied to leaf hashes
+const LEAF_PREFIX: &[u8] = &[0x00];
+
+/// Prefix applied to inner node hashes
+const NODE_PREFIX: &[u8] = &[0x01];
+
+/// Hashes the fields of a leaf into a 32-byte leaf hash
+pub fn leaf_hash(fields: &[&[u8]]) -> [u8; 32] {
+    let mut parts = Vec::with_capacity(fields.len() + 1);
+    parts.push(LEAF_PREFIX);
+    parts.extend_from_slice(fields);
+    hashv(&parts).to_bytes()
+}
+
+/// Hashes two sibling nodes into their parent (order independent)
+pub fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+/// Verifies that `leaf` is included in the tree committed to by `root`
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |node, sibling| node_hash(&node, sibling));
+    computed == *root
+}
+
+/// Computes the root of a tree built from `leaves`
+/// An odd node at any level is promoted unchanged to the next level
+#[cfg(not(target_os = "solana"))]
+pub fn root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    if leaves.is_empty() {
+        return [0u8; 32];
+    }
+
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+/// Builds the inclusion proof for the leaf at `index`
+/// Returns None if the index is out of range
+#[cfg(not(target_os = "solana"))]
+pub fn proof(leaves: &[[u8; 32]], index: usize) -> Option<Vec<[u8; 32]>> {
+    if index >= leaves.len() {
+        return None;
+    }
+
+    let mut proof = Vec::new();
+    let mut level = leaves.to_vec();
+    let mut position = index;
+    while level.len() > 1 {
+        let sibling = position ^ 1;
+        if sibling < level.len() {
+            proof.push(level[sibling]);
+        }
+        level = next_level(&level);
+        position /= 2;
+    }
+    Some(proof)
+}
+
+#[cfg(not(target_os = "solana"))]
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [a, b] => node_hash(a, b),
+            [a] => *a,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: u8) -> Vec<[u8; 32]> {
+        (0..count).map(|i| leaf_hash(&[&[i]])).collect()
+    }
+
+    #[test]
+    fn root_of_empty_and_single_leaf() {
+        assert_eq!(root(&[]), [0u8; 32]);
+
+        let leaf = leaf_hash(&[b"only"]);
+        assert_eq!(root(&[leaf]), leaf);
+        assert_eq!(proof(&[leaf], 0), Some(vec![]));
+        assert!(verify(&[], &leaf, leaf));
+    }
+
+    #[test]
+    fn every_leaf_proves_for_odd_and_even_trees() {
+        for count in 2..=9 {
+            let leaves = leaves(count);
+            let root = root(&leaves);
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = proof(&leaves, index).unwrap();
+                assert!(verify(&proof, &root, *leaf), "leaf {} of {}", index, count);
+            }
+            assert!(proof(&leaves, leaves.len()).is_none());
+        }
+    }
+
+    #[test]
+    fn rejects_foreign_leaf_and_tampered_proof() {
+        let leaves = leaves(5);
+        let root = root(&leaves);
+        let mut proof = proof(&leaves, 2).unwrap();
+
+        assert!(!verify(&proof, &root, leaf_hash(&[&[42]])));
+        proof[0][0] ^= 1;
+        assert!(!verify(&proof, &root, leaves[2]));
+    }
+
+    #[test]
+    fn leaves_and_nodes_are_domain_separated() {
+        let (a, b) = (leaf_hash(&[b"a"]), leaf_hash(&[b"b"]));
+        let parent = node_hash(&a, &b);
+
+        assert_eq!(parent, node_hash(&b, &a));
+        assert_ne!(parent, leaf_hash(&[&a, &b]));
+    }
+}
@@ -0,0 +1,57 @@
+//! In-place account migrations
+//! Used by migrate_business, migrate_config and migrate_offering
+//!
+//! Versioned accounts only grow by appending fields (taken from their reserved
+//! padding), so an old account zero-extended to the current size deserializes
+//! as the current layout with the new fields zeroed. Migrations grow the
+//! account, load it, stamp the current version and store it back.
+//! The one exception is version 2 offerings, rebuilt by OfferingV2::upgrade.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::LocalshareError;
+
+/// Grows `account` to `space` bytes (zero-filled), topping up rent from `payer`
+/// Does nothing if the account is already large enough
+pub fn grow<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    if account.data_len() >= space {
+        return Ok(());
+    }
+
+    let top_up = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    account.resize(space)?;
+    Ok(())
+}
+
+/// Loads a program-owned account (discriminator checked)
+pub fn load<T: AccountDeserialize>(account: &AccountInfo) -> Result<T> {
+    require_keys_eq!(*account.owner, crate::ID, LocalshareError::InvalidBusiness);
+    let data = account.try_borrow_data()?;
+    T::try_deserialize(&mut &data[..])
+}
+
+/// Writes back an account loaded with `load`
+pub fn store<T: AccountSerialize>(value: &T, account: &AccountInfo) -> Result<()> {
+    let mut data = account.try_borrow_mut_data()?;
+    value.try_serialize(&mut &mut data[..])
+}
@@ -0,0 +1,118 @@
+//! Business name slugs
+//! Used to reserve unique business names and route `/business/<slug>`
+//!
+//! A slug is the name lowercased and folded to ASCII, with every run of other
+//! characters collapsed into a single `-` (e.g. "Joe's Pizzà" → "joe-s-pizza").
+//! Slugs can be longer than a PDA seed, so NameRecord is seeded by their hash.
+//!
+//! Names that look the same must share a slug. Combining accents are dropped, so
+//! a decomposed "e" + U+0301 slugs like "é", and names with letters or digits that
+//! don't fold to ASCII (other scripts, homoglyphs such as Cyrillic "а") are
+//! rejected with `is_foldable` rather than slugged.
+
+use solana_sha256_hasher::hashv;
+
+/// Normalizes a business name into its slug
+/// Returns an empty string if the name has no letters or digits
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    let mut separator = false;
+    let mut buf = [0u8; 4];
+
+    for c in name.chars() {
+        if is_combining_mark(c) {
+            continue;
+        }
+        let folded = if c.is_ascii_alphanumeric() {
+            c.to_ascii_lowercase().encode_utf8(&mut buf)
+        } else if let Some(folded) = fold(c) {
+            folded
+        } else {
+            separator = true;
+            continue;
+        };
+
+        if separator && !slug.is_empty() {
+            slug.push('-');
+        }
+        separator = false;
+        slug.push_str(folded);
+    }
+    slug
+}
+
+/// Whether every letter and digit of `name` folds to ASCII
+pub fn is_foldable(name: &str) -> bool {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .all(|c| c.is_ascii_alphanumeric() || fold(c).is_some())
+}
+
+/// PDA seed of the NameRecord reserving `slug`
+pub fn seed(slug: &str) -> [u8; 32] {
+    hashv(&[slug.as_bytes()]).to_bytes()
+}
+
+/// Folds an accented Latin letter to lowercase ASCII
+fn fold(c: char) -> Option<&'static str> {
+    let folded = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' => "a",
+        'ç' | 'Ç' => "c",
+        'è' | 'é' | 'ê' | 'ë' | 'È' | 'É' | 'Ê' | 'Ë' => "e",
+        'ì' | 'í' | 'î' | 'ï' | 'Ì' | 'Í' | 'Î' | 'Ï' => "i",
+        'ñ' | 'Ñ' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' => "o",
+        'ù' | 'ú' | 'û' | 'ü' | 'Ù' | 'Ú' | 'Û' | 'Ü' => "u",
+        'ý' | 'ÿ' | 'Ý' => "y",
+        'ß' => "ss",
+        'æ' | 'Æ' => "ae",
+        'œ' | 'Œ' => "oe",
+        _ => return None,
+    };
+    Some(folded)
+}
+
+/// Combining diacritical marks (accents written after their letter)
+fn is_combining_mark(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}' | '\u{1AB0}'..='\u{1AFF}' | '\u{1DC0}'..='\u{1DFF}' | '\u{20D0}'..='\u{20FF}' | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_lowercases_folds_and_collapses_separators() {
+        assert_eq!(slugify("Joe's Pizzà"), "joe-s-pizza");
+        assert_eq!(slugify("  Café -- da   Esquina! "), "cafe-da-esquina");
+        assert_eq!(slugify("Straße Œuvre"), "strasse-oeuvre");
+        assert_eq!(slugify("Bar 42"), "bar-42");
+        assert_eq!(slugify("?!"), "");
+    }
+
+    #[test]
+    fn decomposed_accents_slug_like_precomposed_ones() {
+        assert_eq!(slugify("Re\u{301}mi"), "remi");
+        assert_eq!(slugify("Rémi"), "remi");
+        assert_eq!(slugify("Pizza\u{300}ria"), slugify("Pizzària"));
+        assert_eq!(slug_seed("Re\u{301}mi"), slug_seed("Rémi"));
+    }
+
+    #[test]
+    fn only_ascii_foldable_letters_are_accepted() {
+        for name in ["Joe's Pizzà", "Re\u{301}mi", "Bar 42", "Straße", "Ørsted & Co."] {
+            assert!(is_foldable(name), "{name:?}");
+        }
+        // Cyrillic "а" and "о" look like Latin ones; other scripts and non-ASCII digits
+        for name in ["C\u{430}fe", "Caf\u{435}", "\u{41C}\u{43E}\u{441}\u{43A}\u{432}\u{430}", "\u{6771}\u{4EAC}", "Bar \u{0664}\u{0662}"] {
+            assert!(!is_foldable(name), "{name:?}");
+        }
+    }
+
+    fn slug_seed(name: &str) -> [u8; 32] {
+        seed(&slugify(name))
+    }
+}
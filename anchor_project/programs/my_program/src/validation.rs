@@ -0,0 +1,131 @@
+//! Text validation
+//! Shared by every instruction that stores a user-supplied string
+//!
+//! Input is trimmed and must not contain control or invisible characters
+//! (zero-width, bidi overrides, soft hyphens), which could hide content or
+//! impersonate another business. Length is limited both in grapheme clusters,
+//! the characters a user sees, and in UTF-8 bytes, the space reserved in the
+//! account.
+//!
+//! Region codes are geohashes: lowercase base32 without "a", "i", "l" and "o".
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Length limits of a text field
+pub struct TextLimit {
+    /// Maximum number of characters (extended grapheme clusters)
+    pub max_chars: usize,
+    /// Maximum UTF-8 length, matching the field's account space
+    pub max_bytes: usize,
+    /// Whether an empty (or whitespace-only) value is rejected
+    pub required: bool,
+}
+
+/// Reason a text field was rejected
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextError {
+    /// Required field is empty after trimming
+    Empty,
+    /// Too many characters or bytes
+    TooLong,
+    /// Contains a control or invisible character
+    InvalidCharacter,
+}
+
+/// Trims `input` and checks it against `limit`
+/// Returns the trimmed text to store
+pub fn sanitize(input: &str, limit: &TextLimit) -> Result<String, TextError> {
+    let text = input.trim();
+
+    if text.is_empty() {
+        return if limit.required { Err(TextError::Empty) } else { Ok(String::new()) };
+    }
+    if text.chars().any(is_forbidden) {
+        return Err(TextError::InvalidCharacter);
+    }
+    if text.len() > limit.max_bytes || text.graphemes(true).count() > limit.max_chars {
+        return Err(TextError::TooLong);
+    }
+    Ok(text.to_string())
+}
+
+/// Whether `code` is a non-empty lowercase geohash
+pub fn is_geohash(code: &[u8]) -> bool {
+    !code.is_empty()
+        && code
+            .iter()
+            .all(|c| matches!(c, b'0'..=b'9' | b'b'..=b'h' | b'j' | b'k' | b'm' | b'n' | b'p'..=b'z'))
+}
+
+/// Control characters and characters that render as nothing
+fn is_forbidden(c: char) -> bool {
+    c.is_control()
+        || matches!(
+            c,
+            // soft hyphen, combining grapheme joiner, hangul fillers, mongolian vowel separator
+            '\u{00AD}' | '\u{034F}' | '\u{115F}' | '\u{1160}' | '\u{3164}' | '\u{FFA0}' | '\u{180E}'
+            // zero-width space and joiners, directional marks
+            | '\u{200B}'..='\u{200F}'
+            // line/paragraph separators, bidi embeddings and overrides
+            | '\u{2028}'..='\u{202E}'
+            // word joiner, invisible operators, bidi isolates, deprecated format characters
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{206F}'
+            // zero-width no-break space (BOM), interlinear annotations
+            | '\u{FEFF}'
+            | '\u{FFF9}'..='\u{FFFB}'
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMIT: TextLimit = TextLimit { max_chars: 4, max_bytes: 16, required: true };
+    const OPTIONAL: TextLimit = TextLimit { max_chars: 4, max_bytes: 16, required: false };
+
+    #[test]
+    fn sanitize_trims_and_rejects_empty_required_text() {
+        assert_eq!(sanitize("  Cafe \n", &LIMIT), Ok("Cafe".to_string()));
+        assert_eq!(sanitize(" \t ", &LIMIT), Err(TextError::Empty));
+        assert_eq!(sanitize(" \t ", &OPTIONAL), Ok(String::new()));
+    }
+
+    #[test]
+    fn sanitize_counts_grapheme_clusters() {
+        // "e" + combining acute accent: two scalar values, one character
+        assert_eq!(sanitize("Cafe\u{301}", &LIMIT), Ok("Cafe\u{301}".to_string()));
+        // Flags are two regional indicators rendered as one character
+        let wide = TextLimit { max_chars: 1, max_bytes: 32, required: true };
+        assert_eq!(sanitize("\u{1F1EB}\u{1F1F7}", &wide), Ok("\u{1F1EB}\u{1F1F7}".to_string()));
+        assert_eq!(sanitize("Cafes", &LIMIT), Err(TextError::TooLong));
+    }
+
+    #[test]
+    fn sanitize_limits_bytes_independently_of_characters() {
+        // Four 4-byte characters fill the 16 bytes; an accent adds bytes but no character
+        assert_eq!(sanitize("\u{1F600}\u{1F600}\u{1F600}\u{1F600}", &LIMIT), Ok("\u{1F600}".repeat(4)));
+        assert_eq!(sanitize("\u{1F600}\u{1F600}\u{1F600}\u{1F600}\u{301}", &LIMIT), Err(TextError::TooLong));
+    }
+
+    #[test]
+    fn sanitize_rejects_invisible_characters_inside_the_text() {
+        for hidden in ["Ca\u{200B}fe", "Ca\u{00AD}fe", "\u{202E}efac", "Ca\u{0007}f", "Ca\u{FEFF}f"] {
+            assert_eq!(sanitize(hidden, &LIMIT), Err(TextError::InvalidCharacter), "{hidden:?}");
+        }
+    }
+
+    #[test]
+    fn is_forbidden_covers_control_and_format_characters() {
+        for c in ['\u{0000}', '\u{001B}', '\u{007F}', '\u{0085}', '\u{00AD}', '\u{034F}', '\u{180E}', '\u{200B}',
+            '\u{200D}', '\u{200F}', '\u{2028}', '\u{202A}', '\u{202E}', '\u{2060}', '\u{2066}', '\u{2069}',
+            '\u{3164}', '\u{FEFF}', '\u{FFF9}', '\u{FFFB}']
+        {
+            assert!(is_forbidden(c), "{c:?}");
+        }
+        for c in ['a', 'Z', '0', ' ', '-', '\'', '\u{00E9}', '\u{0301}', '\u{00A0}', '\u{4E2D}', '\u{1F600}', '\u{2070}']
+        {
+            assert!(!is_forbidden(c), "{c:?}");
+        }
+    }
+}
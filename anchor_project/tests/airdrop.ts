@@ -0,0 +1,138 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { MyProgram } from "../target/types/my_program";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  ASSOCIATED_PROGRAM_ID,
+  ata,
+  BusinessFixture,
+  chainTime,
+  createBusiness,
+  expectError,
+  fundedKeypair,
+  pda,
+  TOKEN_PROGRAM_ID,
+  tokenBalance,
+  waitUntil,
+} from "./helpers";
+import { holderLeaf, merkleProof, merkleRoot } from "./merkle";
+
+/**
+ * Merkle-root share airdrops: claims, replay protection, invalid proofs and closing after expiry
+ */
+describe("airdrop", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.MyProgram as Program<MyProgram>;
+
+  let fixture: BusinessFixture;
+  let claimants: { wallet: Keypair; amount: number }[];
+  let leaves: Buffer[];
+  let airdrop: PublicKey;
+  let airdropVault: PublicKey;
+  let expiryTs: number;
+
+  const claim = (wallet: Keypair, amount: number, proof: Buffer[]) =>
+    program.methods
+      .claimAirdrop(
+        proof.map((node) => Array.from(node)),
+        new anchor.BN(amount)
+      )
+      .accounts({
+        airdrop,
+        airdropVault,
+        claimReceipt: pda(program.programId, "airdrop_claim", airdrop, wallet.publicKey),
+        claimantSharesAta: ata(fixture.shareMint, wallet.publicKey),
+        shareMint: fixture.shareMint,
+        claimant: wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+  const close = () =>
+    program.methods
+      .closeAirdrop()
+      .accounts({
+        business: fixture.business,
+        airdrop,
+        airdropVault,
+        sharesVault: fixture.sharesVault,
+        owner: fixture.owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([fixture.owner])
+      .rpc();
+
+  before(async () => {
+    fixture = await createBusiness(program, provider, { name: "Airdrop Shop" });
+    claimants = [];
+    for (const amount of [10, 20, 5]) {
+      claimants.push({ wallet: await fundedKeypair(provider, 1), amount });
+    }
+    leaves = claimants.map(({ wallet, amount }) => holderLeaf(wallet.publicKey, amount));
+    const root = merkleRoot(leaves);
+    airdrop = pda(program.programId, "airdrop", fixture.business, root);
+    airdropVault = pda(program.programId, "airdrop_vault", airdrop);
+    expiryTs = (await chainTime(provider)) + 15;
+
+    await program.methods
+      .createShareAirdrop(Array.from(root), new anchor.BN(35), new anchor.BN(expiryTs))
+      .accounts({
+        business: fixture.business,
+        airdrop,
+        airdropVault,
+        sharesVault: fixture.sharesVault,
+        shareMint: fixture.shareMint,
+        shareMintAuthority: fixture.shareMintAuthority,
+        owner: fixture.owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([fixture.owner])
+      .rpc();
+  });
+
+  it("pays claimants in the tree once", async () => {
+    const [alice, bob] = claimants;
+    await claim(alice.wallet, alice.amount, merkleProof(leaves, 0));
+    await claim(bob.wallet, bob.amount, merkleProof(leaves, 1));
+
+    assert.equal(await tokenBalance(provider, ata(fixture.shareMint, alice.wallet.publicKey)), 10);
+    assert.equal(await tokenBalance(provider, ata(fixture.shareMint, bob.wallet.publicKey)), 20);
+
+    const state = await program.account.shareAirdrop.fetch(airdrop);
+    assert.equal(state.claimedAmount.toNumber(), 30);
+    assert.equal(state.numClaims, 2);
+
+    // The claim receipt already exists
+    await expectError(claim(alice.wallet, alice.amount, merkleProof(leaves, 0)), "already in use");
+  });
+
+  it("rejects invalid proofs", async () => {
+    const carol = claimants[2];
+    const outsider = await fundedKeypair(provider, 1);
+
+    // Wrong amount, someone else's proof, and a wallet outside the tree
+    await expectError(claim(carol.wallet, 6, merkleProof(leaves, 2)), "InvalidMerkleProof");
+    await expectError(claim(carol.wallet, carol.amount, merkleProof(leaves, 0)), "InvalidMerkleProof");
+    await expectError(claim(outsider, carol.amount, merkleProof(leaves, 2)), "InvalidMerkleProof");
+  });
+
+  it("returns unclaimed shares to the vault after expiry", async () => {
+    await expectError(close(), "AirdropActive");
+
+    await waitUntil(provider, expiryTs);
+    const carol = claimants[2];
+    await expectError(claim(carol.wallet, carol.amount, merkleProof(leaves, 2)), "AirdropExpired");
+
+    const vaultBefore = await tokenBalance(provider, fixture.sharesVault);
+    await close();
+    assert.equal(await tokenBalance(provider, fixture.sharesVault), vaultBefore + carol.amount);
+    assert.isNull(await program.account.shareAirdrop.fetchNullable(airdrop));
+    assert.isNull(await provider.connection.getAccountInfo(airdropVault));
+  });
+});
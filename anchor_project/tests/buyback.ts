@@ -0,0 +1,242 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { MyProgram } from "../target/types/my_program";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  ata,
+  BusinessFixture,
+  buyFromRound,
+  chainTime,
+  createBusiness,
+  createRound,
+  expectError,
+  fundedKeypair,
+  pda,
+  TOKEN_PROGRAM_ID,
+  tokenBalance,
+  u64Seed,
+  waitUntil,
+} from "./helpers";
+
+/**
+ * Escrowed share buybacks: sales, limits, burns and the escrow refund on close
+ */
+describe("buyback", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.MyProgram as Program<MyProgram>;
+
+  let fixture: BusinessFixture;
+  let treasury: Keypair;
+  let seller: Keypair;
+  let buyback: PublicKey;
+
+  const open = (price: number, maxShares: number, expiryTs: number, burn: boolean) =>
+    program.methods
+      .createBuyback(new anchor.BN(price), new anchor.BN(maxShares), new anchor.BN(expiryTs), burn)
+      .accounts({
+        business: fixture.business,
+        buyback,
+        treasury: treasury.publicKey,
+        businessTreasury: null,
+        withdrawal: null,
+        owner: fixture.owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([fixture.owner, treasury])
+      .rpc();
+
+  const sell = (amount: number) =>
+    program.methods
+      .sellToBuyback(new anchor.BN(amount))
+      .accounts({
+        business: fixture.business,
+        buyback,
+        sellerSharesAta: ata(fixture.shareMint, seller.publicKey),
+        sharesVault: fixture.sharesVault,
+        shareMint: fixture.shareMint,
+        seller: seller.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([seller])
+      .rpc();
+
+  const close = () =>
+    program.methods
+      .closeBuyback()
+      .accounts({
+        business: fixture.business,
+        buyback,
+        treasury: treasury.publicKey,
+        owner: fixture.owner.publicKey,
+      })
+      .signers([fixture.owner])
+      .rpc();
+
+  const balance = (account: PublicKey) => provider.connection.getBalance(account);
+
+  before(async () => {
+    treasury = await fundedKeypair(provider, 2);
+    fixture = await createBusiness(program, provider, {
+      name: "Buyback Shop",
+      price: 1_000,
+      treasury: treasury.publicKey,
+      list: true,
+    });
+    buyback = pda(program.programId, "buyback", fixture.business);
+    const round = await createRound(program, fixture, { index: 0, shares: 100 });
+    seller = await fundedKeypair(provider, 1);
+    await buyFromRound(program, fixture, round, seller, 10);
+  });
+
+  it("pays sellers from the escrow and refunds the rest to the treasury after expiry", async () => {
+    const expiryTs = (await chainTime(provider)) + 10;
+    let treasuryBefore = await balance(treasury.publicKey);
+    await open(2_000, 6, expiryTs, false);
+    assert.equal(treasuryBefore - (await balance(treasury.publicKey)), 12_000);
+
+    const sellerBefore = await balance(seller.publicKey);
+    const vaultBefore = await tokenBalance(provider, fixture.sharesVault);
+    await sell(4);
+    assert.equal((await balance(seller.publicKey)) - sellerBefore, 8_000);
+    assert.equal(await tokenBalance(provider, fixture.sharesVault), vaultBefore + 4);
+    assert.equal(await tokenBalance(provider, ata(fixture.shareMint, seller.publicKey)), 6);
+
+    let state = await program.account.buyback.fetch(buyback);
+    assert.equal(state.sharesBought.toNumber(), 4);
+    assert.equal(state.escrowLamports.toNumber(), 4_000);
+
+    // Only 2 of the 6 shares are left to buy
+    await expectError(sell(3), "InsufficientShares");
+    await expectError(close(), "BuybackActive");
+
+    await waitUntil(provider, expiryTs);
+    await expectError(sell(1), "BuybackExpired");
+
+    treasuryBefore = await balance(treasury.publicKey);
+    await close();
+    assert.equal((await balance(treasury.publicKey)) - treasuryBefore, 4_000);
+    assert.isNull(await program.account.buyback.fetchNullable(buyback));
+  });
+
+  it("burns bought-back shares and can close as soon as it is filled", async () => {
+    await open(1_500, 2, (await chainTime(provider)) + 3_600, true);
+
+    const supplyBefore = Number((await provider.connection.getTokenSupply(fixture.shareMint)).value.amount);
+    const vaultBefore = await tokenBalance(provider, fixture.sharesVault);
+    await sell(2);
+
+    assert.equal(Number((await provider.connection.getTokenSupply(fixture.shareMint)).value.amount), supplyBefore - 2);
+    assert.equal(await tokenBalance(provider, fixture.sharesVault), vaultBefore);
+    const business = await program.account.business.fetch(fixture.business);
+    assert.equal(business.totalShares.toNumber(), fixture.totalShares - 2);
+
+    const treasuryBefore = await balance(treasury.publicKey);
+    await close();
+    assert.equal(await balance(treasury.publicKey), treasuryBefore);
+    assert.isNull(await program.account.buyback.fetchNullable(buyback));
+  });
+
+  it("funds the escrow from a BusinessTreasury only through a delayed, rate-limited withdrawal", async () => {
+    const owned = await createBusiness(program, provider, { name: "Treasury Buyback", price: 1_000, list: true });
+    const businessTreasury = pda(program.programId, "treasury", owned.business);
+    const ownedBuyback = pda(program.programId, "buyback", owned.business);
+    const initTreasury = (delay: number, maxPerPeriod: number, period: number) =>
+      program.methods
+        .initTreasury(new anchor.BN(delay), new anchor.BN(maxPerPeriod), new anchor.BN(period))
+        .accounts({
+          business: owned.business,
+          businessTreasury,
+          owner: owned.owner.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([owned.owner])
+        .rpc();
+
+    // Withdrawals can't skip the delay, with or without a rate limit
+    await expectError(initTreasury(0, 0, 0), "InvalidTreasuryConfig");
+    await expectError(initTreasury(59, 5_000, 3_600), "InvalidTreasuryConfig");
+    await initTreasury(60, 5_000, 3_600);
+    const round = await createRound(program, owned, { index: 0, shares: 100 });
+    await buyFromRound(program, owned, round, await fundedKeypair(provider, 1), 10, 1_000, businessTreasury);
+
+    const withdrawalPda = (id: number) => pda(program.programId, "withdrawal", businessTreasury, u64Seed(id));
+    const request = (id: number, amount: number, recipient: PublicKey = ownedBuyback) =>
+      program.methods
+        .requestWithdrawal(new anchor.BN(amount), recipient, "Share buyback")
+        .accounts({
+          business: owned.business,
+          businessTreasury,
+          withdrawal: withdrawalPda(id),
+          owner: owned.owner.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([owned.owner])
+        .rpc();
+    const expiryTs = (await chainTime(provider)) + 3_600;
+    const openFromTreasury = (price: number, maxShares: number, withdrawal: PublicKey | null) =>
+      program.methods
+        .createBuyback(new anchor.BN(price), new anchor.BN(maxShares), new anchor.BN(expiryTs), false)
+        .accounts({
+          business: owned.business,
+          buyback: ownedBuyback,
+          treasury: businessTreasury,
+          businessTreasury,
+          withdrawal,
+          owner: owned.owner.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([owned.owner])
+        .rpc();
+
+    // No direct debit of the treasury
+    await expectError(openFromTreasury(2_000, 2, null), "InvalidWithdrawal");
+
+    const supplier = (await fundedKeypair(provider, 1)).publicKey;
+    const execute = (id: number, recipient: PublicKey) =>
+      program.methods
+        .executeWithdrawal()
+        .accounts({
+          business: owned.business,
+          businessTreasury,
+          withdrawal: withdrawalPda(id),
+          recipient,
+          owner: owned.owner.publicKey,
+        })
+        .signers([owned.owner])
+        .rpc();
+
+    await request(0, 4_000);
+    await request(1, 6_000);
+    await request(2, 1_000, supplier);
+    await expectError(execute(2, supplier), "WithdrawalLocked");
+    const { executableAt } = await program.account.withdrawalRequest.fetch(withdrawalPda(1));
+
+    await expectError(openFromTreasury(2_000, 2, withdrawalPda(0)), "WithdrawalLocked");
+    await waitUntil(provider, executableAt.toNumber());
+
+    // The request must match the escrow, and the escrow counts against the rate limit
+    await expectError(openFromTreasury(1_000, 3, withdrawalPda(0)), "InvalidWithdrawal");
+    await expectError(openFromTreasury(1_000, 6, withdrawalPda(1)), "WithdrawalLimitExceeded");
+
+    const treasuryBefore = await balance(businessTreasury);
+    await openFromTreasury(2_000, 2, withdrawalPda(0));
+    assert.equal(treasuryBefore - (await balance(businessTreasury)), 4_000);
+    assert.equal((await program.account.buyback.fetch(ownedBuyback)).escrowLamports.toNumber(), 4_000);
+    assert.isNull(await program.account.withdrawalRequest.fetchNullable(withdrawalPda(0)));
+
+    const state = await program.account.businessTreasury.fetch(businessTreasury);
+    assert.equal(state.withdrawnInPeriod.toNumber(), 4_000);
+    assert.equal(state.totalWithdrawn.toNumber(), 4_000);
+
+    // Plain withdrawals go to the named recipient and share the same rate limit
+    await expectError(execute(2, owned.owner.publicKey), "InvalidWithdrawal");
+    await expectError(execute(1, ownedBuyback), "WithdrawalLimitExceeded");
+    const supplierBefore = await balance(supplier);
+    await execute(2, supplier);
+    assert.equal((await balance(supplier)) - supplierBefore, 1_000);
+    assert.isNull(await program.account.withdrawalRequest.fetchNullable(withdrawalPda(2)));
+    assert.equal((await program.account.businessTreasury.fetch(businessTreasury)).withdrawnInPeriod.toNumber(), 5_000);
+  });
+});
@@ -10,6 +10,8 @@ import {
 } from "@solana/web3.js";
 import { assert } from "chai";
 import * as token from "@solana/spl-token";
+import { nameRecordPda } from "./slug";
+import { businessIndexPages, indexPageWithRoom, CATEGORIES } from "./search";
 
 /**
  * End-to-End Business Flow Test
@@ -18,8 +20,8 @@ import * as token from "@solana/spl-token";
  * 1. Owner registers a business
  * 2. Owner configures offering parameters
  * 3. Owner initializes share mint
- * 4. Owner lists business on marketplace
- * 5. Buyer purchases shares
+ * 4. Owner lists business on marketplace and opens a funding round
+ * 5. Buyer purchases shares from the round
  * 6. Verifies token transfer and SOL payment
  */
 describe("E2E Business Flow - Register -> Configure -> Mint -> List -> Buy", () => {
@@ -40,11 +42,14 @@ describe("E2E Business Flow - Register -> Configure -> Mint -> List -> Buy", ()
   let shareMintPda: PublicKey;
   let shareMintAuthorityPda: PublicKey;
   let sharesVaultPda: PublicKey;
+  let registryPagePda: PublicKey;
 
   // Test parameters
-  const BUSINESS_NAME = "Test Coffee Shop";
+  const BUSINESS_NAME = `Test Coffee Shop ${Date.now()}`; // names are unique by slug
+  const BUSINESS_REGION = "6gkzw"; // Curitiba geohash
   const TOTAL_SHARES = 1_000;
   const PRICE_PER_SHARE_LAMPORTS = 1_000_000; // 0.001 SOL per share
+  const ROUND_SHARES = 100;
   const SHARES_TO_BUY = 10;
   const EXPECTED_COST = SHARES_TO_BUY * PRICE_PER_SHARE_LAMPORTS;
 
@@ -141,29 +146,45 @@ describe("E2E Business Flow - Register -> Configure -> Mint -> List -> Buy", ()
     // ============================================================
     console.log("\n📝 Step 1: Registering Business");
 
-    const [mintPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("mint"), businessPda.toBuffer()],
-      program.programId
-    );
-    const [mintAuthorityPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("mint_authority"), businessPda.toBuffer()],
-      program.programId
-    );
-    const ownerTokenAccount = await anchor.utils.token.associatedAddress({
-      mint: mintPda,
-      owner: owner.publicKey,
-    });
+    // Find the first registry page with room, creating it if needed
+    for (let page = 0; ; page++) {
+      [registryPagePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("registry"), new anchor.BN(page).toArrayLike(Buffer, "le", 4)],
+        program.programId
+      );
+      const registryPage = await program.account.businessRegistry.fetchNullable(registryPagePda);
+      if (registryPage === null) {
+        const [previousPage] = PublicKey.findProgramAddressSync(
+          [Buffer.from("registry"), new anchor.BN(Math.max(page - 1, 0)).toArrayLike(Buffer, "le", 4)],
+          program.programId
+        );
+        await program.methods
+          .initRegistryPage(page)
+          .accounts({
+            registryPage: registryPagePda,
+            previousPage: page === 0 ? null : previousPage,
+            payer: owner.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([owner])
+          .rpc();
+        break;
+      }
+      if (registryPage.count < registryPage.entries.length) {
+        break;
+      }
+    }
 
     const registerTx = await program.methods
-      .registerBusiness(BUSINESS_NAME)
+      .registerBusiness(BUSINESS_NAME, { cafe: {} }, Array.from(Buffer.from(BUSINESS_REGION)))
       .accounts({
         business: businessPda,
-        mint: mintPda,
-        mintAuthority: mintAuthorityPda,
-        ownerTokenAccount,
+        nameRecord: nameRecordPda(program.programId, BUSINESS_NAME),
+        previousNameRecord: nameRecordPda(program.programId, ""),
+        registryPage: registryPagePda,
+        categoryIndex: await indexPageWithRoom(program, owner, "category", CATEGORIES.cafe),
+        regionIndex: await indexPageWithRoom(program, owner, "region", BUSINESS_REGION),
         owner: owner.publicKey,
-        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([owner])
@@ -189,6 +210,8 @@ describe("E2E Business Flow - Register -> Configure -> Mint -> List -> Buy", ()
       )
       .accounts({
         business: businessPda,
+        registryPage: registryPagePda,
+        ...businessIndexPages(program.programId, await program.account.business.fetch(businessPda)),
         owner: owner.publicKey,
         systemProgram: SystemProgram.programId,
       })
@@ -260,6 +283,8 @@ describe("E2E Business Flow - Register -> Configure -> Mint -> List -> Buy", ()
       .listBusiness()
       .accounts({
         business: businessPda,
+        registryPage: registryPagePda,
+        ...businessIndexPages(program.programId, await program.account.business.fetch(businessPda)),
         owner: owner.publicKey,
         systemProgram: SystemProgram.programId,
       })
@@ -270,8 +295,55 @@ describe("E2E Business Flow - Register -> Configure -> Mint -> List -> Buy", ()
 
     const businessAfterList = await program.account.business.fetch(businessPda);
     assert.isTrue(businessAfterList.isListed, "Business should be listed");
+    const { regionIndex } = businessIndexPages(program.programId, businessAfterList);
+    const regionPage = await program.account.searchIndex.fetch(regionIndex);
+    const regionEntry = regionPage.entries[businessAfterList.regionIndexSlot];
+    assert.ok(regionEntry.business.equals(businessPda), "Region index should hold the business");
+    assert.equal(regionEntry.listed, 1, "Region index should show the business as listed");
     console.log("   ✅ Business listed successfully");
 
+    // Every primary sale goes through an indexed offering (funding round)
+    const [offeringPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("offering"), businessPda.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 4)],
+      program.programId
+    );
+    const [offeringVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("offering_vault"), offeringPda.toBuffer()],
+      program.programId
+    );
+    const [businessStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("business_stats"), businessPda.toBuffer()],
+      program.programId
+    );
+
+    const roundTx = await program.methods
+      .createOffering(
+        0,
+        "Seed",
+        new anchor.BN(PRICE_PER_SHARE_LAMPORTS),
+        new anchor.BN(ROUND_SHARES),
+        new anchor.BN(0),
+        new anchor.BN(0),
+        { fixed: {} }
+      )
+      .accounts({
+        offering: offeringPda,
+        businessStats: businessStatsPda,
+        business: businessPda,
+        offeringVault: offeringVaultPda,
+        sharesVault: sharesVaultPda,
+        shareMint: shareMintPda,
+        shareMintAuthority: shareMintAuthorityPda,
+        owner: owner.publicKey,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+
+    console.log("✅ CreateOffering transaction:", roundTx);
+    console.log("   ✅ Seed round opened with", ROUND_SHARES, "shares");
+
     // ============================================================
     // STEP 5: Buyer Purchases Shares
     // ============================================================
@@ -280,7 +352,7 @@ describe("E2E Business Flow - Register -> Configure -> Mint -> List -> Buy", ()
     // Get balances before purchase
     const treasuryBalanceBefore = await connection.getBalance(treasury.publicKey);
     const buyerBalanceBefore = await connection.getBalance(buyer.publicKey);
-    const vaultBalanceBefore = await connection.getTokenAccountBalance(sharesVaultPda);
+    const vaultBalanceBefore = await connection.getTokenAccountBalance(offeringVaultPda);
 
     console.log("💰 Balances before purchase:");
     console.log("   Treasury:", treasuryBalanceBefore / LAMPORTS_PER_SOL, "SOL");
@@ -293,25 +365,43 @@ describe("E2E Business Flow - Register -> Configure -> Mint -> List -> Buy", ()
       owner: buyer.publicKey,
     });
 
+    // First purchase of the buyer in this business: receipt sequence 0
+    const [receiptPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("receipt"),
+        businessPda.toBuffer(),
+        buyer.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 4),
+      ],
+      program.programId
+    );
+
+    const [positionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("position"), businessPda.toBuffer(), buyer.publicKey.toBuffer()],
+      program.programId
+    );
+
     const buyTx = await program.methods
-      .buyShares(new anchor.BN(SHARES_TO_BUY))
+      .buySharesFromOffering(new anchor.BN(SHARES_TO_BUY), new anchor.BN(PRICE_PER_SHARE_LAMPORTS))
       .accounts({
-        buyer: buyer.publicKey,
+        offering: offeringPda,
         business: businessPda,
-        sharesVault: sharesVaultPda,
-        treasury: treasury.publicKey,
+        businessStats: businessStatsPda,
+        offeringVault: offeringVaultPda,
         buyerSharesAta: buyerSharesAta,
         shareMint: shareMintPda,
-        shareMintAuthority: shareMintAuthorityPda,
+        treasury: treasury.publicKey,
+        position: positionPda,
+        receipt: receiptPda,
+        buyer: buyer.publicKey,
         tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-        systemProgram: SystemProgram.programId,
         associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
-        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
       })
       .signers([buyer])
       .rpc();
 
-    console.log("✅ BuyShares transaction:", buyTx);
+    console.log("✅ BuySharesFromOffering transaction:", buyTx);
 
     // ============================================================
     // STEP 6: Verify Results
@@ -339,7 +429,7 @@ describe("E2E Business Flow - Register -> Configure -> Mint -> List -> Buy", ()
     );
 
     // Check vault balance decreased
-    const vaultBalanceAfter = await connection.getTokenAccountBalance(sharesVaultPda);
+    const vaultBalanceAfter = await connection.getTokenAccountBalance(offeringVaultPda);
     const vaultDecrease = BigInt(vaultBalanceBefore.value.amount) - BigInt(vaultBalanceAfter.value.amount);
     assert.equal(
       vaultDecrease.toString(),
@@ -348,6 +438,16 @@ describe("E2E Business Flow - Register -> Configure -> Mint -> List -> Buy", ()
     );
     console.log("   ✅ Vault decreased by", vaultDecrease.toString(), "shares");
 
+    // Check the purchase receipt
+    const receipt = await program.account.purchaseReceipt.fetch(receiptPda);
+    assert.ok(receipt.buyer.equals(buyer.publicKey), "Receipt buyer should match");
+    assert.ok(receipt.business.equals(businessPda), "Receipt business should match");
+    assert.ok(receipt.offering.equals(offeringPda), "Receipt should reference the round");
+    assert.equal(receipt.shares.toNumber(), SHARES_TO_BUY, "Receipt shares should match");
+    assert.equal(receipt.totalLamports.toNumber(), EXPECTED_COST, "Receipt total should match");
+    assert.equal(receipt.sequence, 0, "First purchase should have sequence 0");
+    console.log("   ✅ Receipt recorded at slot", receipt.slot.toString());
+
     console.log("\n" + "=".repeat(60));
     console.log("✅ E2E Business Flow Test Completed Successfully!");
     console.log("=".repeat(60));
@@ -0,0 +1,204 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { MyProgram } from "../target/types/my_program";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  ata,
+  BusinessFixture,
+  buyFromRound,
+  chainTime,
+  createBusiness,
+  createRound,
+  expectError,
+  fundedKeypair,
+  offeringVaultAccounts,
+  pda,
+  RoundFixture,
+  TOKEN_PROGRAM_ID,
+  tokenBalance,
+  u64Seed,
+  waitUntil,
+} from "./helpers";
+
+/**
+ * Shareholder governance: proposals, token-locked voting, quorum and approval threshold
+ */
+describe("governance", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.MyProgram as Program<MyProgram>;
+
+  const VOTING_PERIOD = 6;
+
+  let fixture: BusinessFixture;
+  let governance: PublicKey;
+  let alice: Keypair;
+  let bob: Keypair;
+  let carol: Keypair;
+  let round: RoundFixture;
+
+  const configure = (quorumBps: number, approvalThresholdBps: number, proposalThreshold: number) =>
+    program.methods
+      .configureGovernance(quorumBps, approvalThresholdBps, new anchor.BN(proposalThreshold))
+      .accounts({
+        business: fixture.business,
+        governance,
+        owner: fixture.owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([fixture.owner])
+      .rpc();
+
+  const propose = async (proposer: Keypair, title: string, offerings = offeringVaultAccounts([round])) => {
+    const id = (await program.account.governance.fetch(governance)).proposalCount;
+    const proposal = pda(program.programId, "proposal", fixture.business, u64Seed(id));
+    const holderAta = ata(fixture.shareMint, proposer.publicKey);
+    await program.methods
+      .createProposal(title, "https://localshare.example/proposals/" + id.toString(), new anchor.BN(VOTING_PERIOD))
+      .accounts({
+        business: fixture.business,
+        governance,
+        proposal,
+        voteVault: pda(program.programId, "vote_vault", proposal),
+        proposerSharesAta: proposer === fixture.owner ? null : holderAta,
+        shareMint: fixture.shareMint,
+        sharesVault: fixture.sharesVault,
+        proposer: proposer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(offerings)
+      .signers([proposer])
+      .rpc();
+    return proposal;
+  };
+
+  const vote = (proposal: PublicKey, voter: Keypair, approve: boolean, amount: number) =>
+    program.methods
+      .castVote(approve, new anchor.BN(amount))
+      .accounts({
+        proposal,
+        voteRecord: pda(program.programId, "vote", proposal, voter.publicKey),
+        voteVault: pda(program.programId, "vote_vault", proposal),
+        voterSharesAta: ata(fixture.shareMint, voter.publicKey),
+        voter: voter.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+  const finalize = (proposal: PublicKey) => program.methods.finalizeProposal().accounts({ proposal }).rpc();
+
+  const withdraw = (proposal: PublicKey, voter: Keypair) =>
+    program.methods
+      .withdrawVoteTokens()
+      .accounts({
+        proposal,
+        voteRecord: pda(program.programId, "vote", proposal, voter.publicKey),
+        voteVault: pda(program.programId, "vote_vault", proposal),
+        voterSharesAta: ata(fixture.shareMint, voter.publicKey),
+        voter: voter.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([voter])
+      .rpc();
+
+  const status = async (proposal: PublicKey) =>
+    Object.keys((await program.account.proposal.fetch(proposal)).status)[0];
+
+  before(async () => {
+    fixture = await createBusiness(program, provider, { name: "Governed Shop", totalShares: 1_000, list: true });
+    governance = pda(program.programId, "governance", fixture.business);
+    round = await createRound(program, fixture, { index: 0, shares: 600 });
+    alice = await fundedKeypair(provider, 10);
+    bob = await fundedKeypair(provider, 10);
+    await buyFromRound(program, fixture, round, alice, 300);
+    await buyFromRound(program, fixture, round, bob, 200);
+    carol = await fundedKeypair(provider, 1);
+    await buyFromRound(program, fixture, round, carol, 1);
+  });
+
+  it("validates the governance rules", async () => {
+    // A tie must never pass: the threshold is a strict majority
+    await expectError(configure(5_000, 5_000, 100), "InvalidGovernanceConfig");
+    await expectError(configure(0, 6_000, 100), "InvalidGovernanceConfig");
+    await expectError(configure(5_000, 10_001, 100), "InvalidGovernanceConfig");
+
+    // Quorum: 50% of the 501 circulating shares, approval: 60% of votes cast
+    await configure(5_000, 6_000, 250);
+  });
+
+  it("takes quorum from circulating supply", async () => {
+    // 400 shares in the business vault and 99 unsold in the round don't count toward quorum
+    const proposal = await propose(fixture.owner, "Circulating quorum");
+    assert.equal((await program.account.proposal.fetch(proposal)).quorumVotes.toNumber(), 250);
+
+    // Omitting the round only raises the bar: its unsold shares count as circulating
+    const withoutRound = await propose(fixture.owner, "Without the round", []);
+    assert.equal((await program.account.proposal.fetch(withoutRound)).quorumVotes.toNumber(), 300);
+
+    // Vaults must be paired with their offering and can't be subtracted twice
+    await expectError(
+      propose(fixture.owner, "Unpaired vault", offeringVaultAccounts([round]).slice(1)),
+      "InvalidOfferingVault"
+    );
+    await expectError(
+      propose(fixture.owner, "Duplicated vault", offeringVaultAccounts([round, round])),
+      "InvalidOfferingVault"
+    );
+  });
+
+  it("requires proposers other than the owner to hold the proposal threshold", async () => {
+    await expectError(propose(bob, "Too small to propose"), "InsufficientVotingPower");
+  });
+
+  it("locks voting shares and passes a proposal exactly at the threshold", async () => {
+    const proposal = await propose(alice, "Open a second store");
+
+    await vote(proposal, alice, true, 300);
+    await vote(proposal, bob, false, 200);
+
+    // Shares are locked in the vote vault, so they can't vote twice from this or another wallet
+    assert.equal(await tokenBalance(provider, ata(fixture.shareMint, alice.publicKey)), 0);
+    assert.equal(await tokenBalance(provider, pda(program.programId, "vote_vault", proposal)), 500);
+    await expectError(vote(proposal, alice, true, 1), "already in use");
+    await expectError(withdraw(proposal, alice), "VotingStillOpen");
+    await expectError(finalize(proposal), "VotingStillOpen");
+
+    await waitUntil(provider, (await chainTime(provider)) + VOTING_PERIOD + 1);
+    await expectError(vote(proposal, carol, true, 1), "VotingClosed");
+
+    // 500 votes meet the 250 quorum and 300/500 = 60% meets the threshold
+    await finalize(proposal);
+    assert.equal(await status(proposal), "succeeded");
+    await expectError(finalize(proposal), "ProposalFinalized");
+
+    await withdraw(proposal, alice);
+    await withdraw(proposal, bob);
+    assert.equal(await tokenBalance(provider, ata(fixture.shareMint, alice.publicKey)), 300);
+    assert.equal(await tokenBalance(provider, ata(fixture.shareMint, bob.publicKey)), 200);
+  });
+
+  it("defeats proposals below the approval threshold or below quorum", async () => {
+    const belowThreshold = await propose(fixture.owner, "Below threshold");
+    await vote(belowThreshold, alice, false, 300);
+    await vote(belowThreshold, bob, true, 200);
+    await waitUntil(provider, (await chainTime(provider)) + VOTING_PERIOD + 1);
+
+    // Quorum is reached but only 200/500 = 40% voted yes
+    await finalize(belowThreshold);
+    assert.equal(await status(belowThreshold), "defeated");
+    await withdraw(belowThreshold, alice);
+    await withdraw(belowThreshold, bob);
+
+    const belowQuorum = await propose(fixture.owner, "Below quorum");
+    await vote(belowQuorum, bob, true, 200);
+    await waitUntil(provider, (await chainTime(provider)) + VOTING_PERIOD + 1);
+
+    // Unanimous, but 200 votes are short of the 250 quorum
+    await finalize(belowQuorum);
+    assert.equal(await status(belowQuorum), "defeated");
+  });
+});
@@ -0,0 +1,472 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { MyProgram } from "../target/types/my_program";
+import { Keypair, PublicKey, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { assert } from "chai";
+import { nameRecordPda } from "./slug";
+import { businessIndexPages, indexPageWithRoom, CATEGORIES } from "./search";
+
+/**
+ * Shared fixtures for the feature tests: funded wallets, a business with its
+ * share mint, funding rounds and purchases, plus PDA and clock helpers
+ */
+
+export const TOKEN_PROGRAM_ID = anchor.utils.token.TOKEN_PROGRAM_ID;
+export const ASSOCIATED_PROGRAM_ID = anchor.utils.token.ASSOCIATED_PROGRAM_ID;
+
+/** Region used by fixture businesses (Curitiba geohash) */
+export const FIXTURE_REGION = "6gkzw";
+
+/** Little-endian u32 seed */
+export function u32Seed(value: number): Buffer {
+  return new anchor.BN(value).toArrayLike(Buffer, "le", 4);
+}
+
+/** Little-endian u64 seed */
+export function u64Seed(value: number | anchor.BN): Buffer {
+  return new anchor.BN(value).toArrayLike(Buffer, "le", 8);
+}
+
+/** Program address for `seeds` (strings are UTF-8 encoded) */
+export function pda(programId: PublicKey, ...seeds: (string | Buffer | PublicKey)[]): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    seeds.map((seed) =>
+      typeof seed === "string" ? Buffer.from(seed) : seed instanceof PublicKey ? seed.toBuffer() : seed
+    ),
+    programId
+  )[0];
+}
+
+/** Associated token account of `owner` for `mint` */
+export function ata(mint: PublicKey, owner: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [owner.toBuffer(), TOKEN_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    ASSOCIATED_PROGRAM_ID
+  )[0];
+}
+
+/** Creates a wallet funded by the provider wallet */
+export async function fundedKeypair(provider: anchor.AnchorProvider, sol = 5): Promise<Keypair> {
+  const keypair = Keypair.generate();
+  await provider.sendAndConfirm(
+    new anchor.web3.Transaction().add(
+      SystemProgram.transfer({
+        fromPubkey: provider.wallet.publicKey,
+        toPubkey: keypair.publicKey,
+        lamports: sol * LAMPORTS_PER_SOL,
+      })
+    )
+  );
+  return keypair;
+}
+
+/** Token balance of `account` (0 if it doesn't exist) */
+export async function tokenBalance(provider: anchor.AnchorProvider, account: PublicKey): Promise<number> {
+  const info = await provider.connection.getAccountInfo(account);
+  if (info === null) {
+    return 0;
+  }
+  return Number((await provider.connection.getTokenAccountBalance(account)).value.amount);
+}
+
+/** Current cluster time (unix seconds) */
+export async function chainTime(provider: anchor.AnchorProvider): Promise<number> {
+  const slot = await provider.connection.getSlot();
+  return (await provider.connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+}
+
+/** Waits until the cluster clock reaches `timestamp` */
+export async function waitUntil(provider: anchor.AnchorProvider, timestamp: number): Promise<void> {
+  while ((await chainTime(provider)) < timestamp) {
+    await new Promise((resolve) => setTimeout(resolve, 500));
+  }
+}
+
+/** Asserts that `action` fails with the program error `code` */
+export async function expectError(action: Promise<unknown>, code: string): Promise<void> {
+  try {
+    await action;
+  } catch (error) {
+    assert.include(String(error), code, `expected ${code}`);
+    return;
+  }
+  assert.fail(`expected ${code}`);
+}
+
+/** First registry page with room, creating it if needed */
+export async function registryPageWithRoom(program: Program<MyProgram>, payer: Keypair): Promise<PublicKey> {
+  for (let page = 0; ; page++) {
+    const registryPage = pda(program.programId, "registry", u32Seed(page));
+    const account = await program.account.businessRegistry.fetchNullable(registryPage);
+    if (account === null) {
+      await program.methods
+        .initRegistryPage(page)
+        .accounts({
+          registryPage,
+          previousPage: page === 0 ? null : pda(program.programId, "registry", u32Seed(page - 1)),
+          payer: payer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([payer])
+        .rpc();
+      return registryPage;
+    }
+    if (account.count < account.entries.length) {
+      return registryPage;
+    }
+  }
+}
+
+/** A business with its share mint initialized */
+export interface BusinessFixture {
+  owner: Keypair;
+  business: PublicKey;
+  registryPage: PublicKey;
+  shareMint: PublicKey;
+  shareMintAuthority: PublicKey;
+  sharesVault: PublicKey;
+  treasury: PublicKey;
+  totalShares: number;
+  price: number;
+}
+
+/** Registers a business, configures it and mints its shares (listing is optional) */
+export async function createBusiness(
+  program: Program<MyProgram>,
+  provider: anchor.AnchorProvider,
+  options: { name: string; totalShares?: number; price?: number; treasury?: PublicKey; list?: boolean }
+): Promise<BusinessFixture> {
+  const owner = await fundedKeypair(provider, 10);
+  const business = pda(program.programId, "business", owner.publicKey);
+  const registryPage = await registryPageWithRoom(program, owner);
+  const name = `${options.name} ${Date.now()}`; // names are unique by slug
+  const totalShares = options.totalShares ?? 10_000;
+  const price = options.price ?? 1_000_000;
+  const treasury = options.treasury ?? Keypair.generate().publicKey;
+
+  await program.methods
+    .registerBusiness(name, { services: {} }, Array.from(Buffer.from(FIXTURE_REGION)))
+    .accounts({
+      business,
+      nameRecord: nameRecordPda(program.programId, name),
+      previousNameRecord: nameRecordPda(program.programId, ""),
+      registryPage,
+      categoryIndex: await indexPageWithRoom(program, owner, "category", CATEGORIES.services),
+      regionIndex: await indexPageWithRoom(program, owner, "region", FIXTURE_REGION),
+      owner: owner.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([owner])
+    .rpc();
+
+  await program.methods
+    .configureOffering(new anchor.BN(totalShares), new anchor.BN(price), treasury)
+    .accounts({
+      business,
+      registryPage,
+      ...businessIndexPages(program.programId, await program.account.business.fetch(business)),
+      owner: owner.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([owner])
+    .rpc();
+
+  const fixture: BusinessFixture = {
+    owner,
+    business,
+    registryPage,
+    shareMint: pda(program.programId, "share_mint", business),
+    shareMintAuthority: pda(program.programId, "share_mint_authority", business),
+    sharesVault: pda(program.programId, "shares_vault", business),
+    treasury,
+    totalShares,
+    price,
+  };
+
+  await program.methods
+    .initShareMint()
+    .accounts({
+      business,
+      owner: owner.publicKey,
+      shareMint: fixture.shareMint,
+      shareMintAuthority: fixture.shareMintAuthority,
+      sharesVault: fixture.sharesVault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([owner])
+    .rpc();
+
+  if (options.list) {
+    await listBusiness(program, fixture);
+  }
+  return fixture;
+}
+
+/** Lists a fixture business */
+export async function listBusiness(program: Program<MyProgram>, fixture: BusinessFixture): Promise<void> {
+  await program.methods
+    .listBusiness()
+    .accounts({
+      business: fixture.business,
+      registryPage: fixture.registryPage,
+      ...businessIndexPages(program.programId, await program.account.business.fetch(fixture.business)),
+      owner: fixture.owner.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([fixture.owner])
+    .rpc();
+}
+
+/** A funding round of a fixture business */
+export interface RoundFixture {
+  index: number;
+  offering: PublicKey;
+  offeringVault: PublicKey;
+  businessStats: PublicKey;
+  pricing: PublicKey;
+  price: number;
+}
+
+/** Opens funding round `index` (fixed price unless `pricing` is given) */
+export async function createRound(
+  program: Program<MyProgram>,
+  fixture: BusinessFixture,
+  options: { index: number; shares: number; price?: number; pricing?: object; startTs?: number; endTs?: number }
+): Promise<RoundFixture> {
+  const offering = pda(program.programId, "offering", fixture.business, u32Seed(options.index));
+  const round: RoundFixture = {
+    index: options.index,
+    offering,
+    offeringVault: pda(program.programId, "offering_vault", offering),
+    businessStats: pda(program.programId, "business_stats", fixture.business),
+    pricing: pda(program.programId, "pricing", offering),
+    price: options.price ?? fixture.price,
+  };
+  const pricing = options.pricing ?? { fixed: {} };
+
+  await program.methods
+    .createOffering(
+      options.index,
+      `Round ${options.index}`,
+      new anchor.BN(round.price),
+      new anchor.BN(options.shares),
+      new anchor.BN(options.startTs ?? 0),
+      new anchor.BN(options.endTs ?? 0),
+      pricing as any
+    )
+    .accounts({
+      offering,
+      businessStats: round.businessStats,
+      business: fixture.business,
+      offeringVault: round.offeringVault,
+      sharesVault: fixture.sharesVault,
+      shareMint: fixture.shareMint,
+      shareMintAuthority: fixture.shareMintAuthority,
+      pricing: "fixed" in pricing ? null : round.pricing,
+      owner: fixture.owner.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([fixture.owner])
+    .rpc();
+  return round;
+}
+
+/** Accounts of buy_shares_from_offering for `buyer` */
+export function buyAccounts(
+  program: Program<MyProgram>,
+  fixture: BusinessFixture,
+  round: RoundFixture,
+  buyer: PublicKey,
+  treasury: PublicKey = fixture.treasury
+) {
+  return {
+    offering: round.offering,
+    business: fixture.business,
+    pricing: round.pricing,
+    businessStats: round.businessStats,
+    offeringVault: round.offeringVault,
+    buyerSharesAta: ata(fixture.shareMint, buyer),
+    shareMint: fixture.shareMint,
+    treasury,
+    position: pda(program.programId, "position", fixture.business, buyer),
+    buyer,
+    tokenProgram: TOKEN_PROGRAM_ID,
+    associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+    systemProgram: SystemProgram.programId,
+  };
+}
+
+/** Buys `amount` shares of a round, accepting up to `maxPrice` per share (default: round price) */
+export async function buyFromRound(
+  program: Program<MyProgram>,
+  fixture: BusinessFixture,
+  round: RoundFixture,
+  buyer: Keypair,
+  amount: number,
+  maxPrice: number = round.price,
+  treasury?: PublicKey
+): Promise<string> {
+  return program.methods
+    .buySharesFromOffering(new anchor.BN(amount), new anchor.BN(maxPrice))
+    .accounts(buyAccounts(program, fixture, round, buyer.publicKey, treasury))
+    .signers([buyer])
+    .rpc();
+}
+
+/** (offering, offering_vault) remaining accounts excluding unsold round shares from proposal quorum */
+export function offeringVaultAccounts(rounds: RoundFixture[]) {
+  return [...rounds]
+    .sort((a, b) => a.index - b.index)
+    .flatMap((round) => [round.offering, round.offeringVault])
+    .map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }));
+}
+
+/** Moves `amount` shares between wallets with a plain SPL Token transfer */
+export async function transferShares(
+  provider: anchor.AnchorProvider,
+  fixture: BusinessFixture,
+  from: Keypair,
+  to: PublicKey,
+  amount: number
+): Promise<string> {
+  const data = Buffer.alloc(9);
+  data.writeUInt8(3, 0); // TokenInstruction::Transfer
+  data.writeBigUInt64LE(BigInt(amount), 1);
+  const instruction = new anchor.web3.TransactionInstruction({
+    programId: TOKEN_PROGRAM_ID,
+    keys: [
+      { pubkey: ata(fixture.shareMint, from.publicKey), isSigner: false, isWritable: true },
+      { pubkey: ata(fixture.shareMint, to), isSigner: false, isWritable: true },
+      { pubkey: from.publicKey, isSigner: true, isWritable: false },
+    ],
+    data,
+  });
+  return provider.sendAndConfirm(new anchor.web3.Transaction().add(instruction), [from]);
+}
+
+/** A fair-launch subscription of a fixture business */
+export interface SubscriptionFixture {
+  subscription: PublicKey;
+  subscriptionVault: PublicKey;
+  businessStats: PublicKey;
+  price: number;
+}
+
+/** Opens the business's subscription for `shares` during [startTs, endTs) */
+export async function createSubscription(
+  program: Program<MyProgram>,
+  fixture: BusinessFixture,
+  options: { shares: number; startTs: number; endTs: number; price?: number }
+): Promise<SubscriptionFixture> {
+  const subscription = pda(program.programId, "subscription", fixture.business);
+  const sale: SubscriptionFixture = {
+    subscription,
+    subscriptionVault: pda(program.programId, "subscription_vault", subscription),
+    businessStats: pda(program.programId, "business_stats", fixture.business),
+    price: options.price ?? fixture.price,
+  };
+
+  await program.methods
+    .createSubscription(
+      new anchor.BN(options.shares),
+      new anchor.BN(sale.price),
+      new anchor.BN(options.startTs),
+      new anchor.BN(options.endTs)
+    )
+    .accounts({
+      business: fixture.business,
+      subscription,
+      subscriptionVault: sale.subscriptionVault,
+      sharesVault: fixture.sharesVault,
+      shareMint: fixture.shareMint,
+      shareMintAuthority: fixture.shareMintAuthority,
+      owner: fixture.owner.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([fixture.owner])
+    .rpc();
+  return sale;
+}
+
+/** Commits `lamports` to the subscription as `investor` */
+export async function commitToSubscription(
+  program: Program<MyProgram>,
+  sale: SubscriptionFixture,
+  investor: Keypair,
+  lamports: number
+): Promise<string> {
+  return program.methods
+    .commitSubscription(new anchor.BN(lamports))
+    .accounts({
+      subscription: sale.subscription,
+      commitment: pda(program.programId, "commitment", sale.subscription, investor.publicKey),
+      investor: investor.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([investor])
+    .rpc();
+}
+
+/** Settles the subscription (permissionless) */
+export async function settleSubscription(
+  program: Program<MyProgram>,
+  fixture: BusinessFixture,
+  sale: SubscriptionFixture
+): Promise<string> {
+  return program.methods
+    .settleSubscription()
+    .accounts({
+      subscription: sale.subscription,
+      subscriptionVault: sale.subscriptionVault,
+      sharesVault: fixture.sharesVault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+}
+
+/** Claims `investor`'s allocation of a settled subscription (paying `treasury`, default: the fixture's) */
+export async function claimAllocation(
+  program: Program<MyProgram>,
+  fixture: BusinessFixture,
+  sale: SubscriptionFixture,
+  investor: Keypair,
+  treasury: PublicKey = fixture.treasury
+): Promise<string> {
+  return program.methods
+    .claimAllocation()
+    .accounts({
+      business: fixture.business,
+      subscription: sale.subscription,
+      commitment: pda(program.programId, "commitment", sale.subscription, investor.publicKey),
+      businessStats: sale.businessStats,
+      position: pda(program.programId, "position", fixture.business, investor.publicKey),
+      subscriptionVault: sale.subscriptionVault,
+      investorSharesAta: ata(fixture.shareMint, investor.publicKey),
+      treasury,
+      shareMint: fixture.shareMint,
+      investor: investor.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([investor])
+    .rpc();
+}
+
+/** Freezes (or unfreezes) a fixture business as its owner */
+export async function setFrozen(program: Program<MyProgram>, fixture: BusinessFixture, frozen: boolean): Promise<void> {
+  const accounts = {
+    config: pda(program.programId, "config"),
+    role: null,
+    business: fixture.business,
+    businessFreeze: pda(program.programId, "freeze", fixture.business),
+    authority: fixture.owner.publicKey,
+  };
+  const method = frozen
+    ? program.methods.freezeBusiness(1).accounts({ ...accounts, systemProgram: SystemProgram.programId })
+    : program.methods.unfreezeBusiness(1).accounts(accounts);
+  await method.signers([fixture.owner]).rpc();
+}
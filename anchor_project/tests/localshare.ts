@@ -28,7 +28,7 @@ describe("Localshare Lite - Bootstrap Tests", () => {
     assert.ok(program.methods.initConfig, "initConfig should be defined");
     assert.ok(program.methods.registerBusiness, "registerBusiness should be defined");
     assert.ok(program.methods.createOffering, "createOffering should be defined");
-    assert.ok(program.methods.buyShares, "buyShares should be defined");
+    assert.ok(program.methods.buySharesFromOffering, "buySharesFromOffering should be defined");
     
     console.log("✅ All 4 Localshare functions are defined in the program");
   });
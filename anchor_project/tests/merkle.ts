@@ -0,0 +1,56 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { createHash } from "crypto";
+
+/**
+ * Merkle trees, mirroring programs/my_program/src/merkle.rs
+ * Leaves are sha256(0x00 || fields), inner nodes sha256(0x01 || min || max),
+ * and an odd node is promoted unchanged to the next level
+ */
+
+/** Leaf of a (holder, amount) pair, as used by airdrops and cap table snapshots */
+export function holderLeaf(holder: PublicKey, amount: number): Buffer {
+  return createHash("sha256")
+    .update(Buffer.from([0x00]))
+    .update(holder.toBuffer())
+    .update(new anchor.BN(amount).toArrayLike(Buffer, "le", 8))
+    .digest();
+}
+
+function nodeHash(a: Buffer, b: Buffer): Buffer {
+  const [left, right] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+  return createHash("sha256").update(Buffer.from([0x01])).update(left).update(right).digest();
+}
+
+function nextLevel(level: Buffer[]): Buffer[] {
+  const next: Buffer[] = [];
+  for (let i = 0; i < level.length; i += 2) {
+    next.push(i + 1 < level.length ? nodeHash(level[i], level[i + 1]) : level[i]);
+  }
+  return next;
+}
+
+/** Root of the tree over `leaves` */
+export function merkleRoot(leaves: Buffer[]): Buffer {
+  let level = leaves;
+  while (level.length > 1) {
+    level = nextLevel(level);
+  }
+  return level[0];
+}
+
+/** Inclusion proof of the leaf at `index` */
+export function merkleProof(leaves: Buffer[], index: number): Buffer[] {
+  const proof: Buffer[] = [];
+  let level = leaves;
+  let position = index;
+  while (level.length > 1) {
+    const sibling = position ^ 1;
+    if (sibling < level.length) {
+      proof.push(level[sibling]);
+    }
+    level = nextLevel(level);
+    position = Math.floor(position / 2);
+  }
+  return proof;
+}
@@ -0,0 +1,383 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { MyProgram } from "../target/types/my_program";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  ata,
+  BusinessFixture,
+  buyFromRound,
+  chainTime,
+  claimAllocation,
+  commitToSubscription,
+  createBusiness,
+  createRound,
+  createSubscription,
+  expectError,
+  fundedKeypair,
+  pda,
+  settleSubscription,
+  TOKEN_PROGRAM_ID,
+  u32Seed,
+  u64Seed,
+  waitUntil,
+} from "./helpers";
+
+/**
+ * Milestone escrows: approval by admin, oracle or shareholder vote, credited contributions,
+ * refunds after a failed milestone and the sweep
+ * The provider wallet is Config.admin (integration.ts)
+ */
+describe("milestone", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.MyProgram as Program<MyProgram>;
+  const balance = (account: PublicKey) => provider.connection.getBalance(account);
+  const admin = provider.wallet.publicKey;
+  const config = pda(program.programId, "config");
+
+  type Escrowed = { fixture: BusinessFixture; escrow: PublicKey; releaseTo: PublicKey; investor: Keypair };
+
+  const initEscrow = (fixture: BusinessFixture, approval: object) =>
+    program.methods
+      .initMilestoneEscrow(approval as any)
+      .accounts({
+        business: fixture.business,
+        milestoneEscrow: pda(program.programId, "milestone_escrow", fixture.business),
+        owner: fixture.owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([fixture.owner])
+      .rpc();
+
+  /** Business whose escrow holds one investor's 10 shares at 1000 lamports (1% of 1000 shares) */
+  const escrowedBusiness = async (name: string, approval: object): Promise<Escrowed> => {
+    const releaseTo = (await fundedKeypair(provider, 1)).publicKey;
+    const fixture = await createBusiness(program, provider, {
+      name,
+      totalShares: 1_000,
+      price: 1_000,
+      treasury: releaseTo,
+      list: true,
+    });
+    const escrow = pda(program.programId, "milestone_escrow", fixture.business);
+    await initEscrow(fixture, approval);
+    const investor = await fundedKeypair(provider, 1);
+    const round = await createRound(program, fixture, { index: 0, shares: 100 });
+    await buyFromRound(program, fixture, round, investor, 10, 1_000, escrow);
+    return { fixture, escrow, releaseTo, investor };
+  };
+
+  const milestoneOf = (fixture: BusinessFixture, index: number) =>
+    pda(program.programId, "milestone", fixture.business, u32Seed(index));
+
+  const addMilestone = async ({ fixture, escrow }: Escrowed, amount: object, deadline: number, proposalId = 0) => {
+    const index = (await program.account.milestoneEscrow.fetch(escrow)).milestoneCount;
+    await program.methods
+      .addMilestone(Array(32).fill(index + 1), amount as any, new anchor.BN(deadline), new anchor.BN(proposalId))
+      .accounts({
+        business: fixture.business,
+        milestoneEscrow: escrow,
+        milestone: milestoneOf(fixture, index),
+        owner: fixture.owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([fixture.owner])
+      .rpc();
+    return index;
+  };
+
+  const approve = (
+    { fixture, escrow, releaseTo }: Escrowed,
+    index: number,
+    approver?: Keypair,
+    role: PublicKey | null = null,
+    proposal: PublicKey | null = null
+  ) =>
+    program.methods
+      .approveMilestone()
+      .accounts({
+        config,
+        role,
+        milestoneEscrow: escrow,
+        milestone: milestoneOf(fixture, index),
+        proposal,
+        releaseTo,
+        approver: approver?.publicKey ?? admin,
+      })
+      .signers(approver ? [approver] : [])
+      .rpc();
+
+  const fail = ({ fixture, escrow }: Escrowed, index: number, proposal: PublicKey | null = null) =>
+    program.methods
+      .failMilestone()
+      .accounts({ business: fixture.business, milestoneEscrow: escrow, milestone: milestoneOf(fixture, index), proposal })
+      .rpc();
+
+  /** Refund claimed by the escrowed investor */
+  const refundOf = async ({ fixture, escrow, investor }: Escrowed) => {
+    const before = await balance(investor.publicKey);
+    await program.methods
+      .claimMilestoneRefund()
+      .accounts({
+        milestoneEscrow: escrow,
+        position: pda(program.programId, "position", fixture.business, investor.publicKey),
+        refundReceipt: pda(program.programId, "milestone_refund", escrow, investor.publicKey),
+        investor: investor.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([investor])
+      .rpc();
+    return (await balance(investor.publicKey)) - before;
+  };
+
+  /** Releases the milestone and returns the lamports received by the release account */
+  const released = async (escrowed: Escrowed, approval: () => Promise<string>) => {
+    const before = await balance(escrowed.releaseTo);
+    await approval();
+    return (await balance(escrowed.releaseTo)) - before;
+  };
+
+  it("releases admin-approved milestones and refunds the rest once one expires", async () => {
+    const escrowed = await escrowedBusiness("Admin Milestones", { admin: {} });
+    const now = await chainTime(provider);
+    const half = await addMilestone(escrowed, { bps: { 0: 5_000 } }, now + 60);
+    const expiring = await addMilestone(escrowed, { bps: { 0: 5_000 } }, now + 10);
+
+    // Only Config.admin or a Verifier approves, and the owner is neither
+    await expectError(approve(escrowed, half, escrowed.fixture.owner), "UnauthorizedApprover");
+    assert.equal(await released(escrowed, () => approve(escrowed, half)), 5_000);
+    await expectError(approve(escrowed, half), "MilestoneNotPending");
+
+    await expectError(fail(escrowed, expiring), "MilestoneStillOpen");
+    await waitUntil(provider, now + 11);
+    await expectError(approve(escrowed, expiring), "MilestoneExpired");
+    await fail(escrowed, expiring);
+
+    // The 5000 lamports left are the investor's whole refund
+    assert.equal(await refundOf(escrowed), 5_000);
+    assert.equal((await program.account.milestoneEscrow.fetch(escrowed.escrow)).refundPool.toNumber(), 5_000);
+  });
+
+  it("rejects owner oracles and requires the oracle to hold a Verifier role", async () => {
+    // The owner, or the account receiving the releases, can't approve their own milestones
+    const owned = await createBusiness(program, provider, { name: "Self Oracle" });
+    await expectError(initEscrow(owned, { oracle: { signer: owned.owner.publicKey } }), "InvalidOracle");
+    await expectError(initEscrow(owned, { oracle: { signer: owned.treasury } }), "InvalidOracle");
+
+    const oracle = await fundedKeypair(provider, 1);
+    const oracleRole = pda(program.programId, "verifier", oracle.publicKey);
+    const escrowed = await escrowedBusiness("Oracle Milestones", { oracle: { signer: oracle.publicKey } });
+    const now = await chainTime(provider);
+    const fixed = await addMilestone(escrowed, { lamports: { 0: new anchor.BN(3_000) } }, now + 60);
+    const expiring = await addMilestone(escrowed, { lamports: { 0: new anchor.BN(3_000) } }, now + 10);
+
+    // Without a Verifier role the oracle can't approve; the admin isn't the oracle
+    await expectError(approve(escrowed, fixed, oracle), "UnauthorizedApprover");
+    await program.methods
+      .grantRole({ verifier: {} }, oracle.publicKey)
+      .accounts({ config, role: oracleRole, admin, systemProgram: SystemProgram.programId })
+      .rpc();
+    await expectError(approve(escrowed, fixed), "UnauthorizedApprover");
+    assert.equal(await released(escrowed, () => approve(escrowed, fixed, oracle, oracleRole)), 3_000);
+
+    await waitUntil(provider, now + 11);
+    await expectError(approve(escrowed, expiring, oracle, oracleRole), "MilestoneExpired");
+    await fail(escrowed, expiring);
+    assert.equal(await refundOf(escrowed), 7_000);
+  });
+
+  it("releases milestones on a succeeded vote and refunds after a defeated one", async () => {
+    const VOTING_PERIOD = 4;
+    const escrowed = await escrowedBusiness("Voted Milestones", { shareholderVote: {} });
+    const { fixture, investor } = escrowed;
+    const governance = pda(program.programId, "governance", fixture.business);
+    await program.methods
+      .configureGovernance(100, 6_000, new anchor.BN(1))
+      .accounts({ business: fixture.business, governance, owner: fixture.owner.publicKey, systemProgram: SystemProgram.programId })
+      .signers([fixture.owner])
+      .rpc();
+
+    const propose = async () => {
+      const id = (await program.account.governance.fetch(governance)).proposalCount;
+      const proposal = pda(program.programId, "proposal", fixture.business, u64Seed(id));
+      await program.methods
+        .createProposal(`Milestone vote ${id}`, "https://localshare.example/milestones", new anchor.BN(VOTING_PERIOD))
+        .accounts({
+          business: fixture.business,
+          governance,
+          proposal,
+          voteVault: pda(program.programId, "vote_vault", proposal),
+          proposerSharesAta: null,
+          shareMint: fixture.shareMint,
+          sharesVault: fixture.sharesVault,
+          proposer: fixture.owner.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([fixture.owner])
+        .rpc();
+      return { id: id.toNumber(), proposal };
+    };
+    const vote = (proposal: PublicKey, approve: boolean) =>
+      program.methods
+        .castVote(approve, new anchor.BN(10))
+        .accounts({
+          proposal,
+          voteRecord: pda(program.programId, "vote", proposal, investor.publicKey),
+          voteVault: pda(program.programId, "vote_vault", proposal),
+          voterSharesAta: ata(fixture.shareMint, investor.publicKey),
+          voter: investor.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([investor])
+        .rpc();
+    const withdraw = (proposal: PublicKey) =>
+      program.methods
+        .withdrawVoteTokens()
+        .accounts({
+          proposal,
+          voteRecord: pda(program.programId, "vote", proposal, investor.publicKey),
+          voteVault: pda(program.programId, "vote_vault", proposal),
+          voterSharesAta: ata(fixture.shareMint, investor.publicKey),
+          voter: investor.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([investor])
+        .rpc();
+    const decide = async (proposal: PublicKey) => {
+      await waitUntil(provider, (await chainTime(provider)) + VOTING_PERIOD + 1);
+      await program.methods.finalizeProposal().accounts({ proposal }).rpc();
+      await withdraw(proposal);
+    };
+
+    // Proposals opened before a milestone was added can't decide it
+    const deadline = (await chainTime(provider)) + 120;
+    const early = await propose();
+    await waitUntil(provider, (await chainTime(provider)) + 1);
+    const tooLate = await addMilestone(escrowed, { bps: { 0: 4_000 } }, deadline, early.id);
+    await vote(early.proposal, true);
+    await decide(early.proposal);
+    await expectError(approve(escrowed, tooLate, undefined, null, early.proposal), "UnauthorizedApprover");
+
+    // A succeeded vote releases its milestone; any signer submits the approval
+    const nextId = (await program.account.governance.fetch(governance)).proposalCount.toNumber();
+    const voted = await addMilestone(escrowed, { bps: { 0: 4_000 } }, deadline, nextId);
+    const { proposal } = await propose();
+    await expectError(approve(escrowed, voted, undefined, null, proposal), "UnauthorizedApprover");
+    await vote(proposal, true);
+    await decide(proposal);
+    await expectError(approve(escrowed, voted), "UnauthorizedApprover");
+    assert.equal(await released(escrowed, () => approve(escrowed, voted, undefined, null, proposal)), 4_000);
+
+    // A defeated vote fails its milestone before the deadline and opens refunds
+    const rejectedId = (await program.account.governance.fetch(governance)).proposalCount.toNumber();
+    const rejected = await addMilestone(escrowed, { bps: { 0: 4_000 } }, deadline, rejectedId);
+    const defeated = (await propose()).proposal;
+    await vote(defeated, false);
+    await expectError(fail(escrowed, rejected, defeated), "MilestoneStillOpen");
+    await decide(defeated);
+    await fail(escrowed, rejected, defeated);
+    assert.equal(await refundOf(escrowed), 6_000);
+  });
+
+  it("refunds only credited contributions and leaves the rest for the sweep", async () => {
+    const releaseTo = (await fundedKeypair(provider, 1)).publicKey;
+    const fixture = await createBusiness(program, provider, {
+      name: "Milestone Shop",
+      price: 1_000,
+      treasury: releaseTo,
+      list: true,
+    });
+    const escrow = pda(program.programId, "milestone_escrow", fixture.business);
+    await program.methods
+      .initMilestoneEscrow({ oracle: { signer: Keypair.generate().publicKey } })
+      .accounts({
+        business: fixture.business,
+        milestoneEscrow: escrow,
+        owner: fixture.owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([fixture.owner])
+      .rpc();
+
+    // Alice buys from a round, Bob through a subscription: both pay into the escrow
+    const alice = await fundedKeypair(provider, 1);
+    const bob = await fundedKeypair(provider, 1);
+    const round = await createRound(program, fixture, { index: 0, shares: 100 });
+    await buyFromRound(program, fixture, round, alice, 10, 1_000, escrow);
+
+    const startTs = await chainTime(provider);
+    const sale = await createSubscription(program, fixture, { shares: 10, startTs, endTs: startTs + 4 });
+    await commitToSubscription(program, sale, bob, 4_000);
+    await waitUntil(provider, startTs + 4);
+    await settleSubscription(program, fixture, sale);
+    await claimAllocation(program, fixture, sale, bob, escrow);
+
+    // A direct transfer reaches the escrow without being credited to anyone
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        SystemProgram.transfer({ fromPubkey: provider.wallet.publicKey, toPubkey: escrow, lamports: 5_000 })
+      )
+    );
+
+    const positionOf = (investor: Keypair) => pda(program.programId, "position", fixture.business, investor.publicKey);
+    assert.equal((await program.account.investorPosition.fetch(positionOf(alice))).escrowedLamports.toNumber(), 10_000);
+    assert.equal((await program.account.investorPosition.fetch(positionOf(bob))).escrowedLamports.toNumber(), 4_000);
+    assert.equal((await program.account.milestoneEscrow.fetch(escrow)).contributedLamports.toNumber(), 14_000);
+
+    // The milestone's deadline passes without approval
+    const milestone = pda(program.programId, "milestone", fixture.business, u32Seed(0));
+    const deadline = (await chainTime(provider)) + 3;
+    await program.methods
+      .addMilestone(Array(32).fill(1), { lamports: { 0: new anchor.BN(1_000_000) } } as any, new anchor.BN(deadline), new anchor.BN(0))
+      .accounts({
+        business: fixture.business,
+        milestoneEscrow: escrow,
+        milestone,
+        owner: fixture.owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([fixture.owner])
+      .rpc();
+    await waitUntil(provider, deadline + 1);
+    await program.methods
+      .failMilestone()
+      .accounts({ business: fixture.business, milestoneEscrow: escrow, milestone, proposal: null })
+      .rpc();
+
+    const state = await program.account.milestoneEscrow.fetch(escrow);
+    assert.isTrue(state.failed);
+    assert.equal(state.refundPool.toNumber(), 14_000);
+    assert.equal(state.refundBasis.toNumber(), 14_000);
+    assert.isTrue((await program.account.business.fetch(fixture.business)).treasury.equals(releaseTo));
+
+    const refund = (investor: Keypair) =>
+      program.methods
+        .claimMilestoneRefund()
+        .accounts({
+          milestoneEscrow: escrow,
+          position: positionOf(investor),
+          refundReceipt: pda(program.programId, "milestone_refund", escrow, investor.publicKey),
+          investor: investor.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([investor])
+        .rpc();
+
+    // Each investor gets back exactly what they paid in
+    for (const [investor, paid] of [[alice, 10_000], [bob, 4_000]] as [Keypair, number][]) {
+      const before = await balance(escrow);
+      await refund(investor);
+      assert.equal(before - (await balance(escrow)), paid);
+    }
+    await expectError(refund(alice), "already in use");
+
+    // The uncredited transfer stays in the escrow until the refund period is over
+    await expectError(
+      program.methods.sweepMilestoneEscrow().accounts({ milestoneEscrow: escrow, releaseTo }).rpc(),
+      "RefundPeriodOpen"
+    );
+  });
+});
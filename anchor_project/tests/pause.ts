@@ -0,0 +1,116 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { MyProgram } from "../target/types/my_program";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  ata,
+  buyFromRound,
+  chainTime,
+  claimAllocation,
+  commitToSubscription,
+  createBusiness,
+  createRound,
+  createSubscription,
+  expectError,
+  fundedKeypair,
+  pda,
+  setFrozen,
+  settleSubscription,
+  tokenBalance,
+  TOKEN_PROGRAM_ID,
+  transferShares,
+  waitUntil,
+} from "./helpers";
+
+/**
+ * Emergency freeze: no sale can be opened, committed to or claimed while a business is frozen,
+ * and holder token accounts can be frozen until it is lifted
+ */
+describe("pause", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.MyProgram as Program<MyProgram>;
+
+  it("blocks opening rounds and subscriptions while frozen", async () => {
+    const fixture = await createBusiness(program, provider, { name: "Frozen Sales" });
+    await setFrozen(program, fixture, true);
+
+    await expectError(createRound(program, fixture, { index: 0, shares: 10 }), "BusinessFrozen");
+    const now = await chainTime(provider);
+    await expectError(
+      createSubscription(program, fixture, { shares: 10, startTs: now, endTs: now + 60 }),
+      "BusinessFrozen"
+    );
+
+    await setFrozen(program, fixture, false);
+    await createRound(program, fixture, { index: 0, shares: 10 });
+  });
+
+  it("blocks subscription commitments and claims while frozen", async () => {
+    const treasury = (await fundedKeypair(provider, 1)).publicKey;
+    const fixture = await createBusiness(program, provider, { name: "Frozen Subscription", treasury });
+    const startTs = await chainTime(provider);
+    const endTs = startTs + 6;
+    const sale = await createSubscription(program, fixture, { shares: 10, startTs, endTs });
+    const investor: Keypair = await fundedKeypair(provider);
+
+    await setFrozen(program, fixture, true);
+    await expectError(commitToSubscription(program, sale, investor, 2 * sale.price), "BusinessFrozen");
+    await setFrozen(program, fixture, false);
+    await commitToSubscription(program, sale, investor, 2 * sale.price);
+
+    await waitUntil(provider, endTs);
+    await settleSubscription(program, fixture, sale);
+
+    await setFrozen(program, fixture, true);
+    await expectError(claimAllocation(program, fixture, sale, investor), "BusinessFrozen");
+    await setFrozen(program, fixture, false);
+    await claimAllocation(program, fixture, sale, investor);
+
+    assert.equal(await tokenBalance(provider, ata(fixture.shareMint, investor.publicKey)), 2);
+  });
+
+  it("freezes holder token accounts until the business is unfrozen", async () => {
+    const fixture = await createBusiness(program, provider, { name: "Frozen Holders", list: true });
+    const round = await createRound(program, fixture, { index: 0, shares: 10 });
+    const alice = await fundedKeypair(provider);
+    const bob = await fundedKeypair(provider);
+    await buyFromRound(program, fixture, round, alice, 5);
+    await buyFromRound(program, fixture, round, bob, 1);
+    const aliceAta = ata(fixture.shareMint, alice.publicKey);
+
+    // The provider wallet is the protocol admin
+    const setHolders = (frozen: boolean, holders: PublicKey[]) =>
+      (frozen ? program.methods.freezeHolderAccounts() : program.methods.thawHolderAccounts())
+        .accounts({
+          config: pda(program.programId, "config"),
+          role: null,
+          business: fixture.business,
+          businessFreeze: pda(program.programId, "freeze", fixture.business),
+          shareMint: fixture.shareMint,
+          shareMintAuthority: fixture.shareMintAuthority,
+          authority: provider.wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(holders.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
+        .rpc();
+
+    await expectError(setHolders(true, [aliceAta]), "BusinessNotFrozen");
+    await setFrozen(program, fixture, true);
+    await setHolders(true, [aliceAta]);
+
+    // SPL Token rejects transfers out of a frozen account (AccountFrozen = 0x11)
+    await expectError(transferShares(provider, fixture, alice, bob.publicKey, 2), "0x11");
+    assert.equal(await tokenBalance(provider, aliceAta), 5);
+
+    // Holder accounts stay frozen until the business itself is unfrozen
+    await expectError(setHolders(false, [aliceAta]), "BusinessFrozen");
+    await setFrozen(program, fixture, false);
+    await setHolders(false, [aliceAta]);
+
+    await transferShares(provider, fixture, alice, bob.publicKey, 2);
+    assert.equal(await tokenBalance(provider, aliceAta), 3);
+    assert.equal(await tokenBalance(provider, ata(fixture.shareMint, bob.publicKey)), 3);
+  });
+});
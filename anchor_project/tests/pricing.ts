@@ -0,0 +1,102 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { MyProgram } from "../target/types/my_program";
+import { assert } from "chai";
+import {
+  ata,
+  buyFromRound,
+  createBusiness,
+  chainTime,
+  createRound,
+  expectError,
+  fundedKeypair,
+  tokenBalance,
+  waitUntil,
+} from "./helpers";
+
+/**
+ * Offering pricing modes set by create_offering
+ */
+describe("pricing", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.MyProgram as Program<MyProgram>;
+
+  it("splits a tiered purchase at the tier boundary and caps every share", async () => {
+    // Funded treasury: the first payments are below the rent-exempt minimum
+    const treasury = (await fundedKeypair(provider, 1)).publicKey;
+    const fixture = await createBusiness(program, provider, { name: "Tiered Pricing", price: 3_000, treasury, list: true });
+    const tiers = [
+      { sharesInTier: new anchor.BN(10), pricePerShareLamports: new anchor.BN(1_000) },
+      { sharesInTier: new anchor.BN(10), pricePerShareLamports: new anchor.BN(2_000) },
+    ];
+    const round = await createRound(program, fixture, {
+      index: 0,
+      shares: 100,
+      pricing: {
+        tiered: {
+          tiers: [...tiers, ...Array(3).fill({ sharesInTier: new anchor.BN(0), pricePerShareLamports: new anchor.BN(0) })],
+          tierCount: tiers.length,
+        },
+      },
+    });
+    const buyer = await fundedKeypair(provider);
+
+    // 8 shares in the first tier
+    await buyFromRound(program, fixture, round, buyer, 8, 1_000);
+
+    // 2 shares left at 1000, 3 at 2000: a cap below 2000 is rejected even though it covers part of the purchase
+    await expectError(buyFromRound(program, fixture, round, buyer, 5, 1_999), "PriceAboveMax");
+
+    const before = await provider.connection.getBalance(fixture.treasury);
+    await buyFromRound(program, fixture, round, buyer, 5, 2_000);
+    const paid = (await provider.connection.getBalance(fixture.treasury)) - before;
+    assert.equal(paid, 2 * 1_000 + 3 * 2_000);
+
+    // Past the last tier the round's fixed price applies
+    await expectError(buyFromRound(program, fixture, round, buyer, 8, 2_000), "PriceAboveMax");
+    await buyFromRound(program, fixture, round, buyer, 8, 3_000);
+
+    assert.equal(await tokenBalance(provider, ata(fixture.shareMint, buyer.publicKey)), 21);
+  });
+
+  it("decays the Dutch auction price down to the floor and enforces the buyer's maximum", async () => {
+    const treasury = (await fundedKeypair(provider, 1)).publicKey;
+    const fixture = await createBusiness(program, provider, { name: "Dutch Pricing", price: 500, treasury, list: true });
+    const startTs = (await chainTime(provider)) + 3;
+    const endTs = startTs + 8;
+    const round = await createRound(program, fixture, {
+      index: 0,
+      shares: 100,
+      pricing: {
+        dutchAuction: {
+          startPrice: new anchor.BN(10_000),
+          floorPrice: new anchor.BN(1_000),
+          startTs: new anchor.BN(startTs),
+          endTs: new anchor.BN(endTs),
+        },
+      },
+    });
+    const buyer = await fundedKeypair(provider);
+
+    await expectError(buyFromRound(program, fixture, round, buyer, 1, 10_000), "AuctionNotStarted");
+
+    // Right after the start the price is still well above the floor
+    await waitUntil(provider, startTs);
+    await expectError(buyFromRound(program, fixture, round, buyer, 1, 1_000), "PriceAboveMax");
+
+    // Mid-auction the price paid is between the floor and the start price
+    let before = await provider.connection.getBalance(fixture.treasury);
+    await buyFromRound(program, fixture, round, buyer, 1, 10_000);
+    const midPrice = (await provider.connection.getBalance(fixture.treasury)) - before;
+    assert.isAbove(midPrice, 1_000);
+    assert.isAtMost(midPrice, 10_000);
+
+    // After the end the floor price applies
+    await waitUntil(provider, endTs);
+    before = await provider.connection.getBalance(fixture.treasury);
+    await buyFromRound(program, fixture, round, buyer, 3, 1_000);
+    assert.equal((await provider.connection.getBalance(fixture.treasury)) - before, 3 * 1_000);
+    assert.equal(await tokenBalance(provider, ata(fixture.shareMint, buyer.publicKey)), 4);
+  });
+});
@@ -0,0 +1,177 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { MyProgram } from "../target/types/my_program";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  ata,
+  BusinessFixture,
+  buyFromRound,
+  createBusiness,
+  createRound,
+  expectError,
+  fundedKeypair,
+  pda,
+  TOKEN_PROGRAM_ID,
+} from "./helpers";
+
+/**
+ * Revenue share: the revenue-per-share accumulator over staked shares
+ */
+describe("revenue", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.MyProgram as Program<MyProgram>;
+
+  let fixture: BusinessFixture;
+  let stakePool: PublicKey;
+  let stakeVault: PublicKey;
+  let revenueShare: PublicKey;
+  let alice: Keypair;
+  let bob: Keypair;
+  let carol: Keypair;
+
+  const stakeAccounts = (holder: Keypair) => ({
+    business: fixture.business,
+    stakePool,
+    revenueShare,
+    stakeVault,
+    stakePosition: pda(program.programId, "stake", fixture.business, holder.publicKey),
+    holderSharesAta: ata(fixture.shareMint, holder.publicKey),
+    holder: holder.publicKey,
+    tokenProgram: TOKEN_PROGRAM_ID,
+  });
+
+  const stake = (holder: Keypair, amount: number) =>
+    program.methods
+      .stakeShares(new anchor.BN(amount))
+      .accounts({ ...stakeAccounts(holder), systemProgram: SystemProgram.programId })
+      .signers([holder])
+      .rpc();
+
+  const unstake = (holder: Keypair, amount: number) =>
+    program.methods
+      .unstakeShares(new anchor.BN(amount))
+      .accounts(stakeAccounts(holder))
+      .signers([holder])
+      .rpc();
+
+  const deposit = (depositor: Keypair, amount: number) =>
+    program.methods
+      .depositRevenue(new anchor.BN(amount))
+      .accounts({
+        business: fixture.business,
+        stakePool,
+        revenueShare,
+        depositor: depositor.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([depositor])
+      .rpc();
+
+  const claim = (holder: Keypair) =>
+    program.methods
+      .claimRevenue()
+      .accounts({
+        business: fixture.business,
+        revenueShare,
+        stakePosition: pda(program.programId, "stake", fixture.business, holder.publicKey),
+        holder: holder.publicKey,
+      })
+      .signers([holder])
+      .rpc();
+
+  /** Lamports paid to `holder` by a claim (the provider wallet pays the fee) */
+  const claimed = async (holder: Keypair) => {
+    const before = await provider.connection.getBalance(holder.publicKey);
+    await claim(holder);
+    return (await provider.connection.getBalance(holder.publicKey)) - before;
+  };
+
+  before(async () => {
+    fixture = await createBusiness(program, provider, { name: "Revenue Shop", price: 1_000, list: true });
+    stakePool = pda(program.programId, "stake_pool", fixture.business);
+    stakeVault = pda(program.programId, "stake_vault", fixture.business);
+    revenueShare = pda(program.programId, "revenue_share", fixture.business);
+
+    const round = await createRound(program, fixture, { index: 0, shares: 100 });
+    alice = await fundedKeypair(provider, 1);
+    bob = await fundedKeypair(provider, 1);
+    carol = await fundedKeypair(provider, 1);
+    for (const holder of [alice, bob, carol]) {
+      await buyFromRound(program, fixture, round, holder, 30);
+    }
+
+    await program.methods
+      .initStakePool()
+      .accounts({
+        business: fixture.business,
+        stakePool,
+        stakeVault,
+        shareMint: fixture.shareMint,
+        payer: fixture.owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([fixture.owner])
+      .rpc();
+    await program.methods
+      .initRevenueShare(1_000, Keypair.generate().publicKey)
+      .accounts({
+        business: fixture.business,
+        stakePool,
+        revenueShare,
+        owner: fixture.owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([fixture.owner])
+      .rpc();
+  });
+
+  it("requires staked shares and an authorized depositor", async () => {
+    await expectError(deposit(fixture.owner, 1_000), "NoStakedShares");
+    await stake(alice, 30);
+    await stake(bob, 10);
+    await expectError(deposit(carol, 1_000), "UnauthorizedDepositor");
+  });
+
+  it("splits each deposit by stake and only pays later stakers later revenue", async () => {
+    // 4000 over 40 staked shares: 3000 for alice, 1000 for bob
+    await deposit(fixture.owner, 4_000);
+    assert.equal(await claimed(alice), 3_000);
+    await expectError(claim(alice), "NothingToClaim");
+
+    // Carol stakes after the first deposit: 6000 over 60 shares
+    await stake(carol, 20);
+    await deposit(fixture.owner, 6_000);
+    assert.equal(await claimed(carol), 2_000);
+    assert.equal(await claimed(alice), 3_000);
+    assert.equal(await claimed(bob), 1_000 + 1_000);
+
+    const state = await program.account.revenueShare.fetch(revenueShare);
+    assert.equal(state.totalDeposited.toNumber(), 10_000);
+    assert.equal(state.totalClaimed.toNumber(), 10_000);
+  });
+
+  it("keeps revenue accrued before unstaking claimable", async () => {
+    await deposit(fixture.owner, 6_000);
+    await unstake(alice, 30);
+
+    // Alice no longer earns, but keeps her 3000 from before unstaking
+    await deposit(fixture.owner, 3_000);
+    assert.equal(await claimed(alice), 3_000);
+    assert.equal(await claimed(bob), 1_000 + 1_000);
+    assert.equal(await claimed(carol), 2_000 + 2_000);
+  });
+
+  it("rejects stake changes that skip the revenue share", async () => {
+    await expectError(
+      program.methods
+        .stakeShares(new anchor.BN(1))
+        .accounts({ ...stakeAccounts(alice), revenueShare: null, systemProgram: SystemProgram.programId })
+        .signers([alice])
+        .rpc(),
+      "RevenueShareRequired"
+    );
+  });
+});
@@ -0,0 +1,146 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { MyProgram } from "../target/types/my_program";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { BusinessFixture, createBusiness, expectError, fundedKeypair, pda } from "./helpers";
+import { businessIndexPages } from "./search";
+
+/**
+ * Role registry: grants and revocations by Config.admin, and role-gated moderation
+ * The provider wallet is Config.admin (integration.ts)
+ */
+describe("roles", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.MyProgram as Program<MyProgram>;
+  const admin = provider.wallet.publicKey;
+  const config = pda(program.programId, "config");
+
+  let verifier: Keypair;
+  let pauser: Keypair;
+  let stranger: Keypair;
+
+  const roleOf = (kind: "verifier" | "pauser", grantee: PublicKey) => pda(program.programId, kind, grantee);
+
+  const grant = (kind: "verifier" | "pauser", grantee: PublicKey) =>
+    program.methods
+      .grantRole({ [kind]: {} } as any, grantee)
+      .accounts({ config, role: roleOf(kind, grantee), admin, systemProgram: SystemProgram.programId })
+      .rpc();
+
+  const setPolicy = (signer: Keypair, role: PublicKey | null) =>
+    program.methods
+      .setVerificationPolicy(false)
+      .accounts({
+        config,
+        role,
+        verificationPolicy: pda(program.programId, "verification_policy"),
+        authority: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+
+  const verificationOf = (fixture: BusinessFixture) => pda(program.programId, "verification", fixture.business);
+
+  const reinstate = (fixture: BusinessFixture, signer: Keypair, role: PublicKey | null) =>
+    program.methods
+      .reinstateBusiness()
+      .accounts({ config, role, verification: verificationOf(fixture), reviewer: signer.publicKey })
+      .signers([signer])
+      .rpc();
+
+  const flag = (fixture: BusinessFixture, signer: Keypair, role: PublicKey | null) =>
+    program.methods
+      .flagOverdueReport()
+      .accounts({
+        config,
+        role,
+        business: fixture.business,
+        reporting: pda(program.programId, "reporting", fixture.business),
+        reviewer: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+
+  before(async () => {
+    verifier = await fundedKeypair(provider, 1);
+    pauser = await fundedKeypair(provider, 1);
+    stranger = await fundedKeypair(provider, 1);
+  });
+
+  it("lets only the admin grant roles, once per kind and grantee", async () => {
+    await expectError(
+      program.methods
+        .grantRole({ verifier: {} }, stranger.publicKey)
+        .accounts({
+          config,
+          role: roleOf("verifier", stranger.publicKey),
+          admin: stranger.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc(),
+      "UnauthorizedRole"
+    );
+
+    await grant("verifier", verifier.publicKey);
+    await grant("pauser", pauser.publicKey);
+    const role = await program.account.role.fetch(roleOf("verifier", verifier.publicKey));
+    assert.isTrue(role.grantee.equals(verifier.publicKey));
+    assert.isTrue(role.grantedBy.equals(admin));
+    await expectError(grant("verifier", verifier.publicKey), "already in use");
+  });
+
+  it("gates the verification policy on the Verifier role", async () => {
+    await expectError(setPolicy(stranger, null), "UnauthorizedRole");
+    await expectError(setPolicy(pauser, null), "UnauthorizedRole");
+    await setPolicy(verifier, roleOf("verifier", verifier.publicKey));
+    const policy = await program.account.verificationPolicy.fetch(pda(program.programId, "verification_policy"));
+    assert.isFalse(policy.requireVerifiedListing);
+  });
+
+  it("lets a Pauser reinstate a suspended business and a Verifier flag overdue reporting", async () => {
+    const fixture = await createBusiness(program, provider, { name: "Role Gated", list: true });
+    const business = await program.account.business.fetch(fixture.business);
+    await program.methods
+      .suspendBusiness()
+      .accounts({
+        config,
+        role: roleOf("pauser", pauser.publicKey),
+        business: fixture.business,
+        registryPage: fixture.registryPage,
+        ...businessIndexPages(program.programId, business),
+        verification: verificationOf(fixture),
+        reviewer: pauser.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([pauser])
+      .rpc();
+
+    await expectError(reinstate(fixture, stranger, null), "UnauthorizedRole");
+    await reinstate(fixture, pauser, roleOf("pauser", pauser.publicKey));
+    const verification = await program.account.businessVerification.fetch(verificationOf(fixture));
+    assert.equal(Object.keys(verification.status)[0], "unverified");
+    assert.isTrue(verification.reviewer.equals(pauser.publicKey));
+
+    // Only a Verifier gets past the role check; a business listed just now isn't overdue yet
+    const listed = await createBusiness(program, provider, { name: "Role Gated Reports", list: true });
+    await expectError(flag(listed, pauser, null), "UnauthorizedRole");
+    await expectError(flag(listed, verifier, roleOf("verifier", verifier.publicKey)), "ReportNotOverdue");
+    assert.isNull(await program.account.reportingStatus.fetchNullable(pda(program.programId, "reporting", listed.business)));
+  });
+
+  it("revokes a role so its holder loses access", async () => {
+    await program.methods
+      .revokeRole()
+      .accounts({ config, role: roleOf("verifier", verifier.publicKey), admin })
+      .rpc();
+    assert.isNull(await program.account.role.fetchNullable(roleOf("verifier", verifier.publicKey)));
+
+    await expectError(setPolicy(verifier, null), "UnauthorizedRole");
+    await expectError(setPolicy(verifier, roleOf("verifier", verifier.publicKey)), "AccountNotInitialized");
+  });
+});
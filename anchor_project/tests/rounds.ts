@@ -0,0 +1,102 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { MyProgram } from "../target/types/my_program";
+import { assert } from "chai";
+import {
+  buyFromRound,
+  chainTime,
+  createBusiness,
+  createRound,
+  expectError,
+  fundedKeypair,
+  pda,
+  u32Seed,
+  tokenBalance,
+  waitUntil,
+} from "./helpers";
+
+/**
+ * Funding rounds: per-business stats, rounds that end by time and layout migrations
+ */
+describe("rounds", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.MyProgram as Program<MyProgram>;
+
+  it("lets anyone close a round after its end_ts and counts it as completed", async () => {
+    const treasury = (await fundedKeypair(provider, 1)).publicKey;
+    const fixture = await createBusiness(program, provider, { name: "Timed Round", treasury, list: true });
+    const endTs = (await chainTime(provider)) + 4;
+    const round = await createRound(program, fixture, { index: 0, shares: 50, endTs });
+    const buyer = await fundedKeypair(provider);
+    await buyFromRound(program, fixture, round, buyer, 10);
+
+    const closeAccounts = (authority: anchor.web3.PublicKey) => ({
+      offering: round.offering,
+      business: fixture.business,
+      businessStats: round.businessStats,
+      offeringVault: round.offeringVault,
+      sharesVault: fixture.sharesVault,
+      authority,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+    });
+    const stranger = await fundedKeypair(provider, 1);
+
+    // Before the end only the owner can close the round
+    await expectError(
+      program.methods.closeOffering().accounts(closeAccounts(stranger.publicKey)).signers([stranger]).rpc(),
+      "InvalidBusinessOwner"
+    );
+
+    await waitUntil(provider, endTs);
+    await expectError(buyFromRound(program, fixture, round, buyer, 1), "RoundNotOpen");
+
+    const vaultBefore = await tokenBalance(provider, fixture.sharesVault);
+    await program.methods.closeOffering().accounts(closeAccounts(stranger.publicKey)).signers([stranger]).rpc();
+
+    assert.equal(await tokenBalance(provider, fixture.sharesVault), vaultBefore + 40);
+    const offering = await program.account.offering.fetch(round.offering);
+    assert.isFalse(offering.isActive);
+    assert.equal(offering.remainingShares.toNumber(), 0);
+
+    const stats = await program.account.businessStats.fetch(round.businessStats);
+    assert.equal(stats.roundsOpened, 1);
+    assert.equal(stats.roundsCompleted, 1);
+    assert.equal(stats.totalSharesSold.toNumber(), 10);
+    assert.equal(stats.totalRaisedLamports.toNumber(), 10 * fixture.price);
+  });
+
+  it("only lets the owner migrate offerings that predate the current layout", async () => {
+    const fixture = await createBusiness(program, provider, { name: "Migrated Round", list: true });
+    const round = await createRound(program, fixture, { index: 0, shares: 20 });
+    const other = await createBusiness(program, provider, { name: "Other Migrated Round", list: true });
+
+    const migrate = (index: number, owner = fixture.owner) =>
+      program.methods
+        .migrateOffering(index)
+        .accounts({
+          business: fixture.business,
+          offering: pda(program.programId, "offering", fixture.business, u32Seed(index)),
+          owner: owner.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+
+    const before = await provider.connection.getAccountInfo(round.offering);
+
+    // Offerings created by create_offering are already at the current version
+    await expectError(migrate(0), "AccountAlreadyMigrated");
+    await expectError(migrate(0, other.owner), "InvalidBusinessOwner");
+    // No offering at this index: nothing to migrate
+    await expectError(migrate(1), "InvalidBusiness");
+
+    // Rejected migrations leave the account untouched
+    const after = await provider.connection.getAccountInfo(round.offering);
+    assert.equal(after.data.length, before.data.length);
+    assert.equal(after.lamports, before.lamports);
+    assert.isTrue(after.data.equals(before.data));
+    const offering = await program.account.offering.fetch(round.offering);
+    assert.equal(offering.remainingShares.toNumber(), 20);
+  });
+});
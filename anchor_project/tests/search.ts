@@ -0,0 +1,85 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { MyProgram } from "../target/types/my_program";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+
+/**
+ * Category and region search indexes, mirroring SearchIndex in programs/my_program/src/lib.rs
+ * Used to derive the index pages passed to register_business, list_business, ...
+ */
+
+export type IndexKind = "category" | "region";
+
+/** Index of each BusinessCategory variant (the category index key) */
+export const CATEGORIES = {
+  uncategorized: 0,
+  cafe: 1,
+  restaurant: 2,
+  bakery: 3,
+  grocery: 4,
+  retail: 5,
+  services: 6,
+  health: 7,
+  beauty: 8,
+  farm: 9,
+  other: 10,
+};
+
+/** Index key of a category or region: [category, 0, 0, 0] or the first 4 geohash characters */
+export function indexKey(kind: IndexKind, value: number | string): Buffer {
+  return kind === "category"
+    ? Buffer.from([value as number, 0, 0, 0])
+    : Buffer.from((value as string).slice(0, 4));
+}
+
+/** Index page PDA: ["category_index" | "region_index", key, page] */
+export function searchIndexPda(programId: PublicKey, kind: IndexKind, key: Buffer, page: number): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(`${kind}_index`), key, new anchor.BN(page).toArrayLike(Buffer, "le", 4)],
+    programId
+  )[0];
+}
+
+/** Pages holding a registered business's category and region entries */
+export function businessIndexPages(
+  programId: PublicKey,
+  business: { category: object; region: number[]; categoryIndexPage: number; regionIndexPage: number }
+): { categoryIndex: PublicKey; regionIndex: PublicKey } {
+  const category = CATEGORIES[Object.keys(business.category)[0] as keyof typeof CATEGORIES];
+  const region = Buffer.from(business.region).toString();
+  return {
+    categoryIndex: searchIndexPda(programId, "category", indexKey("category", category), business.categoryIndexPage),
+    regionIndex: searchIndexPda(programId, "region", indexKey("region", region), business.regionIndexPage),
+  };
+}
+
+/** Finds the first index page with room (an unused or zeroed slot), creating it if needed */
+export async function indexPageWithRoom(
+  program: Program<MyProgram>,
+  payer: Keypair,
+  kind: IndexKind,
+  value: number | string
+): Promise<PublicKey> {
+  const key = indexKey(kind, value);
+  for (let page = 0; ; page++) {
+    const indexPage = searchIndexPda(program.programId, kind, key, page);
+    const account = await program.account.searchIndex.fetchNullable(indexPage);
+    if (account === null) {
+      await program.methods
+        .initSearchIndexPage(kind === "category" ? { category: {} } : { region: {} }, Array.from(key), page)
+        .accounts({
+          indexPage,
+          previousPage: page === 0 ? null : searchIndexPda(program.programId, kind, key, page - 1),
+          payer: payer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([payer])
+        .rpc();
+      return indexPage;
+    }
+    const freed = account.entries.slice(0, account.count).some((entry) => entry.business.equals(PublicKey.default));
+    if (account.count < account.entries.length || freed) {
+      return indexPage;
+    }
+  }
+}
@@ -0,0 +1,52 @@
+import { PublicKey } from "@solana/web3.js";
+import { createHash } from "crypto";
+
+/**
+ * Business name slugs, mirroring programs/my_program/src/slug.rs
+ * Used to derive the NameRecord PDA passed to register_business
+ */
+
+const FOLDS: Record<string, string> = {
+  à: "a", á: "a", â: "a", ã: "a", ä: "a", å: "a", À: "a", Á: "a", Â: "a", Ã: "a", Ä: "a", Å: "a",
+  ç: "c", Ç: "c",
+  è: "e", é: "e", ê: "e", ë: "e", È: "e", É: "e", Ê: "e", Ë: "e",
+  ì: "i", í: "i", î: "i", ï: "i", Ì: "i", Í: "i", Î: "i", Ï: "i",
+  ñ: "n", Ñ: "n",
+  ò: "o", ó: "o", ô: "o", õ: "o", ö: "o", ø: "o", Ò: "o", Ó: "o", Ô: "o", Õ: "o", Ö: "o", Ø: "o",
+  ù: "u", ú: "u", û: "u", ü: "u", Ù: "u", Ú: "u", Û: "u", Ü: "u",
+  ý: "y", ÿ: "y", Ý: "y",
+  ß: "ss",
+  æ: "ae", Æ: "ae",
+  œ: "oe", Œ: "oe",
+};
+
+/** Combining diacritical marks, dropped like in slug.rs */
+const COMBINING_MARK = /[\u0300-\u036F\u1AB0-\u1AFF\u1DC0-\u1DFF\u20D0-\u20FF\uFE20-\uFE2F]/;
+
+/** Normalizes a business name into its slug ("Café da Esquina" → "cafe-da-esquina") */
+export function slugify(name: string): string {
+  let slug = "";
+  let separator = false;
+  for (const c of name) {
+    if (COMBINING_MARK.test(c)) {
+      continue;
+    }
+    const folded = /^[A-Za-z0-9]$/.test(c) ? c.toLowerCase() : FOLDS[c];
+    if (folded === undefined) {
+      separator = true;
+      continue;
+    }
+    if (separator && slug.length > 0) {
+      slug += "-";
+    }
+    separator = false;
+    slug += folded;
+  }
+  return slug;
+}
+
+/** NameRecord PDA reserving the slug of `name`: ["name", sha256(slug)] */
+export function nameRecordPda(programId: PublicKey, name: string): PublicKey {
+  const seed = createHash("sha256").update(slugify(name)).digest();
+  return PublicKey.findProgramAddressSync([Buffer.from("name"), seed], programId)[0];
+}
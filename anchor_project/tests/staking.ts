@@ -0,0 +1,193 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { MyProgram } from "../target/types/my_program";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  ata,
+  BusinessFixture,
+  buyFromRound,
+  chainTime,
+  createBusiness,
+  createRound,
+  expectError,
+  fundedKeypair,
+  pda,
+  TOKEN_PROGRAM_ID,
+  tokenBalance,
+  u64Seed,
+  waitUntil,
+} from "./helpers";
+
+/**
+ * Share staking: staked voting weight and the vote lock on unstaking
+ */
+describe("staking", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.MyProgram as Program<MyProgram>;
+
+  let fixture: BusinessFixture;
+  let stakePool: PublicKey;
+  let stakeVault: PublicKey;
+  let governance: PublicKey;
+  let alice: Keypair;
+
+  const stakeAccounts = (holder: Keypair) => ({
+    business: fixture.business,
+    stakePool,
+    revenueShare: null,
+    stakeVault,
+    stakePosition: pda(program.programId, "stake", fixture.business, holder.publicKey),
+    holderSharesAta: ata(fixture.shareMint, holder.publicKey),
+    holder: holder.publicKey,
+    tokenProgram: TOKEN_PROGRAM_ID,
+  });
+
+  const stake = (holder: Keypair, amount: number) =>
+    program.methods
+      .stakeShares(new anchor.BN(amount))
+      .accounts({ ...stakeAccounts(holder), systemProgram: SystemProgram.programId })
+      .signers([holder])
+      .rpc();
+
+  const unstake = (holder: Keypair, amount: number) =>
+    program.methods
+      .unstakeShares(new anchor.BN(amount))
+      .accounts(stakeAccounts(holder))
+      .signers([holder])
+      .rpc();
+
+  const stakedVote = (proposal: PublicKey, voter: Keypair, approve: boolean) =>
+    program.methods
+      .castStakedVote(approve)
+      .accounts({
+        proposal,
+        voteRecord: pda(program.programId, "staked_vote", proposal, voter.publicKey),
+        stakePosition: pda(program.programId, "stake", fixture.business, voter.publicKey),
+        voter: voter.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+  const walletVote = (proposal: PublicKey, voter: Keypair, approve: boolean, amount: number) =>
+    program.methods
+      .castVote(approve, new anchor.BN(amount))
+      .accounts({
+        proposal,
+        voteRecord: pda(program.programId, "vote", proposal, voter.publicKey),
+        voteVault: pda(program.programId, "vote_vault", proposal),
+        voterSharesAta: ata(fixture.shareMint, voter.publicKey),
+        voter: voter.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+  const propose = async (votingPeriod: number) => {
+    const id = (await program.account.governance.fetch(governance)).proposalCount;
+    const proposal = pda(program.programId, "proposal", fixture.business, u64Seed(id));
+    await program.methods
+      .createProposal("Staked vote", "https://localshare.example/proposals/staked", new anchor.BN(votingPeriod))
+      .accounts({
+        business: fixture.business,
+        governance,
+        proposal,
+        voteVault: pda(program.programId, "vote_vault", proposal),
+        proposerSharesAta: null,
+        shareMint: fixture.shareMint,
+        sharesVault: fixture.sharesVault,
+        proposer: fixture.owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([fixture.owner])
+      .rpc();
+    return proposal;
+  };
+
+  before(async () => {
+    fixture = await createBusiness(program, provider, { name: "Staking Shop", totalShares: 1_000, list: true });
+    stakePool = pda(program.programId, "stake_pool", fixture.business);
+    stakeVault = pda(program.programId, "stake_vault", fixture.business);
+    governance = pda(program.programId, "governance", fixture.business);
+
+    const round = await createRound(program, fixture, { index: 0, shares: 100 });
+    alice = await fundedKeypair(provider, 5);
+    await buyFromRound(program, fixture, round, alice, 50);
+
+    await program.methods
+      .initStakePool()
+      .accounts({
+        business: fixture.business,
+        stakePool,
+        stakeVault,
+        shareMint: fixture.shareMint,
+        payer: fixture.owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([fixture.owner])
+      .rpc();
+    await program.methods
+      .configureGovernance(100, 5_001, new anchor.BN(0))
+      .accounts({
+        business: fixture.business,
+        governance,
+        owner: fixture.owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([fixture.owner])
+      .rpc();
+  });
+
+  it("counts every share once across staked and wallet votes", async () => {
+    await stake(alice, 40);
+    assert.equal(await tokenBalance(provider, stakeVault), 40);
+    assert.equal(await tokenBalance(provider, ata(fixture.shareMint, alice.publicKey)), 10);
+
+    // Only stake held before the proposal opened counts
+    await waitUntil(provider, (await chainTime(provider)) + 1);
+    const proposal = await propose(6);
+    await stakedVote(proposal, alice, true);
+    await expectError(stakedVote(proposal, alice, false), "already in use");
+
+    // The 10 shares left in the wallet vote separately and are locked in the vote vault
+    await walletVote(proposal, alice, true, 10);
+    assert.equal(await tokenBalance(provider, ata(fixture.shareMint, alice.publicKey)), 0);
+
+    const state = await program.account.proposal.fetch(proposal);
+    assert.equal(state.yesVotes.toNumber(), 50);
+    assert.equal(state.noVotes.toNumber(), 0);
+  });
+
+  it("locks voting stake until the end of the vote", async () => {
+    const proposal = await propose(6);
+    await stakedVote(proposal, alice, false);
+    const { votingEnd } = await program.account.proposal.fetch(proposal);
+    const position = await program.account.stakePosition.fetch(
+      pda(program.programId, "stake", fixture.business, alice.publicKey)
+    );
+    assert.equal(position.lockedUntil.toNumber(), votingEnd.toNumber());
+
+    await expectError(unstake(alice, 1), "StakeLocked");
+
+    await waitUntil(provider, votingEnd.toNumber());
+    await unstake(alice, 40);
+    assert.equal(await tokenBalance(provider, stakeVault), 0);
+  });
+
+  it("ignores shares staked after the proposal opened", async () => {
+    const open = await propose(6);
+    await stake(alice, 40);
+    await expectError(stakedVote(open, alice, true), "InsufficientVotingPower");
+
+    // The same stake votes on proposals opened after it
+    await waitUntil(provider, (await chainTime(provider)) + 1);
+    const later = await propose(6);
+    await stakedVote(later, alice, true);
+    assert.equal((await program.account.proposal.fetch(later)).yesVotes.toNumber(), 40);
+  });
+});
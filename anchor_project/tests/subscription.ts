@@ -0,0 +1,141 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { MyProgram } from "../target/types/my_program";
+import { assert } from "chai";
+import {
+  ata,
+  chainTime,
+  claimAllocation,
+  commitToSubscription,
+  createBusiness,
+  createSubscription,
+  expectError,
+  fundedKeypair,
+  pda,
+  settleSubscription,
+  TOKEN_PROGRAM_ID,
+  tokenBalance,
+  waitUntil,
+} from "./helpers";
+
+/**
+ * Fair-launch subscriptions: pro-rata allocation, refunds of the excess and of unclaimed commitments
+ */
+describe("subscription", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.MyProgram as Program<MyProgram>;
+  const balance = (account: anchor.web3.PublicKey) => provider.connection.getBalance(account);
+
+  it("allocates an oversubscribed sale pro-rata and refunds the excess", async () => {
+    const fixture = await createBusiness(program, provider, { name: "Oversubscribed" });
+    const startTs = await chainTime(provider);
+    const endTs = startTs + 8;
+    const sale = await createSubscription(program, fixture, { shares: 10, startTs, endTs });
+    const alice = await fundedKeypair(provider, 20);
+    const bob = await fundedKeypair(provider, 20);
+    const carol = await fundedKeypair(provider, 20);
+
+    // 15 + 4 + 1 = 20 shares demanded for 10 offered; repeated commits accumulate
+    await commitToSubscription(program, sale, alice, 10 * sale.price);
+    await commitToSubscription(program, sale, alice, 5 * sale.price);
+    await commitToSubscription(program, sale, bob, 4 * sale.price);
+    await commitToSubscription(program, sale, carol, sale.price);
+    await expectError(commitToSubscription(program, sale, bob, sale.price - 1), "InvalidShareAmount");
+    await expectError(settleSubscription(program, fixture, sale), "SubscriptionOpen");
+    await expectError(claimAllocation(program, fixture, sale, alice), "SubscriptionNotSettled");
+
+    await waitUntil(provider, endTs);
+    await expectError(commitToSubscription(program, sale, bob, sale.price), "SubscriptionClosed");
+    await settleSubscription(program, fixture, sale);
+    await expectError(settleSubscription(program, fixture, sale), "SubscriptionSettled");
+
+    const state = await program.account.subscription.fetch(sale.subscription);
+    assert.equal(state.sharesDemanded.toNumber(), 20);
+    assert.equal(state.commitmentCount, 3);
+
+    // Alice: 15 * 10 / 20 = 7.5 → 7 shares, paying 7 and getting 8 back
+    const treasuryBefore = await balance(fixture.treasury);
+    const escrowBefore = await balance(sale.subscription);
+    await claimAllocation(program, fixture, sale, alice);
+    assert.equal(await tokenBalance(provider, ata(fixture.shareMint, alice.publicKey)), 7);
+    assert.equal((await balance(fixture.treasury)) - treasuryBefore, 7 * sale.price);
+    assert.equal(escrowBefore - (await balance(sale.subscription)), 15 * sale.price);
+    const position = await program.account.investorPosition.fetch(
+      pda(program.programId, "position", fixture.business, alice.publicKey)
+    );
+    assert.equal(position.sharesBought.toNumber(), 7);
+    assert.equal(position.lamportsSpent.toNumber(), 7 * sale.price);
+
+    // The commitment is closed, so it can't be claimed twice
+    await expectError(claimAllocation(program, fixture, sale, alice), "AccountNotInitialized");
+
+    // Bob: 4 * 10 / 20 = 2 shares
+    await claimAllocation(program, fixture, sale, bob);
+    assert.equal(await tokenBalance(provider, ata(fixture.shareMint, bob.publicKey)), 2);
+
+    const stats = await program.account.businessStats.fetch(sale.businessStats);
+    assert.equal(stats.totalSharesSold.toNumber(), 9);
+    assert.equal(stats.totalRaisedLamports.toNumber(), 9 * sale.price);
+
+    // Carol hasn't claimed: her commitment can only be refunded after the claim deadline
+    const refund = () =>
+      program.methods
+        .refundUnclaimedCommitment()
+        .accounts({
+          subscription: sale.subscription,
+          commitment: pda(program.programId, "commitment", sale.subscription, carol.publicKey),
+          investor: carol.publicKey,
+        })
+        .rpc();
+    await expectError(refund(), "ClaimPeriodOpen");
+
+    const close = () =>
+      program.methods
+        .closeSubscription()
+        .accounts({
+          business: fixture.business,
+          subscription: sale.subscription,
+          subscriptionVault: sale.subscriptionVault,
+          sharesVault: fixture.sharesVault,
+          owner: fixture.owner.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([fixture.owner])
+        .rpc();
+    await expectError(close(), "AllocationsPending");
+
+    // Carol: 1 * 10 / 20 = 0.5 → no shares, the whole commitment is refunded
+    const escrowBeforeCarol = await balance(sale.subscription);
+    await claimAllocation(program, fixture, sale, carol);
+    assert.equal(await tokenBalance(provider, ata(fixture.shareMint, carol.publicKey)), 0);
+    assert.equal(escrowBeforeCarol - (await balance(sale.subscription)), sale.price);
+
+    // The rounding leftover goes back to the shares vault
+    const vaultBefore = await tokenBalance(provider, fixture.sharesVault);
+    await close();
+    assert.equal(await tokenBalance(provider, fixture.sharesVault), vaultBefore + 1);
+    assert.isNull(await program.account.subscription.fetchNullable(sale.subscription));
+  });
+
+  it("returns unsubscribed shares at settlement when demand is below supply", async () => {
+    const fixture = await createBusiness(program, provider, { name: "Undersubscribed" });
+    const startTs = await chainTime(provider);
+    const endTs = startTs + 4;
+    const sale = await createSubscription(program, fixture, { shares: 10, startTs, endTs });
+    const alice = await fundedKeypair(provider, 10);
+
+    // 3.5 shares worth: 3 allocated, half a share refunded
+    await commitToSubscription(program, sale, alice, 3.5 * sale.price);
+    await waitUntil(provider, endTs);
+
+    const vaultBefore = await tokenBalance(provider, fixture.sharesVault);
+    await settleSubscription(program, fixture, sale);
+    assert.equal(await tokenBalance(provider, fixture.sharesVault), vaultBefore + 7);
+
+    const treasuryBefore = await balance(fixture.treasury);
+    await claimAllocation(program, fixture, sale, alice);
+    assert.equal(await tokenBalance(provider, ata(fixture.shareMint, alice.publicKey)), 3);
+    assert.equal((await balance(fixture.treasury)) - treasuryBefore, 3 * sale.price);
+  });
+});
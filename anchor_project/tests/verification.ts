@@ -0,0 +1,168 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { MyProgram } from "../target/types/my_program";
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  ata,
+  BusinessFixture,
+  buyFromRound,
+  chainTime,
+  claimAllocation,
+  commitToSubscription,
+  createBusiness,
+  createRound,
+  createSubscription,
+  expectError,
+  fundedKeypair,
+  pda,
+  settleSubscription,
+  tokenBalance,
+  waitUntil,
+} from "./helpers";
+import { businessIndexPages } from "./search";
+
+/**
+ * Business verification: request/review state machine and suspension of fraudulent listings
+ * The provider wallet is Config.admin (integration.ts), which holds every role implicitly
+ */
+describe("verification", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.MyProgram as Program<MyProgram>;
+  const admin = provider.wallet.publicKey;
+  const config = pda(program.programId, "config");
+
+  const verificationOf = (fixture: BusinessFixture) => pda(program.programId, "verification", fixture.business);
+  const status = async (fixture: BusinessFixture) =>
+    Object.keys((await program.account.businessVerification.fetch(verificationOf(fixture))).status)[0];
+
+  const request = (fixture: BusinessFixture) =>
+    program.methods
+      .requestVerification()
+      .accounts({
+        business: fixture.business,
+        verification: verificationOf(fixture),
+        owner: fixture.owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([fixture.owner])
+      .rpc();
+
+  const review = (fixture: BusinessFixture, approve: boolean, reviewer?: Keypair) =>
+    program.methods
+      .reviewVerification(approve)
+      .accounts({
+        config,
+        role: null,
+        verification: verificationOf(fixture),
+        reviewer: reviewer?.publicKey ?? admin,
+      })
+      .signers(reviewer ? [reviewer] : [])
+      .rpc();
+
+  const suspend = async (fixture: BusinessFixture) => {
+    const business = await program.account.business.fetch(fixture.business);
+    return program.methods
+      .suspendBusiness()
+      .accounts({
+        config,
+        role: null,
+        business: fixture.business,
+        ...(business.isListed
+          ? { registryPage: fixture.registryPage, ...businessIndexPages(program.programId, business) }
+          : { registryPage: null, categoryIndex: null, regionIndex: null }),
+        verification: verificationOf(fixture),
+        reviewer: admin,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  };
+
+  const reinstate = (fixture: BusinessFixture) =>
+    program.methods
+      .reinstateBusiness()
+      .accounts({ config, role: null, verification: verificationOf(fixture), reviewer: admin })
+      .rpc();
+
+  it("moves through request, rejection, resubmission and approval", async () => {
+    const fixture = await createBusiness(program, provider, { name: "Verification Flow" });
+
+    await request(fixture);
+    assert.equal(await status(fixture), "pending");
+    await expectError(request(fixture), "InvalidVerificationTransition");
+
+    // Only the admin or a Verifier can review
+    const stranger = await fundedKeypair(provider, 1);
+    await expectError(review(fixture, true, stranger), "UnauthorizedRole");
+
+    await review(fixture, false);
+    assert.equal(await status(fixture), "rejected");
+    await expectError(review(fixture, true), "InvalidVerificationTransition");
+
+    await request(fixture);
+    await review(fixture, true);
+    assert.equal(await status(fixture), "verified");
+
+    // Verified businesses can't re-enter review, and only suspensions can be lifted
+    await expectError(request(fixture), "InvalidVerificationTransition");
+    await expectError(reinstate(fixture), "InvalidVerificationTransition");
+  });
+
+  it("suspension delists the business and blocks purchases until reinstated", async () => {
+    const treasury = (await fundedKeypair(provider, 1)).publicKey;
+    const fixture = await createBusiness(program, provider, { name: "Suspended Sales", treasury, list: true });
+    const round = await createRound(program, fixture, { index: 0, shares: 10 });
+    const buyer = await fundedKeypair(provider);
+
+    await suspend(fixture);
+    assert.equal(await status(fixture), "suspended");
+    assert.isFalse((await program.account.business.fetch(fixture.business)).isListed);
+    await expectError(buyFromRound(program, fixture, round, buyer, 1), "BusinessSuspended");
+    await expectError(request(fixture), "InvalidVerificationTransition");
+
+    // Never reviewed: reinstating restores Unverified
+    await reinstate(fixture);
+    assert.equal(await status(fixture), "unverified");
+  });
+
+  it("reinstating restores the status held before the suspension", async () => {
+    const rejected = await createBusiness(program, provider, { name: "Suspended Rejected" });
+    await request(rejected);
+    await review(rejected, false);
+    await suspend(rejected);
+    // Suspending twice keeps the original status
+    await suspend(rejected);
+    await reinstate(rejected);
+    assert.equal(await status(rejected), "rejected");
+
+    const verified = await createBusiness(program, provider, { name: "Suspended Verified" });
+    await request(verified);
+    await review(verified, true);
+    await suspend(verified);
+    await reinstate(verified);
+    assert.equal(await status(verified), "verified");
+  });
+
+  it("suspension blocks subscription commitments and claims", async () => {
+    const treasury = (await fundedKeypair(provider, 1)).publicKey;
+    const fixture = await createBusiness(program, provider, { name: "Suspended Subscription", treasury });
+    const startTs = await chainTime(provider);
+    const endTs = startTs + 8;
+    const sale = await createSubscription(program, fixture, { shares: 10, startTs, endTs });
+    const early = await fundedKeypair(provider);
+    const late = await fundedKeypair(provider);
+    await commitToSubscription(program, sale, early, 3 * sale.price);
+
+    await suspend(fixture);
+    await expectError(commitToSubscription(program, sale, late, sale.price), "BusinessSuspended");
+
+    await waitUntil(provider, endTs);
+    await settleSubscription(program, fixture, sale);
+    await expectError(claimAllocation(program, fixture, sale, early), "BusinessSuspended");
+
+    await reinstate(fixture);
+    await claimAllocation(program, fixture, sale, early);
+    assert.equal(await tokenBalance(provider, ata(fixture.shareMint, early.publicKey)), 3);
+  });
+});
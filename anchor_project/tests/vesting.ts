@@ -0,0 +1,152 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { MyProgram } from "../target/types/my_program";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  ASSOCIATED_PROGRAM_ID,
+  ata,
+  BusinessFixture,
+  chainTime,
+  createBusiness,
+  expectError,
+  fundedKeypair,
+  pda,
+  TOKEN_PROGRAM_ID,
+  tokenBalance,
+  waitUntil,
+} from "./helpers";
+
+/**
+ * Vesting schedules: cliff, linear release and revocation of the unvested portion
+ */
+describe("vesting", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.MyProgram as Program<MyProgram>;
+
+  let fixture: BusinessFixture;
+
+  const vestingOf = (beneficiary: PublicKey) => pda(program.programId, "vesting", fixture.business, beneficiary);
+  const vaultOf = (beneficiary: PublicKey) => pda(program.programId, "vesting_vault", vestingOf(beneficiary));
+
+  const grant = (
+    beneficiary: PublicKey,
+    amount: number,
+    startTs: number,
+    cliffSeconds: number,
+    durationSeconds: number,
+    revocable: boolean
+  ) =>
+    program.methods
+      .createVesting(
+        beneficiary,
+        new anchor.BN(amount),
+        new anchor.BN(startTs),
+        new anchor.BN(cliffSeconds),
+        new anchor.BN(durationSeconds),
+        revocable
+      )
+      .accounts({
+        business: fixture.business,
+        vesting: vestingOf(beneficiary),
+        vestingVault: vaultOf(beneficiary),
+        sharesVault: fixture.sharesVault,
+        shareMint: fixture.shareMint,
+        shareMintAuthority: fixture.shareMintAuthority,
+        owner: fixture.owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([fixture.owner])
+      .rpc();
+
+  const release = (beneficiary: Keypair) =>
+    program.methods
+      .releaseVested()
+      .accounts({
+        vesting: vestingOf(beneficiary.publicKey),
+        vestingVault: vaultOf(beneficiary.publicKey),
+        beneficiarySharesAta: ata(fixture.shareMint, beneficiary.publicKey),
+        shareMint: fixture.shareMint,
+        beneficiary: beneficiary.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([beneficiary])
+      .rpc();
+
+  const revoke = (beneficiary: PublicKey) =>
+    program.methods
+      .revokeVesting()
+      .accounts({
+        business: fixture.business,
+        vesting: vestingOf(beneficiary),
+        vestingVault: vaultOf(beneficiary),
+        sharesVault: fixture.sharesVault,
+        owner: fixture.owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([fixture.owner])
+      .rpc();
+
+  before(async () => {
+    fixture = await createBusiness(program, provider, { name: "Vesting Shop" });
+  });
+
+  it("rejects a cliff longer than the schedule", async () => {
+    const beneficiary = Keypair.generate().publicKey;
+    await expectError(grant(beneficiary, 100, await chainTime(provider), 20, 10, false), "InvalidVestingSchedule");
+  });
+
+  it("releases nothing before the cliff, part of the grant after it and the rest at the end", async () => {
+    const founder = await fundedKeypair(provider, 1);
+    const startTs = await chainTime(provider);
+    await grant(founder.publicKey, 1_000, startTs, 4, 20, false);
+    assert.equal(await tokenBalance(provider, vaultOf(founder.publicKey)), 1_000);
+
+    await expectError(release(founder), "NothingToRelease");
+
+    await waitUntil(provider, startTs + 4);
+    await release(founder);
+    const partial = await tokenBalance(provider, ata(fixture.shareMint, founder.publicKey));
+    assert.isAtLeast(partial, 200);
+    assert.isBelow(partial, 1_000);
+    const schedule = await program.account.vestingSchedule.fetch(vestingOf(founder.publicKey));
+    assert.equal(schedule.releasedAmount.toNumber(), partial);
+
+    // A non-revocable schedule stays with the beneficiary
+    await expectError(revoke(founder.publicKey), "VestingNotRevocable");
+
+    await waitUntil(provider, startTs + 20);
+    await release(founder);
+    assert.equal(await tokenBalance(provider, ata(fixture.shareMint, founder.publicKey)), 1_000);
+    assert.equal(await tokenBalance(provider, vaultOf(founder.publicKey)), 0);
+    await expectError(release(founder), "NothingToRelease");
+  });
+
+  it("returns the unvested shares to the shares vault on revocation", async () => {
+    const employee = await fundedKeypair(provider, 1);
+    const startTs = await chainTime(provider);
+    await grant(employee.publicKey, 1_000, startTs, 0, 10_000, true);
+
+    const vaultBefore = await tokenBalance(provider, fixture.sharesVault);
+    await revoke(employee.publicKey);
+
+    const schedule = await program.account.vestingSchedule.fetch(vestingOf(employee.publicKey));
+    const vested = schedule.totalAmount.toNumber();
+    assert.isTrue(schedule.revoked);
+    assert.isBelow(vested, 1_000);
+    assert.equal(await tokenBalance(provider, fixture.sharesVault), vaultBefore + 1_000 - vested);
+    assert.equal(await tokenBalance(provider, vaultOf(employee.publicKey)), vested);
+
+    await expectError(revoke(employee.publicKey), "VestingNotRevocable");
+
+    // What had vested before the revocation is still releasable
+    if (vested > 0) {
+      await release(employee);
+      assert.equal(await tokenBalance(provider, ata(fixture.shareMint, employee.publicKey)), vested);
+    }
+  });
+});